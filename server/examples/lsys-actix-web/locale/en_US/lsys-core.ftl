@@ -3,4 +3,17 @@ utf-parse-error = String is not a valid UTF character, error details:{$msg}
 valid-code-tag-len = Checksum KEY[{$tag}] max length:{$max}
 valid-code-submit-empty = The code is not empty.
 valid-code-not-match = The submitted code [{$code}] does not match.
-wait-notify-timeout=Wait to send timeout
\ No newline at end of file
+wait-notify-timeout=Wait to send timeout
+cron-expr-error = Cron expression [{$expr}] is wrong:{$msg}
+cron-job-exist = Cron job [{$name}] already exists
+task-bad-pk = Task [{$task}] id [{$pk}] is wrong
+task-not-find = Task [{$task}] not found
+config-validate-error = Config validate fail: {$msg}
+valid-code-totp-secret-error = The authenticator secret is invalid
+valid-code-locked = Too many wrong codes, please try again in {$time} seconds
+migrate-checksum-error = Database migration [{$crate_name}:{$version}] has been modified after it was applied
+migrate-missing-error = Database migration [{$crate_name}:{$version}] was applied but is missing from the program
+migrate-pending-error = {$msg} database migrations are pending
+migrate-apply-error = Database migration [{$crate_name}:{$version}] failed:{$msg}
+lock-lost = Lock [{$msg}] has expired or is held by another process
+lock-timeout = Timed out waiting for lock [{$msg}]
@@ -13,4 +13,12 @@ rbac-role-miss-res = role [{$name}:{$id}] does not exist
 rbac-role-miss-res-op = Undiscovered resource [{$name}:{$id}]
 rbac-role-bad-res-user = Non-system roles cannot add user resources that are not part of this role, resource not :{$res}, user is :{$user_id}
 rbac-role-wrong-res-op = Found that the {$res_id} of [{$name}:{$id}] in the system does not match the {$p_res_id} passed in.
-rbac-user-range-bad = Associated roles should be added using a specialized interface.
\ No newline at end of file
+rbac-user-range-bad = Associated roles should be added using a specialized interface.
+rbac-transfer-version = Import data version {$version} is not supported, current version is {$support}
+rbac-transfer-miss-res-op = Resource [{$res}] operation [{$op}] of user [{$user_id}] used by role not found
+rbac-role-parent-miss = Parent role [{$id}] does not exist
+rbac-role-parent-wrong = Role [{$name}:{$id}] cannot be used as parent role, parent role must be a custom role of the same user
+rbac-role-parent-cycle = Role [{$id}] parent roles form a cycle
+rbac-role-parent-depth = Role [{$id}] inherit depth exceeds {$max}
+rbac-transfer-miss-parent = Parent role [{$name}] of role [{$role}] not found
+rbac-role-user-time-wrong = User [{$user_id}] start time {$start_time} must be earlier than timeout {$timeout}
\ No newline at end of file
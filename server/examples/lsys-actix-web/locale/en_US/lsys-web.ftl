@@ -47,3 +47,4 @@ reg-mobile-registered=This cell phone number has already been registered.
 mobile-bind-other-user=Mobile is bound to another account:{$id}
 mobile-is-bind=The mailbox is bound to another account.
 
+rbac-explain-bad-res-user = Resource {$res} does not belong to the current app user
@@ -5,3 +5,17 @@ valid-code-submit-empty = 验证码不为空
 valid-code-not-match = 提交的验证码[{$code}]不匹配
 
 wait-notify-timeout=等待发送超时
+
+cron-expr-error = 定时表达式[{$expr}]错误:{$msg}
+cron-job-exist = 定时任务[{$name}]已存在
+task-bad-pk = 任务[{$task}]的ID[{$pk}]错误
+task-not-find = 任务[{$task}]不存在
+config-validate-error = 配置校验失败:{$msg}
+valid-code-totp-secret-error = 身份验证器密钥错误
+valid-code-locked = 验证码错误次数过多,请在{$time}秒后重试
+migrate-checksum-error = 数据库迁移[{$crate_name}:{$version}]执行后内容已被修改
+migrate-missing-error = 数据库迁移[{$crate_name}:{$version}]已执行但在程序中不存在
+migrate-pending-error = 存在{$msg}个未执行的数据库迁移
+migrate-apply-error = 数据库迁移[{$crate_name}:{$version}]执行失败:{$msg}
+lock-lost = 锁[{$msg}]已过期或被其他进程持有
+lock-timeout = 等待锁[{$msg}]超时
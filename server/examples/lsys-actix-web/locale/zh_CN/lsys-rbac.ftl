@@ -14,4 +14,12 @@ rbac-role-miss-res-op = 未发现的资源[{$name}:{$id}]
 rbac-role-bad-res-user = 非系统角色不能加非本角色用户资源,资源未:{$res},用户为:{$user_id}
 rbac-role-wrong-res-op = 发现系统中的[{$name}:{$id}] 的 {$res_id} 跟传入的 {$p_res_id} 不一致
 rbac-user-range-bad = 关联角色请使用专门的接口添加
-rbac-check-fail = 权限校验失败
\ No newline at end of file
+rbac-check-fail = 权限校验失败
+rbac-transfer-version = 不支持导入数据版本{$version},当前版本为{$support}
+rbac-transfer-miss-res-op = 未找到角色使用的用户[{$user_id}]资源[{$res}]操作[{$op}]
+rbac-role-parent-miss = 父角色[{$id}]不存在
+rbac-role-parent-wrong = 角色[{$name}:{$id}]不能作为父角色,父角色需为同一用户的自定义角色
+rbac-role-parent-cycle = 角色[{$id}]的父角色存在循环继承
+rbac-role-parent-depth = 角色[{$id}]的继承层级超过{$max}
+rbac-transfer-miss-parent = 未找到角色[{$role}]的父角色[{$name}]
+rbac-role-user-time-wrong = 用户[{$user_id}]的生效时间{$start_time}需早于超时时间{$timeout}
\ No newline at end of file
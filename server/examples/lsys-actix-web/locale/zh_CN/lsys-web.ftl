@@ -117,3 +117,4 @@ res-user-address = 收货地址管理
 res-user-external = 关联账号管理
 res-user-password = 账号密码管理
 
+rbac-explain-bad-res-user = 资源 {$res} 不属于当前应用用户
@@ -20,6 +20,7 @@ use super::{ResponseJson, AUTH_COOKIE_NAME};
 
 pub struct UserAuthQuery {
     pub inner: UserAuthQueryDao,
+    #[allow(dead_code)]
     pub req: HttpRequest,
 }
 
@@ -86,6 +87,7 @@ impl FromRequest for UserAuthQuery {
 }
 
 //COOKIE登陆实现[默认实现]
+#[allow(dead_code)]
 pub struct CookieToken<'t> {
     request_dao: &'t UserAuthQuery,
 }
@@ -95,6 +97,7 @@ impl<'t> From<&'t UserAuthQuery> for CookieToken<'t> {
     }
 }
 impl<'t> CookieToken<'t> {
+    #[allow(dead_code)]
     pub fn set_token(&self, token: SessionToken<UserAuthTokenData>) {
         self.request_dao
             .req
@@ -127,6 +130,7 @@ impl<'t> RequestSessionToken<UserAuthTokenData> for CookieToken<'t> {
 //oauth 登陆实现，跟普通登陆实现方式不相同
 pub struct OauthAuthQuery {
     pub inner: RestAuthQueryDao,
+    #[allow(dead_code)]
     pub req: HttpRequest,
 }
 
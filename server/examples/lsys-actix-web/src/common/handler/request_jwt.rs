@@ -119,6 +119,7 @@ impl Future for JwtExtractFut {
 //jwt 登陆信息实现，服务器端处理跟cookie相同
 pub struct JwtQuery {
     pub token_data: TokenData<JwtClaims>,
+    #[allow(dead_code)]
     pub token_str: String,
 }
 
@@ -17,6 +17,7 @@ use super::ResponseJson;
 
 pub struct ReqQuery {
     pub inner: RequestDao,
+    #[allow(dead_code)]
     pub req: HttpRequest,
 }
 
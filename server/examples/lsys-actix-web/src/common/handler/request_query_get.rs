@@ -1,3 +1,4 @@
+#![allow(dead_code)]
 use actix_web::error::ErrorBadRequest;
 use actix_web::web::Query;
 use actix_web::Result;
@@ -341,6 +341,7 @@ impl FromRequest for RestQuery {
                         method: get_param.method,
                         token: get_param.token,
                     };
+                    tracing::Span::current().record("app_id", rfc.app_id.as_str());
                     if !json_req {
                         if let Some(pl) = get_param.payload {
                             if !pl.is_empty() {
@@ -44,11 +44,7 @@ impl Responder for ResponseJson {
             if token.is_ok() {
                 if let Some(data) = token.data() {
                     let now_t = now_time().unwrap_or_default();
-                    let age = if data.time_out > now_t {
-                        data.time_out - now_t
-                    } else {
-                        0
-                    };
+                    let age = data.time_out.saturating_sub(now_t);
                     let cookie = Cookie::build(AUTH_COOKIE_NAME, data.to_string())
                         //.domain("www.rust-lang.org")
                         //.secure(true)
@@ -5,6 +5,7 @@ use actix_web::{
     Error,
 };
 use futures_util::ready;
+use lsys_core::TraceContext;
 use reqwest::header::{HeaderName, HeaderValue};
 use std::{
     future::Future,
@@ -12,6 +13,8 @@ use std::{
     pin::Pin,
     task::{Context, Poll},
 };
+use tokio::task::futures::TaskLocalFuture;
+use tracing::instrument::{Instrument, Instrumented};
 pub struct RequestID {
     name: &'static str,
 }
@@ -67,7 +70,15 @@ where
                 req.headers_mut().insert(name, hval);
             }
         }
-        let fut = self.service.call(req);
+        //请求日志span,user_id及app_id在获取到登录用户或应用后写入
+        let span = tracing::info_span!(
+            "request",
+            request_id = req_id.as_str(),
+            user_id = tracing::field::Empty,
+            app_id = tracing::field::Empty,
+        );
+        //请求内产生的通知消息及后台任务通过追踪上下文关联该请求ID
+        let fut = TraceContext::new(&req_id).with(self.service.call(req).instrument(span));
         DefaultHeaderFuture {
             fut,
             name: self.name,
@@ -80,7 +91,7 @@ where
 #[pin_project::pin_project]
 pub struct DefaultHeaderFuture<S: Service<ServiceRequest>, B> {
     #[pin]
-    fut: S::Future,
+    fut: TaskLocalFuture<TraceContext, Instrumented<S::Future>>,
     name: &'static str,
     req_id: String,
     _body: PhantomData<B>,
@@ -12,7 +12,7 @@ use lsys_web::handler::api::app::{
 };
 
 #[post("/{method}")]
-pub(crate) async fn app<'t>(
+pub(crate) async fn app(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
@@ -12,6 +12,7 @@ mod barcode;
 
 mod sender;
 mod site;
+mod system;
 mod user;
 
 #[options("/{_:.*}")]
@@ -69,6 +70,7 @@ where
     .service(user::res)
     .service(user::role)
     .service(user::access)
+    .service(user::transfer)
     .service(user::reg)
     .service(user::oauth)
     .service(options);
@@ -92,6 +94,15 @@ where
             .service(site::system_info)
             .service(options),
     )
+    .service(
+        scope("/api/system")
+            .service(system::system_task)
+            .service(system::system_node)
+            .service(system::system_cache)
+            .service(system::system_config)
+            .service(system::system_fluent)
+            .service(options),
+    )
     .service(scope("/api/app").service(app::app).service(options))
     .service(
         scope("/api/sender")
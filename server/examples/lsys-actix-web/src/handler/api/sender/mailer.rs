@@ -15,7 +15,7 @@ use lsys_web::handler::api::sender::{
     TplConfigDelParam, TplConfigListParam,
 };
 #[post("/mailer/{method}")]
-pub(crate) async fn sender_mailer<'t>(
+pub(crate) async fn sender_mailer(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
@@ -29,7 +29,7 @@ use lsys_web::handler::api::sender::{
     TplConfigListParam,
 };
 #[post("/smser/{method}")]
-pub(crate) async fn sender_smser<'t>(
+pub(crate) async fn sender_smser(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
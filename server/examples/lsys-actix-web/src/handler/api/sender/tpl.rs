@@ -7,7 +7,7 @@ use lsys_web::handler::api::sender::{
     TplEditParam, TplListParam,
 };
 #[post("/tpls/{method}")]
-pub(crate) async fn sender_tpl_body<'t>(
+pub(crate) async fn sender_tpl_body(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
@@ -0,0 +1,118 @@
+use crate::common::handler::{
+    JsonQuery, JwtQuery, ResponseJson, ResponseJsonResult, UserAuthQuery,
+};
+use actix_web::post;
+
+use lsys_web::handler::api::system::{
+    system_cache_clear, system_cache_list, system_config_reload, system_fluent_keys,
+    system_fluent_override_del, system_fluent_override_list, system_fluent_override_set,
+    system_fluent_reload, system_node_list, system_task_list, system_task_release,
+    SystemCacheClearParam, SystemConfigReloadParam, SystemFluentKeysParam,
+    SystemFluentOverrideDelParam, SystemFluentOverrideSetParam, SystemFluentReloadParam,
+    SystemTaskReleaseParam,
+};
+
+//后台任务运行状态
+#[post("/task/{type}")]
+pub async fn system_task(
+    path: actix_web::web::Path<String>,
+    jwt: JwtQuery,
+    auth_dao: UserAuthQuery,
+    json_param: JsonQuery,
+) -> ResponseJsonResult<ResponseJson> {
+    auth_dao.set_request_token(&jwt).await;
+    let res = match path.into_inner().as_str() {
+        "list" => system_task_list(&auth_dao).await,
+        "release" => {
+            system_task_release(json_param.param::<SystemTaskReleaseParam>()?, &auth_dao).await
+        }
+        name => handler_not_found!(name),
+    };
+    Ok(res?.into())
+}
+
+//集群节点
+#[post("/node/{type}")]
+pub async fn system_node(
+    path: actix_web::web::Path<String>,
+    jwt: JwtQuery,
+    auth_dao: UserAuthQuery,
+) -> ResponseJsonResult<ResponseJson> {
+    auth_dao.set_request_token(&jwt).await;
+    let res = match path.into_inner().as_str() {
+        "list" => system_node_list(&auth_dao).await,
+        name => handler_not_found!(name),
+    };
+    Ok(res?.into())
+}
+
+//本地缓存
+#[post("/cache/{type}")]
+pub async fn system_cache(
+    path: actix_web::web::Path<String>,
+    jwt: JwtQuery,
+    auth_dao: UserAuthQuery,
+    json_param: JsonQuery,
+) -> ResponseJsonResult<ResponseJson> {
+    auth_dao.set_request_token(&jwt).await;
+    let res = match path.into_inner().as_str() {
+        "list" => system_cache_list(&auth_dao).await,
+        "clear" => {
+            system_cache_clear(json_param.param::<SystemCacheClearParam>()?, &auth_dao).await
+        }
+        name => handler_not_found!(name),
+    };
+    Ok(res?.into())
+}
+
+//配置重新加载
+#[post("/config/{type}")]
+pub async fn system_config(
+    path: actix_web::web::Path<String>,
+    jwt: JwtQuery,
+    auth_dao: UserAuthQuery,
+    json_param: JsonQuery,
+) -> ResponseJsonResult<ResponseJson> {
+    auth_dao.set_request_token(&jwt).await;
+    let res = match path.into_inner().as_str() {
+        "reload" => {
+            system_config_reload(json_param.param::<SystemConfigReloadParam>()?, &auth_dao).await
+        }
+        name => handler_not_found!(name),
+    };
+    Ok(res?.into())
+}
+
+//翻译管理
+#[post("/fluent/{type}")]
+pub async fn system_fluent(
+    path: actix_web::web::Path<String>,
+    jwt: JwtQuery,
+    auth_dao: UserAuthQuery,
+    json_param: JsonQuery,
+) -> ResponseJsonResult<ResponseJson> {
+    auth_dao.set_request_token(&jwt).await;
+    let res = match path.into_inner().as_str() {
+        "keys" => system_fluent_keys(json_param.param::<SystemFluentKeysParam>()?, &auth_dao).await,
+        "override_list" => system_fluent_override_list(&auth_dao).await,
+        "override_set" => {
+            system_fluent_override_set(
+                json_param.param::<SystemFluentOverrideSetParam>()?,
+                &auth_dao,
+            )
+            .await
+        }
+        "override_del" => {
+            system_fluent_override_del(
+                json_param.param::<SystemFluentOverrideDelParam>()?,
+                &auth_dao,
+            )
+            .await
+        }
+        "reload" => {
+            system_fluent_reload(json_param.param::<SystemFluentReloadParam>()?, &auth_dao).await
+        }
+        name => handler_not_found!(name),
+    };
+    Ok(res?.into())
+}
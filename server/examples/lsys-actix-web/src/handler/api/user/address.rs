@@ -8,7 +8,7 @@ use lsys_web::handler::api::user::{
 };
 
 #[post("address/{method}")]
-pub(crate) async fn address<'t>(
+pub(crate) async fn address(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
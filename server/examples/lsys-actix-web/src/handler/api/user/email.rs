@@ -9,7 +9,7 @@ use lsys_web::handler::api::user::{
 };
 
 #[post("email_confirm")]
-pub(crate) async fn email_confirm<'t>(
+pub(crate) async fn email_confirm(
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
 ) -> ResponseJsonResult<ResponseJson> {
@@ -21,7 +21,7 @@ pub(crate) async fn email_confirm<'t>(
 }
 
 #[post("email/{method}")]
-pub(crate) async fn email<'t>(
+pub(crate) async fn email(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
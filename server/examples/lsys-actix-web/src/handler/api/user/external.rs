@@ -32,7 +32,7 @@ pub struct ExternalBindCheckParam {
 }
 
 #[post("external/{method}")]
-pub(crate) async fn external<'t>(
+pub(crate) async fn external(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
@@ -8,7 +8,7 @@ use lsys_web::handler::api::user::{
 };
 
 #[post("info/{method}")]
-pub(crate) async fn set_info<'t>(
+pub(crate) async fn set_info(
     jwt: JwtQuery,
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
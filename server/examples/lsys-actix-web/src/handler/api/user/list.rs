@@ -7,7 +7,7 @@ use lsys_web::handler::api::user::{
 };
 
 #[post("list/{method}")]
-pub(crate) async fn user_list<'t>(
+pub(crate) async fn user_list(
     jwt: JwtQuery,
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
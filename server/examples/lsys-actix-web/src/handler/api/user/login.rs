@@ -70,7 +70,7 @@ async fn jwt_login_data(
 }
 
 #[post("/login/{type}")]
-pub(crate) async fn login<'t>(
+pub(crate) async fn login(
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
@@ -118,7 +118,7 @@ pub(crate) async fn login<'t>(
 }
 
 #[post("/login_data")]
-pub(crate) async fn user_data<'t>(
+pub(crate) async fn user_data(
     jwt: JwtQuery,
     auth_dao: UserAuthQuery,
     json_param: JsonQuery,
@@ -132,7 +132,7 @@ pub(crate) async fn user_data<'t>(
 }
 
 #[post("/login_history")]
-pub async fn login_history<'t>(
+pub async fn login_history(
     auth_dao: UserAuthQuery,
     json_param: JsonQuery,
     jwt: JwtQuery,
@@ -143,7 +143,7 @@ pub async fn login_history<'t>(
 }
 
 #[get("/logout")]
-pub async fn logout<'t>(
+pub async fn logout(
     jwt: JwtQuery,
     auth_dao: UserAuthQuery,
 ) -> ResponseJsonResult<ResponseJson> {
@@ -260,6 +260,7 @@ pub async fn external_state_callback(
 }
 
 //外部登录完成回调地址,不包含扫码登录,目前没用到
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct ExternalCallbackParam {
     pub login_type: String,
@@ -268,7 +269,7 @@ pub struct ExternalCallbackParam {
 }
 
 #[post("/external_login_callback")]
-pub async fn external_login_callback<'t>(
+pub async fn external_login_callback(
     json_param: JsonQuery,
     _auth_dao: UserAuthQuery,
 ) -> ResponseJsonResult<ResponseJson> {
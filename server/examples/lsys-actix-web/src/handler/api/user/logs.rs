@@ -5,7 +5,7 @@ use actix_web::post;
 use lsys_web::handler::api::user::{change_logs_list, ChangeLogsListParam};
 
 #[post("logs/{method}")]
-pub(crate) async fn user_logs<'t>(
+pub(crate) async fn user_logs(
     jwt: JwtQuery,
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
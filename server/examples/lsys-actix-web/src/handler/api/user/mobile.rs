@@ -9,7 +9,7 @@ use lsys_web::handler::api::user::{
 };
 
 #[post("mobile/{method}")]
-pub(crate) async fn mobile<'t>(
+pub(crate) async fn mobile(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
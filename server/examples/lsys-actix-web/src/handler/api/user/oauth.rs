@@ -7,7 +7,7 @@ use lsys_web::handler::oauth::{
 };
 
 #[post("oauth/{method}")]
-pub(crate) async fn oauth<'t>(
+pub(crate) async fn oauth(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
@@ -10,7 +10,7 @@ use lsys_web::handler::api::user::{
 };
 
 #[post("password_reset/{method}")]
-pub(crate) async fn password_reset<'t>(
+pub(crate) async fn password_reset(
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
@@ -50,7 +50,7 @@ pub(crate) async fn password_reset<'t>(
 }
 
 #[post("password/{method}")]
-pub(crate) async fn password<'t>(
+pub(crate) async fn password(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
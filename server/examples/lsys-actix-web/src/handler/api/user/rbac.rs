@@ -5,27 +5,32 @@ use actix_web::post;
 
 use lsys_web::handler::access::res_tpls;
 use lsys_web::handler::api::rbac::{
-    rbac_all_res_list, RbacAccessParam, RbacMenuParam, ResAddParam, ResDeleteParam, ResEditParam,
-    ResListDataParam, RoleRelationDataParam,
+    rbac_all_res_list, RbacAccessParam, RbacExplainParam, RbacExportParam, RbacImportParam,
+    RbacMenuParam, RbacOpAccessParam, ResAddParam, ResDeleteParam, ResEditParam, ResListDataParam,
+    RoleRelationDataParam,
 };
 use lsys_web::handler::api::rbac::{ResAllParam, ResTagsParam, RoleOptionsParam, RoleTagsParam};
 use lsys_web::handler::api::rbac::{
     RoleAddParam, RoleAddUserParam, RoleDeleteParam, RoleDeleteUserParam, RoleEditParam,
-    RoleListDataParam, RoleListUserParam,
+    RoleExpiringUserParam, RoleListDataParam, RoleListUserParam, RoleParentParam,
+    RoleSetParentParam,
 };
 use lsys_web::handler::api::user::{
-    user_access_check, user_menu_check, user_res_tags, user_role_options, user_role_tags,
+    user_access_check, user_access_explain, user_menu_check, user_op_access, user_res_tags,
+    user_role_options, user_role_tags,
 };
 use lsys_web::handler::api::user::{
-    user_relation_data, user_role_add, user_role_add_user, user_role_delete, user_role_delete_user,
-    user_role_edit, user_role_list_data, user_role_list_user,
+    user_rbac_export, user_rbac_import, user_res_add, user_res_delete, user_res_edit,
+    user_res_list_data,
 };
 use lsys_web::handler::api::user::{
-    user_res_add, user_res_delete, user_res_edit, user_res_list_data,
+    user_relation_data, user_role_add, user_role_add_user, user_role_delete, user_role_delete_user,
+    user_role_edit, user_role_expiring_user, user_role_list_data, user_role_list_user,
+    user_role_parent, user_role_set_parent,
 };
 
 #[post("/res/{method}")]
-pub async fn res<'t>(
+pub async fn res(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
@@ -47,7 +52,7 @@ pub async fn res<'t>(
 }
 
 #[post("/role/{method}")]
-pub async fn role<'t>(
+pub async fn role(
     jwt: JwtQuery,
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
@@ -68,18 +73,25 @@ pub async fn role<'t>(
         "list_data" => {
             user_role_list_data(json_param.param::<RoleListDataParam>()?, &auth_dao).await
         }
+        "expiring_user" => {
+            user_role_expiring_user(json_param.param::<RoleExpiringUserParam>()?, &auth_dao).await
+        }
         "options" => user_role_options(json_param.param::<RoleOptionsParam>()?, &auth_dao).await,
         "relation" => {
             user_relation_data(json_param.param::<RoleRelationDataParam>()?, &auth_dao).await
         }
         "tags" => user_role_tags(json_param.param::<RoleTagsParam>()?, &auth_dao).await,
+        "set_parent" => {
+            user_role_set_parent(json_param.param::<RoleSetParentParam>()?, &auth_dao).await
+        }
+        "parent" => user_role_parent(json_param.param::<RoleParentParam>()?, &auth_dao).await,
         name => Err(lsys_web::JsonData::message(name).set_sub_code("method_not_found")),
     };
     Ok(data?.into())
 }
 
 #[post("/access/{method}")]
-pub async fn access<'t>(
+pub async fn access(
     jwt: JwtQuery,
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
@@ -89,7 +101,25 @@ pub async fn access<'t>(
     let data = match path.into_inner().as_str() {
         "check" => user_access_check(json_param.param::<RbacAccessParam>()?, &auth_dao).await,
         "menu" => user_menu_check(json_param.param::<RbacMenuParam>()?, &auth_dao).await,
+        "explain" => user_access_explain(json_param.param::<RbacExplainParam>()?, &auth_dao).await,
+        "op_access" => user_op_access(json_param.param::<RbacOpAccessParam>()?, &auth_dao).await,
         name => Err(lsys_web::JsonData::message(name).set_sub_code("method_not_found")),
     };
     Ok(data?.into())
 }
+
+#[post("/transfer/{method}")]
+pub async fn transfer(
+    jwt: JwtQuery,
+    path: actix_web::web::Path<String>,
+    json_param: JsonQuery,
+    auth_dao: UserAuthQuery,
+) -> ResponseJsonResult<ResponseJson> {
+    auth_dao.set_request_token(&jwt).await;
+    let data = match path.into_inner().as_str() {
+        "export" => user_rbac_export(json_param.param::<RbacExportParam>()?, &auth_dao).await,
+        "import" => user_rbac_import(json_param.param::<RbacImportParam>()?, &auth_dao).await,
+        name => handler_not_found!(name),
+    };
+    Ok(data?.into())
+}
@@ -7,7 +7,7 @@ use lsys_web::handler::api::user::{
 };
 
 #[post("/signup/{type}")]
-pub(crate) async fn reg<'t>(
+pub(crate) async fn reg(
     path: actix_web::web::Path<String>,
     json_param: JsonQuery,
     auth_dao: UserAuthQuery,
@@ -1,6 +1,8 @@
 use crate::common::handler::{ResponseJson, ResponseJsonResult, RestQuery};
 use actix_web::post;
-use lsys_web::handler::app::{app_rbac_check, app_rbac_menu_check, CheckParam, MenuParam};
+use lsys_web::handler::app::{
+    app_rbac_check, app_rbac_explain, app_rbac_menu_check, CheckParam, ExplainParam, MenuParam,
+};
 
 #[post("access")]
 pub(crate) async fn access(mut rest: RestQuery) -> ResponseJsonResult<ResponseJson> {
@@ -13,6 +15,10 @@ pub(crate) async fn access(mut rest: RestQuery) -> ResponseJsonResult<ResponseJs
             let param = rest.param::<MenuParam>()?;
             app_rbac_menu_check(&rest, &rest.to_app_model().await?, param).await
         }
+        Some("explain") => {
+            let param = rest.param::<ExplainParam>()?;
+            app_rbac_explain(&rest, &rest.to_app_model().await?, param).await
+        }
         var => handler_not_found!(var.unwrap_or_default()),
     }?
     .into())
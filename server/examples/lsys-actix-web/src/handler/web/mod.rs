@@ -24,7 +24,7 @@ where
         .unwrap_or_else(|_| "/ui".to_string());
     if let Ok(ui_config) = app_dao
         .app_core
-        .config_path(app_dao.app_core.config.find(None), "ui_dir")
+        .config_path(&app_dao.app_core.config.find(None), "ui_dir")
     {
         if ui_config.exists() {
             let ui_path_full = ui_path.trim_matches('/').to_string() + "/";
@@ -7,7 +7,7 @@ use futures_util::TryFutureExt;
 use handler::router_main;
 use jsonwebtoken::{DecodingKey, Validation};
 use lsys_core::{fluent_message, AppCore, AppCoreError};
-use lsys_web::dao::WebDao;
+use lsys_web::dao::{web_migrate, WebDao};
 use lsys_web::FluentFormat;
 use rustls::server::ServerConfig;
 use rustls::{Certificate, PrivateKey};
@@ -86,15 +86,71 @@ fn load_rustls_config(
     Ok(config.with_single_cert(cert_chain, PrivateKey(key))?)
 }
 
+//等待服务停止信号
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {},
+                    _ = term.recv() => {},
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// 执行数据库迁移命令
+/// * `cmd` - status 全部迁移状态 pending 未执行的迁移 verify 校验已执行的迁移 apply 执行未执行的迁移 baseline 标记全部迁移为已执行,用于已有数据库
+pub async fn run_migrate(app_dir: &str, cmd: &str) -> Result<(), AppError> {
+    let mut app_core = AppCore::init(app_dir, "config", None).await?;
+    app_core.init_tracing()?;
+    let db = app_core.create_db().await?;
+    let migrate = web_migrate(&app_core, db);
+    let list = match cmd {
+        "status" => migrate.status().await,
+        "pending" => migrate.pending().await,
+        "verify" => migrate.verify().await.map(|_| vec![]),
+        "apply" => migrate.apply().await,
+        "baseline" => migrate.baseline().await,
+        _ => {
+            return Err(AppError::AppCore(AppCoreError::System(format!(
+                "unknown migrate command:{}, support: status pending verify apply baseline",
+                cmd
+            ))))
+        }
+    }
+    .map_err(AppCoreError::from)?;
+    for item in list {
+        println!(
+            "{}\t{:04}_{}\t{:?}\t{}",
+            item.crate_name, item.version, item.name, item.state, item.checksum
+        );
+    }
+    Ok(())
+}
+
 pub async fn create_server(app_dir: &str) -> Result<Server, AppError> {
     let mut app_core = AppCore::init(app_dir, "config", None).await?;
     app_core.init_tracing()?;
     let app_core = Arc::new(app_core);
     //console_subscriber::init();
     let app_dao = Data::new(WebDao::new(app_core.clone()).await?);
+    //需要订阅领域事件时在此调用 app_dao.event_bus.subscribe 添加
+    app_dao.event_bus.start();
     let bind_addr = app_dao.bind_addr();
     let bind_ssl_data = app_dao.bind_ssl_data();
     let is_redirect_http=bind_ssl_data.is_some();
+    let shutdown_dao = app_dao.clone();
     let app_jwt_key = app_dao
         .app_core
         .config
@@ -170,6 +226,13 @@ pub async fn create_server(app_dir: &str) -> Result<Server, AppError> {
             )
             .map_err(AppCoreError::Io)?;
     }
-    let s = server.run();
+    //自行处理停止信号,先停止后台任务在停止HTTP服务
+    let s = server.disable_signals().run();
+    let server_handle = s.handle();
+    actix_web::rt::spawn(async move {
+        shutdown_signal().await;
+        shutdown_dao.shutdown().await;
+        server_handle.stop(true).await;
+    });
     Ok(s)
 }
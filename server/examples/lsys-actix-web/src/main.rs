@@ -1,4 +1,4 @@
-use lsys_actix_web::create_server;
+use lsys_actix_web::{create_server, run_migrate};
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -10,5 +10,15 @@ async fn main() {
         //on dev
         env!("CARGO_MANIFEST_DIR")
     };
+    //数据库迁移: lsys-actix-web migrate [status|pending|verify|apply|baseline]
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.get(1).map(|e| e.as_str()) == Some("migrate") {
+        let cmd = args.get(2).map(|e| e.as_str()).unwrap_or("status");
+        if let Err(err) = run_migrate(app_dir, cmd).await {
+            eprintln!("migrate fail:{}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
     create_server(app_dir).await.unwrap().await.unwrap();
 }
@@ -89,7 +89,7 @@ impl WeChatLib {
         params: Vec<(String, String)>,
     ) -> Result<String, String> {
         let mut http_url = Url::parse(http_url).map_err(|e| e.to_string())?;
-        http_url.query_pairs_mut().extend_pairs(params.into_iter());
+        http_url.query_pairs_mut().extend_pairs(params);
         let client = reqwest::Client::builder();
         let client = client
             .timeout(Duration::from_secs(60))
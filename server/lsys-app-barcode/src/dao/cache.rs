@@ -2,7 +2,7 @@ use std::{str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
 use image::{ImageBuffer, Rgb};
-use lsys_core::cache::{LocalCache, LocalCacheClearItem};
+use lsys_core::cache::{LocalCache, LocalCacheClearItem, LocalCacheStats};
 
 use crate::model::BarcodeCreateModel;
 
@@ -44,4 +44,34 @@ impl LocalCacheClearItem for BarCodeCacheClear {
         };
         Ok(())
     }
+    async fn clear_all_from_message(&self) {
+        match self {
+            Self::CreateModel(cache) => cache.del_all().await,
+            Self::CreateBuffer(cache) => cache.del_all().await,
+        }
+    }
+    async fn clear(&self, key: Option<&str>) -> Result<(), String> {
+        match (self, key) {
+            (Self::CreateModel(cache), Some(key)) => {
+                let key = &u64::from_str(key).map_err(|e| e.to_string())?;
+                cache.clear(key).await
+            }
+            (Self::CreateBuffer(cache), Some(key)) => cache.clear(&key.to_owned()).await,
+            (Self::CreateModel(cache), None) => cache.clear_all().await,
+            (Self::CreateBuffer(cache), None) => cache.clear_all().await,
+        };
+        Ok(())
+    }
+    async fn stats(&self) -> LocalCacheStats {
+        match self {
+            Self::CreateModel(cache) => cache.stats().await,
+            Self::CreateBuffer(cache) => cache.stats().await,
+        }
+    }
+    async fn set_enable(&self, enable: bool) {
+        match self {
+            Self::CreateModel(cache) => cache.set_enable(enable).await,
+            Self::CreateBuffer(cache) => cache.set_enable(enable).await,
+        }
+    }
 }
@@ -0,0 +1,3 @@
+-- ----------- lsys-app-notify 0002 ---------------
+ALTER TABLE `yaf_notify_data`
+ADD COLUMN `request_id` varchar(32) NOT NULL DEFAULT '' COMMENT '请求ID' AFTER `try_num`;
@@ -1,8 +1,10 @@
 mod notify;
+mod notify_event;
 mod notify_task;
 mod record;
 
 pub use notify::*;
+pub use notify_event::*;
 pub use notify_task::*;
 pub use record::*;
 mod result;
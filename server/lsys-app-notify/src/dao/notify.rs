@@ -5,7 +5,7 @@ use std::{
 
 
 use lsys_app::dao::app::Apps;
-use lsys_core::{AppCore, TaskDispatch};
+use lsys_core::{AppCore, IntoFluentMessage, TaskBackendConfig, TaskDispatch, TaskMonitorItem};
 use lsys_logger::dao::ChangeLogger;
 use sqlx::Pool;
 use tracing::warn;
@@ -22,12 +22,10 @@ pub trait NotifyData {
     fn app_id(&self) -> &u64;
 }
 pub struct Notify {
-    app_core: Arc<AppCore>,
     db: Pool<sqlx::MySql>,
     apps: Arc<Apps>,
     pub record: Arc<NotifyRecord>,
-    task: TaskDispatch<u64, NotifyTaskItem>,
-    redis: deadpool_redis::Pool,
+    task: Arc<TaskDispatch<u64, NotifyTaskItem>>,
     max_try: u16,
 }
 
@@ -59,21 +57,21 @@ impl Notify {
             None => NOTIFY_MIN_DELAY_TIME as usize,
         };
 
-        let task = TaskDispatch::new(
-            format!("{}-notify", NOTIFY_REDIS_PREFIX),
-            format!("{}-notify-read-lock", NOTIFY_REDIS_PREFIX),
-            format!("{}-notify-run-task", NOTIFY_REDIS_PREFIX),
+        let task = Arc::new(TaskDispatch::new(
+            TaskBackendConfig::new(app_core, redis).create(
+                format!("{}-notify", NOTIFY_REDIS_PREFIX),
+                format!("{}-notify-read-lock", NOTIFY_REDIS_PREFIX),
+                format!("{}-notify-run-task", NOTIFY_REDIS_PREFIX),
+            ),
             task_size,
             task_timeout,
             is_check,
             task_timeout,
-        );
+        ));
         Self {
-            app_core,
             db,
             record,
             task,
-            redis,
             apps,
             max_try: max_try.unwrap_or(5),
         }
@@ -84,19 +82,24 @@ impl Notify {
     }
     pub async fn add(&self, method: &str, app_id: &u64, data: &str) -> NotifyResult<u64> {
         let id = self.record.add(method, app_id, data).await?;
-        let mut redis = self.redis.get().await?;
-        if let Err(err) = self.task.notify(&mut redis).await {
-            warn!("add notify task fail :{}", err)
+        if let Err(err) = self.task.notify().await {
+            warn!(
+                "add notify task fail :{}",
+                err.to_fluent_message().default_format()
+            )
         }
         Ok(id)
     }
 
+    //回调任务,用于查看运行状态
+    pub fn task_monitor(&self) -> Arc<dyn TaskMonitorItem> {
+        self.task.clone()
+    }
     //后台发送任务，内部循环不退出
     pub async fn task(&self) -> NotifyResult<()> {
         let acquisition = NotifyTaskAcquisition::new(self.db.clone());
         self.task
             .dispatch(
-                self.app_core.clone(),
                 &acquisition,
                 NotifyTask::new(
                     self.db.clone(),
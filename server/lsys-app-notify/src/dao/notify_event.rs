@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use lsys_core::IntoFluentMessage;
+use lsys_logger::{dao::EventSubscriber, model::EventOutboxModel};
+
+use super::{Notify, NotifyError};
+
+// 将领域事件转发给外部应用
+// 仅转发属于应用的事件,该应用配置了 event_{事件类型} 的回调地址时转发,未配置时忽略
+// 事件可能重复投递,应用需按事件数据自行去重
+pub struct NotifyEventSubscriber {
+    notify: Arc<Notify>,
+    event_types: Vec<String>,
+}
+
+impl NotifyEventSubscriber {
+    /// * `event_types` - 转发的事件类型,事件数据原样发送给应用,不要转发包含敏感数据的事件
+    pub fn new(notify: Arc<Notify>, event_types: Vec<String>) -> Self {
+        Self {
+            notify,
+            event_types,
+        }
+    }
+    /// 事件对应的回调方法名
+    pub fn event_method(event_type: &str) -> String {
+        format!("event_{}", event_type)
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for NotifyEventSubscriber {
+    fn name(&self) -> &str {
+        "app-notify"
+    }
+    fn accept(&self, event_type: &str) -> bool {
+        self.event_types.iter().any(|e| e == event_type)
+    }
+    async fn handle(&self, event: &EventOutboxModel) -> Result<(), String> {
+        if event.app_id == 0 {
+            return Ok(());
+        }
+        let method = Self::event_method(&event.event_type);
+        match self
+            .notify
+            .record
+            .find_config_by_app(&event.app_id, &method)
+            .await
+        {
+            Ok(_) => {}
+            Err(NotifyError::Sqlx(sqlx::Error::RowNotFound)) => return Ok(()),
+            Err(err) => return Err(err.to_fluent_message().default_format()),
+        };
+        self.notify
+            .add(&method, &event.app_id, &event.event_data)
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?;
+        Ok(())
+    }
+}
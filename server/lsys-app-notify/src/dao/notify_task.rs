@@ -9,6 +9,7 @@ use sqlx::{MySql, Pool};
 
 use lsys_core::{
     now_time, IntoFluentMessage, TaskAcquisition, TaskData, TaskExecutor, TaskItem, TaskRecord,
+    TraceContext,
 };
 use sqlx_model::{sql_format, ModelTableName, Select, SqlExpr};
 use tracing::warn;
@@ -23,6 +24,9 @@ impl TaskItem<u64> for NotifyTaskItem {
     fn to_task_pk(&self) -> u64 {
         self.0.id
     }
+    fn to_trace_context(&self) -> TraceContext {
+        TraceContext::new(&self.0.request_id)
+    }
 }
 
 pub struct NotifyTaskAcquisition {
@@ -4,7 +4,7 @@ use std::time::Duration;
 use crate::model::{
     NotifyConfigModel, NotifyConfigModelRef, NotifyDataModel, NotifyDataModelRef, NotifyDataStatus,
 };
-use lsys_core::{fluent_message, now_time, LimitParam, RequestEnv};
+use lsys_core::{fluent_message, now_time, LimitParam, RequestEnv, TraceContext};
 
 use reqwest::Method;
 use serde::Serialize;
@@ -156,6 +156,8 @@ impl NotifyRecord {
         let payload = data.to_owned();
         let create_time = now_time().unwrap_or_default();
         let status = NotifyDataStatus::Init as i8;
+        //推送时使用产生该推送的请求追踪上下文
+        let request_id = TraceContext::current().request_id;
         let res =
             Insert::<sqlx::MySql, NotifyDataModel, _>::new(model_option_set!(NotifyDataModelRef ,{
                 app_id: *app_id,
@@ -163,6 +165,7 @@ impl NotifyRecord {
                 payload: payload,
                 status: status,
                 try_num: 0,
+                request_id: request_id,
                 create_time: create_time,
             }))
             .execute(&self.db)
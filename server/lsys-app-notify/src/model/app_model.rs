@@ -69,6 +69,10 @@ pub struct NotifyDataModel {
     #[sqlx(default)]
     pub try_num: i8,
 
+    /// 产生推送的请求ID
+    #[sqlx(default)]
+    pub request_id: String,
+
     /// 最后推送时间
     #[sqlx(default)]
     pub publish_time: u64,
@@ -0,0 +1,3 @@
+-- ----------- lsys-app-sender 0002 ---------------
+ALTER TABLE `yaf_sender_log`
+ADD COLUMN `request_id` varchar(32) NOT NULL DEFAULT '' COMMENT '请求ID' AFTER `message`;
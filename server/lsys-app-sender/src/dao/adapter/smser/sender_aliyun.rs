@@ -365,7 +365,7 @@ impl<'t> SmsSendNotifyParse for AliYunNotify<'t> {
         items: &[SendNotifyItem],
         msg: Vec<SenderSmsMessageModel>,
     ) -> Result<Vec<(Option<SenderSmsMessageModel>, SendNotifyItem)>, String> {
-        return Ok(items
+        Ok(items
             .iter()
             .map(|e| {
                 let tmp = msg
@@ -377,7 +377,7 @@ impl<'t> SmsSendNotifyParse for AliYunNotify<'t> {
                     .map(|t| t.to_owned());
                 (tmp, e.to_owned())
             })
-            .collect::<Vec<_>>());
+            .collect::<Vec<_>>())
     }
 }
 
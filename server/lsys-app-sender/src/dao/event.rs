@@ -0,0 +1,23 @@
+use lsys_logger::dao::EventData;
+use serde::{Deserialize, Serialize};
+
+//短信或邮件发送失败,每次发送失败产生一个事件
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SenderFailEvent {
+    pub app_id: u64,
+    pub sender_type: i8,
+    pub message_id: u64,
+    pub message: String,
+}
+
+impl EventData for SenderFailEvent {
+    fn event_type<'t>() -> &'t str {
+        "sender-fail"
+    }
+    fn encode(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+    fn app_id(&self) -> Option<u64> {
+        Some(self.app_id)
+    }
+}
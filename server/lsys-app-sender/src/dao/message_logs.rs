@@ -1,6 +1,10 @@
-use crate::dao::SenderResult;
+use std::sync::Arc;
+
+use crate::dao::{SenderFailEvent, SenderResult};
 use crate::model::{SenderLogModel, SenderLogModelRef, SenderLogStatus, SenderLogType, SenderType};
-use lsys_core::{now_time, PageParam};
+use async_trait::async_trait;
+use lsys_core::{now_time, CronJob, IntoFluentMessage, PageParam, TraceContext};
+use lsys_logger::dao::ChangeLogger;
 
 use sqlx::Pool;
 use sqlx_model::{sql_format, Insert, ModelTableName, Select, SqlExpr};
@@ -12,11 +16,21 @@ use tracing::warn;
 pub struct MessageLogs {
     db: Pool<sqlx::MySql>,
     send_type: SenderType,
+    logger: Option<Arc<ChangeLogger>>,
 }
 
 impl MessageLogs {
     pub fn new(db: Pool<sqlx::MySql>, send_type: SenderType) -> Self {
-        Self { db, send_type }
+        Self {
+            db,
+            send_type,
+            logger: None,
+        }
+    }
+    /// 发送失败时写入 SenderFailEvent 领域事件
+    pub fn with_logger(mut self, logger: Arc<ChangeLogger>) -> Self {
+        self.logger = Some(logger);
+        self
     }
     pub async fn add_exec_log(
         &self,
@@ -32,6 +46,8 @@ impl MessageLogs {
         let log_type = SenderLogType::Send as i8;
         let sender_type = self.send_type as i8;
         let executor_type = executor_type.to_owned();
+        //在任务执行中记录时为发起发送的请求ID
+        let request_id = TraceContext::current().request_id;
         let mut idata = Vec::with_capacity(log_data.len());
         let tmp_dat = log_data
             .iter()
@@ -46,6 +62,7 @@ impl MessageLogs {
                 status:log_status,
                 executor_type:executor_type,
                 message:message,
+                request_id:request_id,
                 create_time:send_time,
             }));
         }
@@ -58,6 +75,34 @@ impl MessageLogs {
                 app_id, executor_type, ie
             );
         }
+        if let Some(logger) = &self.logger {
+            for (message_id, log_status, message) in log_data.iter() {
+                if *log_status != SenderLogStatus::Fail {
+                    continue;
+                }
+                if let Err(err) = logger
+                    .add_event(
+                        &SenderFailEvent {
+                            app_id,
+                            sender_type,
+                            message_id: *message_id,
+                            message: message.to_owned(),
+                        },
+                        &Some(*message_id),
+                        &None,
+                        None,
+                    )
+                    .await
+                {
+                    warn!(
+                        "sms[{}:{}] is send ,add fail event fail : {}",
+                        app_id,
+                        executor_type,
+                        err.to_fluent_message().default_format()
+                    );
+                }
+            }
+        }
     }
     pub async fn list_count(&self, message_id: &u64) -> SenderResult<i64> {
         let sender_type = self.send_type as i8;
@@ -96,3 +141,44 @@ impl MessageLogs {
         Ok(data)
     }
 }
+
+//定时清理过期的发送日志
+pub struct MessageLogsClearCron {
+    db: Pool<sqlx::MySql>,
+    keep_time: u64,
+}
+
+impl MessageLogsClearCron {
+    /// * `keep_time` - 日志保留时间,单位秒
+    pub fn new(db: Pool<sqlx::MySql>, keep_time: u64) -> Self {
+        Self { db, keep_time }
+    }
+}
+
+#[async_trait]
+impl CronJob for MessageLogsClearCron {
+    fn job_name(&self) -> &str {
+        "sender-log-clear"
+    }
+    async fn run(&self, tick: u64) -> Result<(), String> {
+        let clear_time = tick.saturating_sub(self.keep_time);
+        //分批删除,避免长时间锁表
+        let limit = 1000;
+        loop {
+            let sql = sql_format!(
+                "delete from {} where create_time<{} limit {}",
+                SenderLogModel::table_name(),
+                clear_time,
+                limit
+            );
+            let res = sqlx::query(sql.as_str())
+                .execute(&self.db)
+                .await
+                .map_err(|e| e.to_string())?;
+            if res.rows_affected() < limit {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,16 +1,15 @@
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-    dao::SenderResult,
+    dao::{SenderError, SenderResult},
     model::{SenderMessageCancelModel, SenderType},
 };
 
-use lsys_core::{now_time, AppCore};
-use parking_lot::Mutex;
+use lsys_core::{now_time, AppCore, IntoFluentMessage, SnowflakeIdGen};
 use sqlx::{FromRow, MySql, Pool};
 use sqlx_model::{sql_format, ModelTableField, ModelTableName, Select, SqlExpr};
 
-use lsys_core::{TaskData, TaskItem};
+use lsys_core::{TaskData, TaskFair, TaskFairItem, TaskItem};
 use sqlx_model::SqlQuote;
 
 //统一任务消息读取实现
@@ -23,10 +22,11 @@ where
         FromRow<'t, sqlx::mysql::MySqlRow> + Send + Unpin + ModelTableName + ModelTableField<MySql>,
 {
     db: Pool<MySql>,
-    id_generator: Arc<Mutex<snowflake::SnowflakeIdGenerator>>,
+    id_generator: Arc<SnowflakeIdGen>,
     marker_task: std::marker::PhantomData<BM>,
     marker_message: std::marker::PhantomData<MM>,
     send_type: SenderType,
+    task_fair: TaskFair,
 }
 
 impl<BM, MM> MessageReader<BM, MM>
@@ -37,25 +37,37 @@ where
         FromRow<'t, sqlx::mysql::MySqlRow> + Send + Unpin + ModelTableName + ModelTableField<MySql>,
 {
     pub fn new(db: Pool<sqlx::MySql>, app_core: Arc<AppCore>, send_type: SenderType) -> Self {
-        let id_generator = Arc::new(Mutex::new(app_core.create_snowflake_id_generator()));
+        let id_generator = Arc::new(app_core.create_snowflake_id_gen());
         Self {
             id_generator,
             db,
             marker_task: std::marker::PhantomData,
             marker_message: std::marker::PhantomData,
             send_type,
+            task_fair: TaskFair::default(),
         }
     }
-    pub fn message_id(&self) -> u64 {
-        self.id_generator.lock().real_time_generate() as u64
+    //待发送任务的公平选取,可设置各应用的权重
+    pub fn task_fair(&self) -> &TaskFair {
+        &self.task_fair
     }
-    //读取邮件任务数据
+    //工作ID租约丢失时返回错误,避免与其他节点生成重复ID
+    pub fn message_id(&self) -> SenderResult<u64> {
+        self.id_generator
+            .generate()
+            .map_err(|e| SenderError::System(e.to_fluent_message()))
+    }
+    //读取待发送任务数据
+    //按优先级从高到低,同优先级下各应用轮流选取
     pub async fn read_task(
         &self,
         tasking_record: &HashMap<u64, TaskData>,
         status: i8,
         limit: usize,
-    ) -> SenderResult<(Vec<BM>, bool)> {
+    ) -> SenderResult<(Vec<BM>, bool)>
+    where
+        BM: TaskFairItem,
+    {
         let mut sql_vec = vec![];
         sql_vec.push(sql_format!(
             "expected_time<={} and status = {}",
@@ -66,22 +78,19 @@ where
         if !ids.is_empty() {
             sql_vec.push(sql_format!(" id not in ({})", ids));
         }
-        let mut app_res = Select::type_new::<BM>()
-            .fetch_all_by_where::<BM, _>(
-                &sqlx_model::WhereOption::Where(format!(
-                    "{} order by id asc limit {}",
-                    sql_vec.join(" and "),
-                    limit + 1
-                )),
-                &self.db,
-            )
-            .await?;
-        let next = if app_res.len() > limit {
-            app_res.pop();
-            true
-        } else {
-            false
-        };
+        //各优先级下每个应用最多读取limit+1条候选任务,多出的一条用于判断是否还有任务
+        //候选任务一次读取,由 TaskFair 在内存中按优先级及应用轮流选取
+        //窗口函数 row_number 需 MySQL 8.0+ 或 MariaDB 10.2+
+        let sql = format!(
+            "select * from (select *,row_number() over (partition by priority,app_id order by id asc) as fair_row from {} where {}) as task where fair_row<={} order by priority desc,app_id asc,id asc",
+            BM::table_name(),
+            sql_vec.join(" and "),
+            limit + 1
+        );
+        let app_res = sqlx::query_as::<_, BM>(&sql).fetch_all(&self.db).await?;
+        let total = app_res.len();
+        let app_res = self.task_fair.select(app_res, limit);
+        let next = total > app_res.len();
         Ok((app_res, next))
     }
     //读取邮件任务数据
@@ -210,7 +210,7 @@ impl MessageTpls {
     ) -> SenderResult<String> {
         let sender_type = sender_type as i8;
         let tkey = &self.tpl_key(sender_type, tpl_id);
-        if self.tera.read().await.templates.get(tkey).is_none() {
+        if !self.tera.read().await.templates.contains_key(tkey) {
             let tpl = Select::type_new::<SenderTplBodyModel>()
                 .fetch_one_by_where::<SenderTplBodyModel, _>(
                     &WhereOption::Where(sql_format!(
@@ -1,4 +1,5 @@
 mod adapter;
+mod event;
 mod logger;
 mod message_cancel;
 mod message_logs;
@@ -11,6 +12,7 @@ mod sender_task;
 mod sender_tpl_config;
 mod sender_wait;
 pub use adapter::*;
+pub use event::*;
 pub use message_cancel::*;
 pub use message_logs::*;
 pub use message_reader::*;
@@ -4,7 +4,7 @@ use std::{
 };
 
 use deadpool_redis::PoolError;
-use lsys_core::{fluent_message, FluentMessage, IntoFluentMessage};
+use lsys_core::{fluent_message, FluentMessage, IntoFluentMessage, TaskError};
 use lsys_setting::dao::SettingError;
 
 //公共结构定义
@@ -16,6 +16,7 @@ pub enum SenderError {
     Tera(tera::Error),
     System(FluentMessage),
     Setting(SettingError),
+    Task(TaskError),
 }
 
 impl IntoFluentMessage for SenderError {
@@ -27,6 +28,7 @@ impl IntoFluentMessage for SenderError {
             SenderError::Tera(err) => fluent_message!("tera-error", err),
             SenderError::System(err) => err.to_owned(),
             SenderError::Setting(err) => err.to_fluent_message(),
+            SenderError::Task(err) => err.to_fluent_message(),
         }
     }
 }
@@ -59,6 +61,11 @@ impl From<SettingError> for SenderError {
         SenderError::Setting(err)
     }
 }
+impl From<TaskError> for SenderError {
+    fn from(err: TaskError) -> Self {
+        SenderError::Task(err)
+    }
+}
 impl From<tera::Error> for SenderError {
     fn from(err: tera::Error) -> Self {
         SenderError::Tera(err)
@@ -7,7 +7,8 @@ use crate::{
     model::{
         SenderConfigModel, SenderLogModel, SenderMailBodyModel, SenderMailBodyModelRef,
         SenderMailBodyStatus, SenderMailConfigData, SenderMailConfigLimit, SenderMailConfigType,
-        SenderMailMessageModel, SenderMailMessageModelRef, SenderMailMessageStatus, SenderType,
+        SenderMailMessageModel, SenderMailMessageModelRef, SenderMailMessageStatus, SenderPriority,
+        SenderType,
     },
 };
 use lsys_core::{fluent_message, now_time, LimitParam, PageParam, RequestEnv};
@@ -236,6 +237,7 @@ impl MailRecord {
         reply_mail: &Option<String>,
         user_id: &Option<u64>,
         max_try_num: &u8,
+        priority: SenderPriority,
         env_data: Option<&RequestEnv>,
     ) -> SenderResult<(u64, Vec<(u64, &'t str)>)> //(body id,<msg id,mail>)
     {
@@ -253,10 +255,10 @@ impl MailRecord {
         let add_data = mail
             .iter()
             .map(|e| {
-                let id = self.message_reader.message_id();
-                (id, *e, e.to_string())
+                let id = self.message_reader.message_id()?;
+                Ok((id, *e, e.to_string()))
             })
-            .collect::<Vec<_>>();
+            .collect::<SenderResult<Vec<_>>>()?;
         let mut tran = self.db.begin().await?;
 
         let user_ip = env_data
@@ -288,6 +290,7 @@ impl MailRecord {
                 add_time:add_time,
                 reply_mail:reply_mail,
                 max_try_num:max_try_num as u16,
+                priority:priority as i8,
                 user_id:user_id,
                 user_ip:user_ip,
                 expected_time:expected_time,
@@ -547,7 +550,7 @@ impl MailRecord {
             .await?;
         let mut limit_sql = vec![];
         let nowt = send_time;
-        rule.sort_by(|a, b| a.0.priority.cmp(&b.0.priority));
+        rule.sort_by_key(|a| a.0.priority);
         if let Some(max_send) = (|| {
             for t in rule.iter() {
                 if let SenderMailConfigData::MaxOfSend(u) = t.1 {
@@ -588,37 +591,32 @@ impl MailRecord {
                     );
                     limit_sql.push((sql, c.id, limit));
                 }
-                SenderMailConfigData::PassTpl(itpl_id) => {
-                    if *tpl_id == *itpl_id {
-                        break;
-                    }
+                SenderMailConfigData::PassTpl(itpl_id) if *tpl_id == *itpl_id => {
+                    break;
                 }
-                SenderMailConfigData::Block { to } => {
-                    if mails.iter().any(|a| *a == *to) {
-                        return Err(SenderError::System(
-                            fluent_message!("mail-send-check-block", //"send block on :{} [{}]",
-                                {
-                                "to":to,
-                                "config_id":c.id
-                                }
-                            ),
-                        ));
-                    }
+                SenderMailConfigData::Block { to } if mails.iter().any(|a| *a == *to) => {
+                    return Err(SenderError::System(
+                        fluent_message!("mail-send-check-block", //"send block on :{} [{}]",
+                            {
+                            "to":to,
+                            "config_id":c.id
+                            }
+                        ),
+                    ));
                 }
-                SenderMailConfigData::BlockDomain { domain } => {
+                SenderMailConfigData::BlockDomain { domain }
                     if mails
                         .iter()
-                        .any(|a| a.split('@').nth(1).unwrap_or_default() == *domain)
-                    {
-                        return Err(SenderError::System(
-                            fluent_message!("mail-send-check-block-domain", //"send block on :{} [{}]",
-                                {
-                                "domain":domain,
-                                "config_id":c.id
-                                }
-                            ),
-                        ));
-                    }
+                        .any(|a| a.split('@').nth(1).unwrap_or_default() == *domain) =>
+                {
+                    return Err(SenderError::System(
+                        fluent_message!("mail-send-check-block-domain", //"send block on :{} [{}]",
+                            {
+                            "domain":domain,
+                            "config_id":c.id
+                            }
+                        ),
+                    ));
                 }
                 SenderMailConfigData::Close => {
                     return Err(SenderError::System(
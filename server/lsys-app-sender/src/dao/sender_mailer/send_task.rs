@@ -10,8 +10,8 @@ use crate::{
     },
 };
 use async_trait::async_trait;
-use lsys_core::{fluent_message, now_time, IntoFluentMessage};
-use lsys_core::{TaskAcquisition, TaskData, TaskExecutor, TaskItem, TaskRecord};
+use lsys_core::{fluent_message, now_time, IntoFluentMessage, TraceContext};
+use lsys_core::{TaskAcquisition, TaskData, TaskExecutor, TaskFairItem, TaskItem, TaskRecord};
 use lsys_setting::model::SettingModel;
 use sqlx_model::{ModelTableName, SqlExpr, SqlQuote};
 use std::{
@@ -33,6 +33,9 @@ impl TaskItem<u64> for MailTaskItem {
     fn to_task_pk(&self) -> u64 {
         self.mail.id
     }
+    fn to_trace_context(&self) -> TraceContext {
+        TraceContext::new(&self.mail.request_id)
+    }
 }
 impl SenderTaskItem<u64> for MailTaskItem {
     fn app_id(&self) -> u64 {
@@ -42,6 +45,14 @@ impl SenderTaskItem<u64> for MailTaskItem {
         self.mail.tpl_id.to_owned()
     }
 }
+impl TaskFairItem for SenderMailBodyModel {
+    fn task_priority(&self) -> i8 {
+        self.priority
+    }
+    fn task_group(&self) -> u64 {
+        self.app_id
+    }
+}
 
 pub struct MailTaskData {
     pub(crate) data: Vec<SenderMailMessageModel>,
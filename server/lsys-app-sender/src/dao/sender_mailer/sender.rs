@@ -1,6 +1,8 @@
 use std::{collections::HashSet, sync::Arc};
 
-use lsys_core::{fluent_message, now_time, AppCore, FluentMessage, RequestEnv, TaskData};
+use lsys_core::{
+    fluent_message, now_time, AppCore, FluentMessage, RequestEnv, TaskBackendConfig, TaskData,
+};
 
 use lsys_logger::dao::ChangeLogger;
 use lsys_setting::dao::Setting;
@@ -13,23 +15,24 @@ use crate::{
         MessageCancel, MessageLogs, MessageReader, SenderConfig, SenderError, SenderResult,
         SenderTaskExecutor, SenderTplConfig, SenderWaitItem, SenderWaitNotify,
     },
-    model::{SenderMailBodyModel, SenderMailMessageModel, SenderMailMessageStatus, SenderType},
+    model::{
+        SenderMailBodyModel, SenderMailMessageModel, SenderMailMessageStatus, SenderPriority,
+        SenderType,
+    },
 };
 use lsys_core::IntoFluentMessage;
-use lsys_core::TaskDispatch;
+use lsys_core::{TaskDispatch, TaskFair, TaskMonitorItem};
 
 const MAILER_REDIS_PREFIX: &str = "sender-mail-";
 
 pub struct MailSender {
     pub tpl_config: Arc<SenderTplConfig>,
     pub mail_record: Arc<MailRecord>,
-    redis: deadpool_redis::Pool,
     db: Pool<sqlx::MySql>,
-    app_core: Arc<AppCore>,
     message_logs: Arc<MessageLogs>,
     cancel: Arc<MessageCancel>,
     message_reader: Arc<MessageReader<SenderMailBodyModel, SenderMailMessageModel>>,
-    task: TaskDispatch<u64, MailTaskItem>,
+    task: Arc<TaskDispatch<u64, MailTaskItem>>,
     send_wait: Arc<SenderWaitNotify>,
 }
 
@@ -59,7 +62,9 @@ impl MailSender {
             SenderType::Mailer,
         ));
         let cancel = Arc::new(MessageCancel::new(db.clone(), SenderType::Mailer));
-        let message_logs = Arc::new(MessageLogs::new(db.clone(), SenderType::Mailer));
+        let message_logs = Arc::new(
+            MessageLogs::new(db.clone(), SenderType::Mailer).with_logger(logger.clone()),
+        );
         let message_reader = Arc::new(MessageReader::new(
             db.clone(),
             app_core.clone(),
@@ -80,20 +85,20 @@ impl MailSender {
             wait_timeout.unwrap_or(30),
         ));
 
-        let task = TaskDispatch::new(
-            format!("{}-notify", MAILER_REDIS_PREFIX),
-            format!("{}-read-lock", MAILER_REDIS_PREFIX),
-            format!("{}-run-task", MAILER_REDIS_PREFIX),
+        let task = Arc::new(TaskDispatch::new(
+            TaskBackendConfig::new(app_core.clone(), redis.clone()).create(
+                format!("{}-notify", MAILER_REDIS_PREFIX),
+                format!("{}-read-lock", MAILER_REDIS_PREFIX),
+                format!("{}-run-task", MAILER_REDIS_PREFIX),
+            ),
             task_size,
             task_timeout,
             is_check,
             task_timeout,
-        );
+        ));
         Self {
             tpl_config,
-            redis,
             mail_record,
-            app_core,
             db,
             message_logs,
             message_reader,
@@ -114,6 +119,7 @@ impl MailSender {
         user_id: &Option<u64>,
         reply_mail: &Option<String>,
         max_try_num: &Option<u8>,
+        priority: SenderPriority,
         env_data: Option<&RequestEnv>,
     ) -> SenderResult<(u64, Vec<(u64, &'t str, Result<bool, FluentMessage>)>)> {
         let tmp = mail
@@ -140,6 +146,7 @@ impl MailSender {
                 reply_mail,
                 user_id,
                 &max_try_num,
+                priority,
                 env_data,
             )
             .await?;
@@ -155,9 +162,12 @@ impl MailSender {
             .map(|e| e - 1 <= now_time().unwrap_or_default())
             .unwrap_or(true)
         {
-            let mut redis = self.redis.get().await?;
-            if let Err(err) = self.task.notify(&mut redis).await {
-                warn!("mail is add [{}] ,but send fail :{}", res.0, err)
+            if let Err(err) = self.task.notify().await {
+                warn!(
+                    "mail is add [{}] ,but send fail :{}",
+                    res.0,
+                    err.to_fluent_message().default_format()
+                )
             }
         }
 
@@ -254,8 +264,7 @@ impl MailSender {
         &self,
         check_message_data: Vec<(&u64, D)>,
     ) -> SenderResult<Vec<(D, Option<TaskData>)>> {
-        let mut redis = self.redis.get().await?;
-        let mut tdata = self.task.task_data(&mut redis).await?;
+        let mut tdata = self.task.task_data().await?;
         let mut out = Vec::with_capacity(check_message_data.len());
         for (mid, data) in check_message_data {
             out.push((data, tdata.remove(mid)));
@@ -292,6 +301,14 @@ impl MailSender {
         }
         Ok(out)
     }
+    //发送任务,用于查看运行状态
+    pub fn task_monitor(&self) -> Arc<dyn TaskMonitorItem> {
+        self.task.clone()
+    }
+    //发送任务的公平选取,可设置各应用的发送权重
+    pub fn task_fair(&self) -> &TaskFair {
+        self.message_reader.task_fair()
+    }
     //发送等待回调处理监听
     pub async fn task_wait(&self) {
         self.send_wait.listen().await;
@@ -309,7 +326,6 @@ impl MailSender {
         ));
         self.task
             .dispatch(
-                self.app_core.clone(),
                 acquisition.as_ref(),
                 MailerTask::new(acquisition.to_owned(), self.tpl_config.clone(), se)?,
             )
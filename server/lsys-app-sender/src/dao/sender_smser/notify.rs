@@ -91,7 +91,7 @@ pub trait SmsSendNotifyParse {
         items: &[SendNotifyItem],
         msg: Vec<SenderSmsMessageModel>,
     ) -> Result<Vec<(Option<SenderSmsMessageModel>, SendNotifyItem)>, String> {
-        return Ok(items
+        Ok(items
             .iter()
             .map(|e| {
                 let tmp = msg
@@ -103,7 +103,7 @@ pub trait SmsSendNotifyParse {
                     .map(|t| t.to_owned());
                 (tmp, e.to_owned())
             })
-            .collect::<Vec<_>>());
+            .collect::<Vec<_>>())
     }
 }
 
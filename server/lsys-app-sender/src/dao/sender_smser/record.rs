@@ -5,9 +5,10 @@ use crate::{
         logger::LogMessage, MessageLogs, MessageReader, SenderConfig, SenderError, SenderResult,
     },
     model::{
-        SenderConfigModel, SenderLogModel, SenderSmsBodyModel, SenderSmsBodyModelRef,
-        SenderSmsBodyStatus, SenderSmsConfigData, SenderSmsConfigLimit, SenderSmsConfigType,
-        SenderSmsMessageModel, SenderSmsMessageModelRef, SenderSmsMessageStatus, SenderType,
+        SenderConfigModel, SenderLogModel, SenderPriority, SenderSmsBodyModel,
+        SenderSmsBodyModelRef, SenderSmsBodyStatus, SenderSmsConfigData, SenderSmsConfigLimit,
+        SenderSmsConfigType, SenderSmsMessageModel, SenderSmsMessageModelRef,
+        SenderSmsMessageStatus, SenderType,
     },
 };
 use lsys_core::{fluent_message, now_time, LimitParam, PageParam, RequestEnv};
@@ -241,6 +242,7 @@ impl SmsRecord {
         expected_time: &u64,
         user_id: &Option<u64>,
         max_try_num: &u8,
+        priority: SenderPriority,
         env_data: Option<&RequestEnv>,
     ) -> SenderResult<(u64, Vec<(u64, &'t str, &'t str)>)> //返回(body id,<msg id,area,mobile>)
     {
@@ -253,11 +255,11 @@ impl SmsRecord {
         let add_data = mobiles
             .iter()
             .map(|e| {
-                let id = self.message_reader.message_id();
+                let id = self.message_reader.message_id()?;
 
-                (id, e.0, e.1, e.0.to_owned(), e.1.to_owned())
+                Ok((id, e.0, e.1, e.0.to_owned(), e.1.to_owned()))
             })
-            .collect::<Vec<_>>();
+            .collect::<SenderResult<Vec<_>>>()?;
 
         let mut tran = self.db.begin().await?;
         let user_ip = env_data
@@ -290,6 +292,7 @@ impl SmsRecord {
                 status:SenderSmsBodyStatus::Init as i8,
                 add_time:add_time,
                 max_try_num:max_try_num as u16,
+                priority:priority as i8,
                 user_id:user_id,
                 user_ip:user_ip,
                 expected_time:expected_time,
@@ -575,7 +578,7 @@ impl SmsRecord {
             .await?;
         let mut limit_sql = vec![];
         let nowt = send_time;
-        rule.sort_by(|a, b| a.0.priority.cmp(&b.0.priority));
+        rule.sort_by_key(|a| a.0.priority);
         if let Some(max_send) = (|| {
             for t in rule.iter() {
                 if let SenderSmsConfigData::MaxOfSend(u) = t.1 {
@@ -620,27 +623,25 @@ impl SmsRecord {
                     );
                     limit_sql.push((sql, c.id, limit));
                 }
-                SenderSmsConfigData::PassTpl(itpl_id) => {
-                    if *tpl_id == *itpl_id {
-                        break;
-                    }
+                SenderSmsConfigData::PassTpl(itpl_id) if *tpl_id == *itpl_id => {
+                    break;
                 }
-                SenderSmsConfigData::Block { area, mobile } => {
-                    if mobiles.iter().any(|a| *a.0 == *area && *a.1 == *mobile) {
-                        return Err(SenderError::System(
-                            fluent_message!("sms-send-check-block", //"send block on :{} [{}]",
-                                {
-                                    "area":area,
-                                "mobile":mobile,
-                                "config_id":c.id
-                                }
-                            ),
-                        ));
-                        // return Err(SenderError::System(format!(
-                        //     "send block on :{}{} [{}]",
-                        //     area, mobile, c.id
-                        // )));
-                    }
+                SenderSmsConfigData::Block { area, mobile }
+                    if mobiles.iter().any(|a| *a.0 == *area && *a.1 == *mobile) =>
+                {
+                    return Err(SenderError::System(
+                        fluent_message!("sms-send-check-block", //"send block on :{} [{}]",
+                            {
+                                "area":area,
+                            "mobile":mobile,
+                            "config_id":c.id
+                            }
+                        ),
+                    ));
+                    // return Err(SenderError::System(format!(
+                    //     "send block on :{}{} [{}]",
+                    //     area, mobile, c.id
+                    // )));
                 }
                 SenderSmsConfigData::Close => {
                     return Err(SenderError::System(
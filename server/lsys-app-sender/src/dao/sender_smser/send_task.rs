@@ -4,7 +4,7 @@ use std::{
 };
 
 use async_trait::async_trait;
-use lsys_core::{fluent_message, now_time, IntoFluentMessage};
+use lsys_core::{fluent_message, now_time, IntoFluentMessage, TraceContext};
 
 use crate::{
     dao::{
@@ -17,7 +17,7 @@ use crate::{
         SenderSmsBodyStatus, SenderSmsMessageModel, SenderSmsMessageStatus, SenderTplConfigModel,
     },
 };
-use lsys_core::{TaskAcquisition, TaskData, TaskExecutor, TaskItem, TaskRecord};
+use lsys_core::{TaskAcquisition, TaskData, TaskExecutor, TaskFairItem, TaskItem, TaskRecord};
 use lsys_setting::model::SettingModel;
 use sqlx::{MySql, Pool};
 use sqlx_model::{sql_format, ModelTableName, SqlExpr, Update};
@@ -34,6 +34,9 @@ impl TaskItem<u64> for SmsTaskItem {
     fn to_task_pk(&self) -> u64 {
         self.sms.id
     }
+    fn to_trace_context(&self) -> TraceContext {
+        TraceContext::new(&self.sms.request_id)
+    }
 }
 impl SenderTaskItem<u64> for SmsTaskItem {
     fn app_id(&self) -> u64 {
@@ -43,6 +46,14 @@ impl SenderTaskItem<u64> for SmsTaskItem {
         self.sms.tpl_id.to_owned()
     }
 }
+impl TaskFairItem for SenderSmsBodyModel {
+    fn task_priority(&self) -> i8 {
+        self.priority
+    }
+    fn task_group(&self) -> u64 {
+        self.app_id
+    }
+}
 
 pub struct SmsTaskData {
     pub(crate) data: Vec<SenderSmsMessageModel>,
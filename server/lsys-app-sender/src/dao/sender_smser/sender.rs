@@ -1,7 +1,8 @@
 use std::{collections::HashSet, sync::Arc};
 
 use lsys_core::{
-    fluent_message, now_time, AppCore, FluentMessage, IntoFluentMessage, RequestEnv, TaskData,
+    fluent_message, now_time, AppCore, FluentMessage, IntoFluentMessage, RequestEnv,
+    TaskBackendConfig, TaskData,
 };
 
 use lsys_app_notify::dao::Notify;
@@ -20,9 +21,12 @@ use crate::{
         MessageCancel, MessageLogs, MessageReader, SenderConfig, SenderError, SenderResult,
         SenderTaskExecutor, SenderTplConfig, SenderWaitItem, SenderWaitNotify,
     },
-    model::{SenderSmsBodyModel, SenderSmsMessageModel, SenderSmsMessageStatus, SenderType},
+    model::{
+        SenderPriority, SenderSmsBodyModel, SenderSmsMessageModel, SenderSmsMessageStatus,
+        SenderType,
+    },
 };
-use lsys_core::TaskDispatch;
+use lsys_core::{TaskDispatch, TaskFair, TaskMonitorItem};
 
 const SMSER_REDIS_PREFIX: &str = "sender-sms-";
 
@@ -33,12 +37,11 @@ pub struct SmsSender {
     status_query: Arc<SmsStatusQuery>,
     redis: deadpool_redis::Pool,
     db: Pool<sqlx::MySql>,
-    app_core: Arc<AppCore>,
     cancel: Arc<MessageCancel>,
     message_logs: Arc<MessageLogs>,
     message_reader: Arc<MessageReader<SenderSmsBodyModel, SenderSmsMessageModel>>,
-    task_sender: TaskDispatch<u64, SmsTaskItem>,
-    task_status: TaskDispatch<u64, SmsStatusTaskItem>,
+    task_sender: Arc<TaskDispatch<u64, SmsTaskItem>>,
+    task_status: Arc<TaskDispatch<u64, SmsStatusTaskItem>>,
     send_wait: Arc<SenderWaitNotify>,
     task_status_key: String,
     setting: Arc<Setting>,
@@ -74,7 +77,9 @@ impl SmsSender {
             SenderType::Smser,
         ));
         let cancel = Arc::new(MessageCancel::new(db.clone(), SenderType::Smser));
-        let message_logs = Arc::new(MessageLogs::new(db.clone(), SenderType::Smser));
+        let message_logs = Arc::new(
+            MessageLogs::new(db.clone(), SenderType::Smser).with_logger(logger.clone()),
+        );
         let message_reader = Arc::new(MessageReader::new(
             db.clone(),
             app_core.clone(),
@@ -88,25 +93,30 @@ impl SmsSender {
             message_reader.clone(),
         ));
 
-        let task_sender = TaskDispatch::new(
-            format!("{}-sender-notify", SMSER_REDIS_PREFIX),
-            format!("{}-sender-read-lock", SMSER_REDIS_PREFIX),
-            format!("{}-sender-run-task", SMSER_REDIS_PREFIX),
+        let task_backend = TaskBackendConfig::new(app_core.clone(), redis.clone());
+        let task_sender = Arc::new(TaskDispatch::new(
+            task_backend.create(
+                format!("{}-sender-notify", SMSER_REDIS_PREFIX),
+                format!("{}-sender-read-lock", SMSER_REDIS_PREFIX),
+                format!("{}-sender-run-task", SMSER_REDIS_PREFIX),
+            ),
             sender_task_size,
             task_timeout,
             is_check,
             task_timeout,
-        );
+        ));
 
-        let task_status = TaskDispatch::new(
-            format!("{}-status-notify", SMSER_REDIS_PREFIX),
-            format!("{}-status-read-lock", SMSER_REDIS_PREFIX),
-            format!("{}-status-run-task", SMSER_REDIS_PREFIX),
+        let task_status = Arc::new(TaskDispatch::new(
+            task_backend.create(
+                format!("{}-status-notify", SMSER_REDIS_PREFIX),
+                format!("{}-status-read-lock", SMSER_REDIS_PREFIX),
+                format!("{}-status-run-task", SMSER_REDIS_PREFIX),
+            ),
             notify_task_size,
             task_timeout,
             is_check,
             task_timeout,
-        );
+        ));
         let task_status_key = format!("{}-status-data", SMSER_REDIS_PREFIX);
         let sms_notify = Arc::new(SmsSendNotify::new(db.clone(), notify.clone()));
 
@@ -128,7 +138,6 @@ impl SmsSender {
             tpl_config,
             redis,
             sms_record,
-            app_core,
             db,
             message_logs,
             message_reader,
@@ -145,9 +154,11 @@ impl SmsSender {
     }
     pub async fn add_status_query(&self, items: &[&SenderSmsMessageModel]) -> SenderResult<()> {
         self.status_query.add_query(items).await?;
-        let mut redis = self.redis.get().await?;
-        if let Err(err) = self.task_status.notify(&mut redis).await {
-            warn!("add status query task fail :{}", err)
+        if let Err(err) = self.task_status.notify().await {
+            warn!(
+                "add status query task fail :{}",
+                err.to_fluent_message().default_format()
+            )
         }
         Ok(())
     }
@@ -162,6 +173,7 @@ impl SmsSender {
         send_time: &Option<u64>,
         user_id: &Option<u64>,
         max_try_num: &Option<u8>,
+        priority: SenderPriority,
         env_data: Option<&RequestEnv>,
     ) -> SenderResult<(
         u64,
@@ -190,6 +202,7 @@ impl SmsSender {
                 &sendtime,
                 user_id,
                 &max_try_num,
+                priority,
                 env_data,
             )
             .await?;
@@ -205,9 +218,12 @@ impl SmsSender {
             .map(|e| e - 1 <= now_time().unwrap_or_default())
             .unwrap_or(true)
         {
-            let mut redis = self.redis.get().await?;
-            if let Err(err) = self.task_sender.notify(&mut redis).await {
-                warn!("sms is add [{}] ,but send fail :{}", res.0, err)
+            if let Err(err) = self.task_sender.notify().await {
+                warn!(
+                    "sms is add [{}] ,but send fail :{}",
+                    res.0,
+                    err.to_fluent_message().default_format()
+                )
             }
         }
         let mut tmp = vec![];
@@ -327,8 +343,7 @@ impl SmsSender {
         &self,
         check_message_data: Vec<(&u64, D)>,
     ) -> SenderResult<Vec<(D, Option<TaskData>)>> {
-        let mut redis = self.redis.get().await?;
-        let mut tdata = self.task_sender.task_data(&mut redis).await?;
+        let mut tdata = self.task_sender.task_data().await?;
         let mut out = Vec::with_capacity(check_message_data.len());
         for (mid, data) in check_message_data {
             out.push((data, tdata.remove(mid)));
@@ -348,13 +363,23 @@ impl SmsSender {
         ));
         self.task_sender
             .dispatch(
-                self.app_core.clone(),
                 acquisition.as_ref(),
                 SmsTask::new(acquisition.to_owned(), self.tpl_config.clone(), se)?,
             )
             .await;
         Ok(())
     }
+    //发送及状态查询任务,用于查看运行状态
+    pub fn task_monitor(&self) -> Vec<Arc<dyn TaskMonitorItem>> {
+        vec![
+            self.task_sender.clone() as Arc<dyn TaskMonitorItem>,
+            self.task_status.clone() as Arc<dyn TaskMonitorItem>,
+        ]
+    }
+    //发送任务的公平选取,可设置各应用的发送权重
+    pub fn task_fair(&self) -> &TaskFair {
+        self.message_reader.task_fair()
+    }
     //发送等待回调处理监听
     pub async fn task_wait(&self) {
         self.send_wait.listen().await;
@@ -368,7 +393,6 @@ impl SmsSender {
             SmsStatusTaskAcquisition::new(self.redis.clone(), self.task_status_key.clone());
         self.task_status
             .dispatch(
-                self.app_core.clone(),
                 &acquisition,
                 SmsStatusTask::new(
                     se,
@@ -6,6 +6,7 @@ use lsys_app_notify::dao::Notify;
 use lsys_core::fluent_message;
 use lsys_core::now_time;
 use lsys_core::IntoFluentMessage;
+use lsys_core::TraceContext;
 use lsys_setting::dao::MultipleSetting;
 
 use lsys_lib_sms::SendNotifyStatus;
@@ -25,7 +26,7 @@ use crate::model::SenderLogStatus;
 
 use crate::{
     dao::{SenderError, SenderExecError},
-    model::{SenderSmsMessageModel, SenderSmsMessageStatus},
+    model::{SenderSmsBodyModel, SenderSmsMessageModel, SenderSmsMessageStatus},
 };
 use lsys_core::{TaskAcquisition, TaskData, TaskExecutor, TaskItem, TaskRecord};
 
@@ -62,7 +63,7 @@ impl SmsStatusQuery {
             return Ok(());
         }
         let mut conn = self.redis.get().await?;
-        conn.sadd(&self.notify_data_key, query_ids).await?;
+        conn.sadd::<_, _, ()>(&self.notify_data_key, query_ids).await?;
 
         Ok(())
     }
@@ -190,23 +191,12 @@ impl SmsStatusTask {
             notify,
         })
     }
-}
-
-#[async_trait]
-impl TaskExecutor<u64, SmsStatusTaskItem> for SmsStatusTask {
-    async fn exec(&self, val: SmsStatusTaskItem) -> Result<(), String> {
-        let sms = self
-            .recrod
-            .find_message_by_id(&val.0)
-            .await
-            .map_err(|e| e.to_fluent_message().default_format())?;
-
-        let body = self
-            .recrod
-            .find_body_by_id(&sms.sender_body_id)
-            .await
-            .map_err(|e| e.to_fluent_message().default_format())?;
-
+    async fn exec_status(
+        &self,
+        val: SmsStatusTaskItem,
+        sms: SenderSmsMessageModel,
+        body: SenderSmsBodyModel,
+    ) -> Result<(), String> {
         match self.setting.find(&None, &sms.setting_id).await {
             Ok(setting) => {
                 for tmp in self.inner.iter() {
@@ -309,6 +299,28 @@ impl TaskExecutor<u64, SmsStatusTaskItem> for SmsStatusTask {
                 return Err(err.to_fluent_message().default_format());
             }
         };
-        return Err(format!("not find any status apatar :{}", val.0));
+        Err(format!("not find any status apatar :{}", val.0))
+    }
+}
+
+#[async_trait]
+impl TaskExecutor<u64, SmsStatusTaskItem> for SmsStatusTask {
+    async fn exec(&self, val: SmsStatusTaskItem) -> Result<(), String> {
+        let sms = self
+            .recrod
+            .find_message_by_id(&val.0)
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?;
+
+        let body = self
+            .recrod
+            .find_body_by_id(&sms.sender_body_id)
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?;
+
+        //状态查询任务使用发送请求的追踪上下文,回调推送可关联到原请求
+        TraceContext::new(&body.request_id)
+            .scope("sms-status", self.exec_status(val, sms, body))
+            .await
     }
 }
@@ -1,3 +1,21 @@
 pub mod dao;
 pub mod model;
 pub use lsys_lib_sms::*;
+
+/// 数据库迁移,按版本顺序执行
+pub fn migrations() -> Vec<lsys_core::MigrateItem> {
+    vec![
+        lsys_core::MigrateItem::new(
+            env!("CARGO_PKG_NAME"),
+            1,
+            "init",
+            include_str!("../tables.sql"),
+        ),
+        lsys_core::MigrateItem::new(
+            env!("CARGO_PKG_NAME"),
+            2,
+            "log_request_id",
+            include_str!("../migrations/0002_log_request_id.sql"),
+        ),
+    ]
+}
@@ -57,6 +57,15 @@ pub enum SenderTplConfigStatus {
     Delete = 2,
 }
 
+// 发送优先级,优先级高的消息优先发送
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[sqlx_model_status(field_type = "i8")]
+pub enum SenderPriority {
+    Low = 1,     //低,如营销类消息
+    Normal = 50, //默认
+    High = 100,  //高,如验证码
+}
+
 //发送模块公共常量 -end
 
 //短信公共常量-start
@@ -83,6 +83,10 @@ pub struct SenderLogModel {
     #[sqlx(default)]
     pub message: String,
 
+    /// 请求ID
+    #[sqlx(default)]
+    pub request_id: String,
+
     /// 发送时间
     #[sqlx(default)]
     pub create_time: u64,
@@ -230,6 +234,10 @@ pub struct SenderSmsBodyModel {
     #[sqlx(default)]
     pub max_try_num: u16,
 
+    /// 发送优先级
+    #[sqlx(default)]
+    pub priority: i8,
+
     /// 1 未发送 2 已发送 3 发送失败
     #[sqlx(default)]
     pub status: i8,
@@ -341,6 +349,10 @@ pub struct SenderMailBodyModel {
     #[sqlx(default)]
     pub max_try_num: u16,
 
+    /// 发送优先级
+    #[sqlx(default)]
+    pub priority: i8,
+
     #[sqlx(default)]
     pub reply_mail: String,
 
@@ -74,6 +74,7 @@ CREATE TABLE `yaf_sender_mail_body` (
     `tpl_id` varchar(32) NOT NULL COMMENT '模板ID',
     `tpl_var` varchar(512) NOT NULL DEFAULT '' COMMENT '模板变量',
     `max_try_num` smallint unsigned NOT NULL DEFAULT 1 COMMENT '最大发送次数',
+    `priority` tinyint NOT NULL DEFAULT 50 COMMENT '发送优先级',
     `status` tinyint NOT NULL COMMENT '启用状态:未完成,完成发送',
     `add_time` bigint unsigned NOT NULL COMMENT '申请时间',
     `expected_time` bigint unsigned NOT NULL COMMENT '预计发送时间',
@@ -84,7 +85,8 @@ CREATE TABLE `yaf_sender_mail_body` (
     `request_id` varchar(32) NOT NULL COMMENT '请求ID',
     `reply_host` varchar(255) NOT NULL COMMENT '结果返回主机',
     PRIMARY KEY (`id`),
-    KEY `sender_record_data_IDX` (`expected_time`, `status`, `id`) USING BTREE
+    KEY `sender_record_data_IDX` (`expected_time`, `status`, `id`) USING BTREE,
+    KEY `sender_record_task_IDX` (`status`, `priority`, `app_id`, `id`) USING BTREE
 ) ENGINE = InnoDB CHARSET = utf8mb4 COMMENT = '发送邮件数据';
 CREATE TABLE `yaf_sender_mail_message` (
     `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT 'ID,由应用生成',
@@ -110,6 +112,7 @@ CREATE TABLE `yaf_sender_sms_body` (
     `tpl_id` varchar(32) NOT NULL COMMENT '模板ID',
     `tpl_var` varchar(512) NOT NULL DEFAULT '' COMMENT '模板变量',
     `max_try_num` smallint unsigned NOT NULL DEFAULT 1 COMMENT '最大发送次数',
+    `priority` tinyint NOT NULL DEFAULT 50 COMMENT '发送优先级',
     `status` tinyint NOT NULL COMMENT '启用状态:未完成,完成发送',
     `add_time` bigint unsigned NOT NULL COMMENT '申请时间',
     `expected_time` bigint unsigned NOT NULL COMMENT '预计发送时间',
@@ -119,7 +122,8 @@ CREATE TABLE `yaf_sender_sms_body` (
     `request_id` varchar(32) NOT NULL COMMENT '请求ID',
     `reply_host` varchar(255) NOT NULL COMMENT '结果返回主机',
     PRIMARY KEY (`id`),
-    KEY `sender_record_data_IDX` (`expected_time`, `status`, `id`) USING BTREE
+    KEY `sender_record_data_IDX` (`expected_time`, `status`, `id`) USING BTREE,
+    KEY `sender_record_task_IDX` (`status`, `priority`, `app_id`, `id`) USING BTREE
 ) ENGINE = InnoDB CHARSET = utf8mb4 COMMENT = '发送短信数据';
 CREATE TABLE `yaf_sender_sms_message` (
     `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT 'ID,由应用生成',
@@ -18,7 +18,7 @@ use super::{
     super::{AppsError, AppsResult},
     SubApps,
 };
-use super::{range_client_key, AppLog};
+use super::{range_client_key, AppConfirmEvent, AppLog};
 pub struct Apps {
     db: Pool<MySql>,
     pub(crate) cache: Arc<LocalCache<String, AppsModel>>,
@@ -233,7 +233,26 @@ impl Apps {
                 return Err(ie.into());
             }
         };
+        if let Err(ie) = self
+            .logger
+            .add_event(
+                &AppConfirmEvent {
+                    app_id: app.id,
+                    user_id: app.user_id,
+                    name: app.name.to_owned(),
+                    client_id: app.client_id.to_owned(),
+                },
+                &Some(app.id),
+                &Some(app.user_id),
+                Some(&mut db),
+            )
+            .await
+        {
+            db.rollback().await?;
+            return Err(ie.into());
+        }
         db.commit().await?;
+        self.logger.notify_event().await;
         self.cache.clear(&app.client_id).await;
 
         self.logger
@@ -1,5 +1,5 @@
 use lsys_core::{rand_str, RandType};
-use lsys_logger::dao::ChangeLogData;
+use lsys_logger::dao::{ChangeLogData, EventData};
 
 // use rand::seq::SliceRandom;
 
@@ -13,7 +13,7 @@ mod sub_apps;
 
 pub use apps::*;
 pub use oauth::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 pub use sub_apps::*;
 
 //日志
@@ -39,6 +39,27 @@ impl ChangeLogData for AppLog {
     }
 }
 
+//应用审核通过
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AppConfirmEvent {
+    pub app_id: u64,
+    pub user_id: u64,
+    pub name: String,
+    pub client_id: String,
+}
+
+impl EventData for AppConfirmEvent {
+    fn event_type<'t>() -> &'t str {
+        "app-confirm"
+    }
+    fn encode(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+    fn app_id(&self) -> Option<u64> {
+        Some(self.app_id)
+    }
+}
+
 #[derive(Serialize)]
 pub(crate) struct AppSubUserLog {
     pub used: bool,
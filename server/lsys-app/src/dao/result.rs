@@ -7,6 +7,7 @@ use std::{
 
 use deadpool_redis::PoolError;
 
+use lsys_logger::dao::LoggerError;
 use lsys_user::dao::account::UserAccountError;
 
 use redis::RedisError;
@@ -59,6 +60,13 @@ impl From<sqlx::Error> for AppsError {
         AppsError::Sqlx(err)
     }
 }
+impl From<LoggerError> for AppsError {
+    fn from(err: LoggerError) -> Self {
+        match err {
+            LoggerError::Sqlx(err) => AppsError::Sqlx(err),
+        }
+    }
+}
 impl From<RedisError> for AppsError {
     fn from(err: RedisError) -> Self {
         AppsError::Redis(err)
@@ -0,0 +1,281 @@
+use std::{
+    fmt::Debug,
+    io::Write,
+    net::{SocketAddr, TcpStream, UdpSocket},
+    sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+//未发送日志的最大数量,超过时丢弃新日志
+const GELF_BUFFER_SIZE: usize = 10000;
+//UDP单个分块的最大长度
+const GELF_UDP_CHUNK_SIZE: usize = 8192;
+//UDP最大分块数量
+const GELF_UDP_CHUNK_MAX: usize = 128;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GelfProtocol {
+    Udp,
+    Tcp,
+}
+
+impl GelfProtocol {
+    pub fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "tcp" => Self::Tcp,
+            _ => Self::Udp,
+        }
+    }
+}
+
+//记录日志字段
+#[derive(Default)]
+struct GelfFields(Map<String, Value>);
+
+impl Visit for GelfFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_owned(), Value::from(format!("{:?}", value)));
+    }
+}
+
+// GELF 1.1 格式日志输出
+// 日志在后台线程中通过UDP或TCP发送,span中的字段如 request_id 作为附加字段发送
+pub struct GelfLayer {
+    host: String,
+    sender: SyncSender<Vec<u8>>,
+}
+
+impl GelfLayer {
+    /// * `addr` - 日志收集服务地址,如 127.0.0.1:12201
+    /// * `protocol` - 传输协议
+    pub fn new(addr: SocketAddr, protocol: GelfProtocol) -> std::io::Result<Self> {
+        let (sender, receiver) = sync_channel::<Vec<u8>>(GELF_BUFFER_SIZE);
+        match protocol {
+            GelfProtocol::Udp => {
+                let bind: SocketAddr = if addr.is_ipv4() {
+                    ([0, 0, 0, 0], 0).into()
+                } else {
+                    ([0u16; 8], 0).into()
+                };
+                let socket = UdpSocket::bind(bind)?;
+                std::thread::Builder::new()
+                    .name("log-gelf".to_string())
+                    .spawn(move || gelf_udp_send(socket, addr, receiver))?;
+            }
+            GelfProtocol::Tcp => {
+                std::thread::Builder::new()
+                    .name("log-gelf".to_string())
+                    .spawn(move || gelf_tcp_send(addr, receiver))?;
+            }
+        }
+        Ok(Self {
+            host: hostname::get()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            sender,
+        })
+    }
+}
+
+//syslog 日志等级
+fn gelf_level(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    }
+}
+
+fn gelf_message(
+    host: &str,
+    event: &Event<'_>,
+    span_fields: Map<String, Value>,
+) -> Map<String, Value> {
+    let mut fields = GelfFields::default();
+    event.record(&mut fields);
+    let meta = event.metadata();
+    let mut data = Map::new();
+    data.insert("version".to_owned(), Value::from("1.1"));
+    data.insert("host".to_owned(), Value::from(host));
+    data.insert(
+        "short_message".to_owned(),
+        fields
+            .0
+            .remove("message")
+            .unwrap_or_else(|| Value::from(meta.name())),
+    );
+    data.insert(
+        "timestamp".to_owned(),
+        Value::from(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|e| e.as_secs_f64())
+                .unwrap_or_default(),
+        ),
+    );
+    data.insert("level".to_owned(), Value::from(gelf_level(meta.level())));
+    data.insert("_target".to_owned(), Value::from(meta.target()));
+    if let Some(file) = meta.file() {
+        data.insert("_file".to_owned(), Value::from(file));
+    }
+    if let Some(line) = meta.line() {
+        data.insert("_line".to_owned(), Value::from(line));
+    }
+    //GELF 附加字段以_开头,且不能为 _id
+    for (key, val) in span_fields.into_iter().chain(fields.0) {
+        let key = if key == "id" {
+            "_span_id".to_owned()
+        } else {
+            format!("_{}", key.trim_start_matches("log."))
+        };
+        data.insert(key, val);
+    }
+    data
+}
+
+impl<S> Layer<S> for GelfLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut fields = GelfFields::default();
+            attrs.record(&mut fields);
+            span.extensions_mut().insert(fields);
+        }
+    }
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut ext = span.extensions_mut();
+            if let Some(fields) = ext.get_mut::<GelfFields>() {
+                values.record(fields);
+            }
+        }
+    }
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        //外层span字段先写入,内层同名字段覆盖外层
+        let mut span_fields = Map::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(fields) = span.extensions().get::<GelfFields>() {
+                    for (key, val) in fields.0.iter() {
+                        span_fields.insert(key.to_owned(), val.to_owned());
+                    }
+                }
+            }
+        }
+        let data = gelf_message(&self.host, event, span_fields);
+        let data = serde_json::to_vec(&data).unwrap_or_default();
+        if let Err(TrySendError::Disconnected(_)) = self.sender.try_send(data) {
+            eprintln!("gelf log sender is closed");
+        }
+    }
+}
+
+fn gelf_udp_chunks(data: &[u8]) -> Vec<Vec<u8>> {
+    if data.len() <= GELF_UDP_CHUNK_SIZE {
+        return vec![data.to_vec()];
+    }
+    //分块头: 0x1e 0x0f + 8字节消息ID + 序号 + 总数
+    let body_size = GELF_UDP_CHUNK_SIZE - 12;
+    let chunks = data.chunks(body_size).collect::<Vec<_>>();
+    if chunks.len() > GELF_UDP_CHUNK_MAX {
+        return vec![];
+    }
+    let msg_id = rand::random::<[u8; 8]>();
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, body)| {
+            let mut out = Vec::with_capacity(body.len() + 12);
+            out.extend_from_slice(&[0x1e, 0x0f]);
+            out.extend_from_slice(&msg_id);
+            out.push(i as u8);
+            out.push(chunks.len() as u8);
+            out.extend_from_slice(body);
+            out
+        })
+        .collect()
+}
+
+fn gelf_udp_send(socket: UdpSocket, addr: SocketAddr, receiver: Receiver<Vec<u8>>) {
+    while let Ok(data) = receiver.recv() {
+        for chunk in gelf_udp_chunks(&data) {
+            if let Err(err) = socket.send_to(&chunk, addr) {
+                eprintln!("gelf log send to {} fail:{}", addr, err);
+                break;
+            }
+        }
+    }
+}
+
+fn gelf_tcp_send(addr: SocketAddr, receiver: Receiver<Vec<u8>>) {
+    let mut stream: Option<TcpStream> = None;
+    while let Ok(mut data) = receiver.recv() {
+        //TCP方式以空字节分隔消息
+        data.push(0);
+        //发送失败时重新连接一次,仍失败丢弃该日志
+        for _ in 0..2 {
+            if stream.is_none() {
+                match TcpStream::connect_timeout(&addr, Duration::from_secs(3)) {
+                    Ok(conn) => stream = Some(conn),
+                    Err(err) => {
+                        eprintln!("gelf log connect to {} fail:{}", addr, err);
+                        break;
+                    }
+                }
+            }
+            if let Some(conn) = stream.as_mut() {
+                match conn.write_all(&data) {
+                    Ok(_) => break,
+                    Err(err) => {
+                        eprintln!("gelf log send to {} fail:{}", addr, err);
+                        stream = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_gelf_udp_chunks() {
+    let data = vec![1u8; GELF_UDP_CHUNK_SIZE * 2];
+    let chunks = gelf_udp_chunks(&data);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(&chunks[0][0..2], &[0x1e, 0x0f]);
+    assert_eq!(chunks[0][10], 0);
+    assert_eq!(chunks[2][10], 2);
+    assert_eq!(chunks[2][11], 3);
+    assert_eq!(chunks[0][2..10], chunks[2][2..10]);
+    assert_eq!(
+        chunks.iter().map(|e| e.len() - 12).sum::<usize>(),
+        data.len()
+    );
+    assert_eq!(gelf_udp_chunks(b"{}"), vec![b"{}".to_vec()]);
+}
@@ -1,4 +1,6 @@
+mod log_gelf;
 mod result;
+mod snowflake;
 
 // use config::Config;
 use deadpool_redis::{Config as RedisConfig, Runtime};
@@ -10,20 +12,31 @@ use sqlx::pool::PoolOptions;
 use sqlx::{ConnectOptions, Connection, Database, Pool};
 use std::env;
 use std::str::FromStr;
+use std::time::Duration;
 use tracing_appender::non_blocking::WorkerGuard;
 
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tera::Tera;
+use tracing_subscriber::filter::LevelFilter as TracingLevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
 
 use sqlx_model::TableName;
 
-use crate::{Config, ConfigError};
+use crate::{Config, ConfigData, ConfigError, ConfigSubscriber, ConfigSubscriberFn};
 
+pub use log_gelf::*;
 pub use result::*;
+pub use snowflake::*;
 pub struct AppCore {
     pub app_path: PathBuf,
     pub config: Config,
     log_guard: Option<WorkerGuard>,
+    snowflake_worker: parking_lot::RwLock<Option<Arc<SnowflakeWorker>>>,
 }
 
 impl AppCore {
@@ -56,6 +69,7 @@ impl AppCore {
         Ok(AppCore {
             app_path,
             log_guard: None,
+            snowflake_worker: parking_lot::RwLock::new(None),
             config: Config::new(config_path, "app", config_files).await?,
         })
     }
@@ -76,23 +90,6 @@ impl AppCore {
         }
         Ok(self.app_path.join(path))
     }
-    // pub fn init_gelf() {
-    //     //输出格式 span{args=3}:span{args=3}: mod::mod: message
-    //     let address: Result<std::net::SocketAddr, _> = app_config
-    //         .get_string("log_gelf")
-    //         .unwrap_or_default()
-    //         .parse();
-    //     if address.is_ok() {
-    //         tracing_log::LogTracer::builder()
-    //             .with_max_level(log::LevelFilter::Debug)
-    //             .init()
-    //             .unwrap();
-    //         let bg_task = tracing_gelf::Logger::builder()
-    //             .init_tcp(address.unwrap_or_default())
-    //             .unwrap();
-    //         //actix_web::rt::spawn(bg_task);
-    //     }
-    // }
     pub fn init_tracing(&mut self) -> Result<(), AppCoreError> {
         let log_level = self
             .config
@@ -108,7 +105,12 @@ impl AppCore {
         )
         .unwrap_or(tracing::Level::TRACE);
 
-        let sub = tracing_subscriber::fmt();
+        let log_json = self
+            .config
+            .find(None)
+            .get_string("log_format")
+            .map(|e| e.as_str() == "json")
+            .unwrap_or(false);
 
         let name = self
             .config
@@ -116,24 +118,26 @@ impl AppCore {
             .get_string("log_name")
             .unwrap_or_default();
         if !name.is_empty() {
-            match name.as_str() {
-                "std::out" | "std::err" => {
-                    let sub = sub
-                        .compact() //是否隐藏参数
-                        .with_ansi(true)
-                        .pretty();
-                    if name.as_str() == "std::out" {
-                        sub.with_writer(std::io::stdout)
-                            .with_max_level(log_max_level)
-                            .with_env_filter(log_level)
-                            .try_init()
-                    } else {
-                        sub.with_writer(std::io::stderr)
-                            .with_max_level(log_max_level)
-                            .with_env_filter(log_level)
-                            .try_init()
-                    }
-                    .map_err(|e| AppCoreError::System(e.to_string()))?;
+            let layer = match name.as_str() {
+                "std::out" => log_fmt_layer(log_json, true, std::io::stdout),
+                "std::err" => log_fmt_layer(log_json, true, std::io::stderr),
+                "gelf" => {
+                    let addr = self
+                        .config
+                        .find(None)
+                        .get_string("log_gelf_addr")
+                        .unwrap_or_default();
+                    let addr = addr.parse::<SocketAddr>().map_err(|e| {
+                        AppCoreError::System(format!("log gelf addr [{}] error:{}", addr, e))
+                    })?;
+                    let protocol = GelfProtocol::from_name(
+                        &self
+                            .config
+                            .find(None)
+                            .get_string("log_gelf_protocol")
+                            .unwrap_or_default(),
+                    );
+                    Box::new(GelfLayer::new(addr, protocol)?)
                 }
                 _ => {
                     let dir = self
@@ -144,19 +148,24 @@ impl AppCore {
 
                     let file_appender = tracing_appender::rolling::daily(dir, name);
                     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-                    sub.with_ansi(false)
-                        .with_writer(non_blocking)
-                        .with_max_level(log_max_level)
-                        //.with_env_filter(EnvFilter::from_default_env().add_directive("echo=trace".parse()?))//手动分开配置方式
-                        // 基于span过滤 target[span{field=value}]=level
-                        .with_env_filter(log_level) //格式 模块:最大等级 mod:level
-                        .try_init()
-                        .map_err(|e| AppCoreError::System(e.to_string()))?;
                     self.log_guard = Some(guard);
+                    log_fmt_layer(log_json, false, non_blocking)
                 }
             };
-            // write //只能有一个writer
-            //     .map_err(|e| AppCoreError::Log(e.to_string()));
+            // 基于span过滤 target[span{field=value}]=level
+            // 格式 模块:最大等级 mod:level
+            let filter =
+                EnvFilter::try_new(log_level).map_err(|e| AppCoreError::System(e.to_string()))?;
+            let (filter, handle) = tracing_subscriber::reload::Layer::new(filter);
+            self.config.subscribe(log_level_subscriber(move |filter| {
+                handle.reload(filter).map_err(|e| e.to_string())
+            }));
+            tracing_subscriber::registry()
+                .with(layer)
+                .with(TracingLevelFilter::from_level(log_max_level))
+                .with(filter)
+                .try_init()
+                .map_err(|e| AppCoreError::System(e.to_string()))?;
         }
 
         Ok(())
@@ -172,6 +181,12 @@ impl AppCore {
             .find(None)
             .get_string("database_url")
             .unwrap_or_default();
+        //数据模型及SQL仅支持MySQL,MySQL连接不校验URL协议,未拦截时会按MySQL连接本机
+        if database_url.starts_with("sqlite:") {
+            return Err(AppCoreError::System(
+                "database_url: sqlite is not supported, use a mysql url".to_string(),
+            ));
+        }
         let database_level = self
             .config
             .find(None)
@@ -195,7 +210,48 @@ impl AppCore {
             .await?;
         Ok(poll)
     }
-    pub fn create_snowflake_id_generator(&self) -> snowflake::SnowflakeIdGenerator {
+    /// 通过REDIS租用雪花ID工作ID,之后创建的ID生成器使用租用的工作ID
+    /// 配置 snowflake_worker_lease 为租期秒数,为0时不租用,使用 snowflake_machine_id snowflake_node_id 配置
+    pub async fn init_snowflake_worker(
+        &self,
+        redis: deadpool_redis::Pool,
+    ) -> Result<Arc<SnowflakeWorker>, AppCoreError> {
+        let lease = self
+            .config
+            .find(None)
+            .get_int("snowflake_worker_lease")
+            .unwrap_or(30);
+        let worker = if lease > 0 {
+            let prefix = self
+                .config
+                .find(None)
+                .get_string("snowflake_worker_prefix")
+                .unwrap_or_default();
+            SnowflakeWorker::lease(redis, &prefix, Duration::from_secs(lease as u64)).await?
+        } else {
+            self.config_snowflake_worker()
+        };
+        let worker = Arc::new(worker);
+        *self.snowflake_worker.write() = Some(worker.clone());
+        Ok(worker)
+    }
+    /// 当前雪花ID工作ID,未调用 init_snowflake_worker 时使用配置的工作ID
+    pub fn snowflake_worker(&self) -> Arc<SnowflakeWorker> {
+        if let Some(worker) = self.snowflake_worker.read().as_ref() {
+            return worker.clone();
+        }
+        Arc::new(self.config_snowflake_worker())
+    }
+    /// 创建雪花ID生成器,工作ID租约丢失时拒绝生成,用于需持久化的ID
+    pub fn create_snowflake_id_gen(&self) -> SnowflakeIdGen {
+        SnowflakeIdGen::new(self.snowflake_worker())
+    }
+    /// 创建雪花ID生成器,不检测租约,用于临时ID
+    pub fn create_snowflake_id_generator(&self) -> ::snowflake::SnowflakeIdGenerator {
+        let worker = self.snowflake_worker();
+        ::snowflake::SnowflakeIdGenerator::new(worker.machine_id(), worker.node_id())
+    }
+    fn config_snowflake_worker(&self) -> SnowflakeWorker {
         let machine_id = self
             .config
             .find(None)
@@ -216,7 +272,7 @@ impl AppCore {
                 .into()
             });
         let node_id = (node_id.abs() % 31) as i32;
-        snowflake::SnowflakeIdGenerator::new(machine_id, node_id)
+        SnowflakeWorker::new(machine_id, node_id)
     }
     pub fn create_redis_client(&self) -> Result<redis::Client, AppCoreError> {
         let redis_url = self
@@ -327,3 +383,43 @@ impl AppCore {
     //     })
     // }
 }
+
+type LogLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+//标准输出及文件日志,json格式时包含当前span及上级span的字段
+fn log_fmt_layer<W>(json: bool, std: bool, writer: W) -> LogLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+    if json {
+        Box::new(
+            layer
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .with_ansi(false),
+        )
+    } else if std {
+        Box::new(layer.with_ansi(true).pretty())
+    } else {
+        Box::new(layer.with_ansi(false))
+    }
+}
+
+//配置重新加载时更新日志等级
+fn log_level_subscriber(
+    reload: impl Fn(EnvFilter) -> Result<(), String> + Send + Sync + 'static,
+) -> Arc<dyn ConfigSubscriber> {
+    let log_filter = |config: &ConfigData| {
+        let log_level = config
+            .find(None)
+            .get_string("log_level")
+            .unwrap_or_else(|_| String::from("info"));
+        EnvFilter::try_new(log_level).map_err(|e| e.to_string())
+    };
+    Arc::new(
+        ConfigSubscriberFn::new(Box::new(move |config| reload(log_filter(config)?)))
+            .with_validate(Box::new(move |config| log_filter(config).map(|_| ()))),
+    )
+}
@@ -7,7 +7,10 @@ use std::env::VarError;
 // use std::error::Error;
 // use std::fmt::{Display, Formatter};
 
-use crate::{fluent_message, AppCore, FluentBundleError, FluentMessage, RemoteNotifyError};
+use crate::{
+    fluent_message, AppCore, FluentBundleError, FluentMessage, LimitError, MigrateError,
+    RemoteNotifyError,
+};
 use crate::{ConfigError, IntoFluentMessage};
 #[derive(Debug)]
 pub enum AppCoreError {
@@ -24,6 +27,8 @@ pub enum AppCoreError {
     Config(ConfigError),
     Fluent(FluentBundleError),
     RemoteNotify(RemoteNotifyError),
+    Migrate(MigrateError),
+    Limit(LimitError),
 }
 
 impl IntoFluentMessage for AppCoreError {
@@ -42,6 +47,8 @@ impl IntoFluentMessage for AppCoreError {
             AppCoreError::Config(err) => err.to_fluent_message(),
             AppCoreError::RemoteNotify(err) => err.to_fluent_message(),
             AppCoreError::Fluent(err) => err.to_fluent_message(),
+            AppCoreError::Migrate(err) => err.to_fluent_message(),
+            AppCoreError::Limit(err) => err.to_fluent_message(),
         }
     }
 }
@@ -67,6 +74,16 @@ impl From<RemoteNotifyError> for AppCoreError {
         AppCoreError::RemoteNotify(err)
     }
 }
+impl From<MigrateError> for AppCoreError {
+    fn from(err: MigrateError) -> Self {
+        AppCoreError::Migrate(err)
+    }
+}
+impl From<LimitError> for AppCoreError {
+    fn from(err: LimitError) -> Self {
+        AppCoreError::Limit(err)
+    }
+}
 
 impl From<VarError> for AppCoreError {
     fn from(err: VarError) -> Self {
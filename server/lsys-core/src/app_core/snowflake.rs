@@ -0,0 +1,206 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use snowflake::SnowflakeIdGenerator;
+use tracing::{info, warn};
+
+use crate::{LimitError, LimitResult, RedisLock, RedisLockGuard};
+
+//机器ID及节点ID各占5位
+const SNOWFLAKE_ID_SIZE: i32 = 32;
+const SNOWFLAKE_LEASE_KEY: &str = "snowflake-worker-";
+//租约丢失后重新租用的最小重试间隔,失败时加倍,最大为租期
+const SNOWFLAKE_LEASE_RETRY: Duration = Duration::from_secs(1);
+
+//租用的工作ID状态,与后台重新租用任务共享
+struct WorkerLease {
+    guard: Mutex<Option<RedisLockGuard>>,
+    //已主动释放,不再重新租用
+    released: AtomicBool,
+}
+
+// 雪花ID工作ID
+// 多节点部署时通过REDIS租用空闲的工作ID,避免各节点生成重复ID
+// 租约丢失或超过本地租约截止时间后拒绝生成ID,后台按退避间隔重新租用同一工作ID,租用成功后恢复生成
+pub struct SnowflakeWorker {
+    machine_id: i32,
+    node_id: i32,
+    lease: Option<Arc<WorkerLease>>,
+}
+
+impl SnowflakeWorker {
+    /// 使用固定的工作ID,由部署方保证各节点不重复
+    pub fn new(machine_id: i32, node_id: i32) -> Self {
+        Self {
+            machine_id: machine_id.rem_euclid(SNOWFLAKE_ID_SIZE),
+            node_id: node_id.rem_euclid(SNOWFLAKE_ID_SIZE),
+            lease: None,
+        }
+    }
+    /// 通过REDIS租用空闲的工作ID,从主机名对应的ID开始查找
+    /// * `redis` - REDIS连接池
+    /// * `prefix` - REDIS KEY 前缀
+    /// * `lease` - 租期,持有期间自动续期,节点异常退出后租期结束即可被其他节点使用
+    pub async fn lease(
+        redis: deadpool_redis::Pool,
+        prefix: &str,
+        lease: Duration,
+    ) -> LimitResult<Self> {
+        let lock = RedisLock::new(redis, prefix);
+        let size = SNOWFLAKE_ID_SIZE * SNOWFLAKE_ID_SIZE;
+        let start = crc32fast::hash(
+            hostname::get()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .as_bytes(),
+        ) as i32;
+        for i in 0..size {
+            let worker_id = (start.rem_euclid(size) + i) % size;
+            let key = format!("{}{}", SNOWFLAKE_LEASE_KEY, worker_id);
+            if let Some(guard) = lock.try_lock(&key, lease).await? {
+                let worker_lease = Arc::new(WorkerLease {
+                    guard: Mutex::new(Some(guard)),
+                    released: AtomicBool::new(false),
+                });
+                tokio::spawn(lease_keep(
+                    lock,
+                    key,
+                    lease,
+                    Arc::downgrade(&worker_lease),
+                ));
+                let worker = Self {
+                    machine_id: worker_id / SNOWFLAKE_ID_SIZE,
+                    node_id: worker_id % SNOWFLAKE_ID_SIZE,
+                    lease: Some(worker_lease),
+                };
+                info!(
+                    "snowflake worker lease machine id:{} node id:{}",
+                    worker.machine_id, worker.node_id
+                );
+                return Ok(worker);
+            }
+        }
+        Err(LimitError::LockTimeout(SNOWFLAKE_LEASE_KEY.to_owned()))
+    }
+    pub fn machine_id(&self) -> i32 {
+        self.machine_id
+    }
+    pub fn node_id(&self) -> i32 {
+        self.node_id
+    }
+    /// 是否为租用的工作ID
+    pub fn is_lease(&self) -> bool {
+        self.lease.is_some()
+    }
+    /// 工作ID是否可用,租约丢失,超过截止时间未续期或已释放时返回 LimitError::LockLost
+    /// 租约丢失后在重新租用成功前均返回错误,可用于健康检查
+    pub fn check(&self) -> LimitResult<()> {
+        match &self.lease {
+            None => Ok(()),
+            Some(lease) => match lease.guard.lock().as_ref() {
+                Some(guard) => guard.check(),
+                None => Err(LimitError::LockLost(SNOWFLAKE_LEASE_KEY.to_owned())),
+            },
+        }
+    }
+    /// 释放租用的工作ID,服务停止时调用,释放后不能再生成ID
+    pub async fn release(&self) {
+        let guard = match &self.lease {
+            Some(lease) => {
+                lease.released.store(true, Ordering::Release);
+                lease.guard.lock().take()
+            }
+            None => None,
+        };
+        if let Some(guard) = guard {
+            if let Err(err) = guard.unlock().await {
+                warn!("snowflake worker lease release fail:{:?}", err);
+            }
+        }
+    }
+}
+
+//定时检测租约,丢失时按退避间隔重新租用同一工作ID
+//工作ID释放或不再被引用时退出
+async fn lease_keep(lock: RedisLock, key: String, lease: Duration, state: Weak<WorkerLease>) {
+    let check_time = (lease / 3).max(SNOWFLAKE_LEASE_RETRY);
+    let mut retry_time = SNOWFLAKE_LEASE_RETRY;
+    let mut wait_time = check_time;
+    loop {
+        tokio::time::sleep(wait_time).await;
+        let state = match state.upgrade() {
+            Some(state) => state,
+            None => return,
+        };
+        if state.released.load(Ordering::Acquire) {
+            return;
+        }
+        let lost = state
+            .guard
+            .lock()
+            .as_ref()
+            .map(|e| e.check().is_err())
+            .unwrap_or(true);
+        if !lost {
+            retry_time = SNOWFLAKE_LEASE_RETRY;
+            wait_time = check_time;
+            continue;
+        }
+        match lock.try_lock(&key, lease).await {
+            Ok(Some(guard)) => {
+                if state.released.load(Ordering::Acquire) {
+                    if let Err(err) = guard.unlock().await {
+                        warn!("snowflake worker lease release fail:{:?}", err);
+                    }
+                    return;
+                }
+                *state.guard.lock() = Some(guard);
+                info!("snowflake worker {} lease renewed", key);
+                retry_time = SNOWFLAKE_LEASE_RETRY;
+                wait_time = check_time;
+            }
+            Ok(None) => {
+                warn!(
+                    "snowflake worker {} lease lost and held by other node, retry after {:?}",
+                    key, retry_time
+                );
+                wait_time = retry_time;
+                retry_time = (retry_time * 2).min(lease.max(SNOWFLAKE_LEASE_RETRY));
+            }
+            Err(err) => {
+                warn!(
+                    "snowflake worker {} lease fail:{:?}, retry after {:?}",
+                    key, err, retry_time
+                );
+                wait_time = retry_time;
+                retry_time = (retry_time * 2).min(lease.max(SNOWFLAKE_LEASE_RETRY));
+            }
+        }
+    }
+}
+
+/// 雪花ID生成器,工作ID租约丢失时拒绝生成
+pub struct SnowflakeIdGen {
+    worker: Arc<SnowflakeWorker>,
+    generator: Mutex<SnowflakeIdGenerator>,
+}
+
+impl SnowflakeIdGen {
+    pub fn new(worker: Arc<SnowflakeWorker>) -> Self {
+        let generator = SnowflakeIdGenerator::new(worker.machine_id, worker.node_id);
+        Self {
+            worker,
+            generator: Mutex::new(generator),
+        }
+    }
+    pub fn generate(&self) -> LimitResult<u64> {
+        self.worker.check()?;
+        Ok(self.generator.lock().real_time_generate() as u64)
+    }
+}
@@ -0,0 +1,172 @@
+use redis::AsyncCommands;
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::{debug, warn};
+
+// 本地缓存的REDIS二级缓存
+// 本地缓存未命中时先从REDIS读取,各服务器共用,减少数据库查询
+pub struct LocalCacheRedis<T> {
+    redis: deadpool_redis::Pool,
+    cache_name: String,
+    //创建时确定序列化方式,使 LocalCache 本身不依赖 serde
+    encode: fn(&T) -> serde_json::Result<String>,
+    decode: fn(&str) -> serde_json::Result<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> LocalCacheRedis<T> {
+    /// * `cache_name` - 缓存名,同 LocalCacheConfig 的 cache_name
+    pub fn new(redis: deadpool_redis::Pool, cache_name: &str) -> Self {
+        Self {
+            redis,
+            cache_name: cache_name.to_owned(),
+            encode: |data| serde_json::to_string(data),
+            decode: |data| serde_json::from_str(data),
+        }
+    }
+}
+
+impl<T> LocalCacheRedis<T> {
+    fn data_key(&self, key: &str) -> String {
+        format!("lsys-cache:{}:{}", self.cache_name, key)
+    }
+    fn lock_key(&self, key: &str) -> String {
+        format!("lsys-cache-lock:{}:{}", self.cache_name, key)
+    }
+    /// 返回缓存数据及REDIS中剩余缓存时间,单位秒,未设置过期时为0
+    pub(crate) async fn get(&self, key: &str) -> Option<(T, u64)> {
+        let mut redis = match self.redis.get().await {
+            Ok(redis) => redis,
+            Err(err) => {
+                warn!("redis cache get conn fail:{}", err);
+                return None;
+            }
+        };
+        let data_key = self.data_key(key);
+        let (data, ttl): (Option<String>, i64) = match redis::pipe()
+            .get(&data_key)
+            .ttl(&data_key)
+            .query_async(&mut redis)
+            .await
+        {
+            Ok(data) => data,
+            Err(err) => {
+                warn!("redis cache get {} fail:{}", key, err);
+                return None;
+            }
+        };
+        match (self.decode)(&data?) {
+            Ok(data) => {
+                debug!("get redis cache :{} msg:cache hit,ttl:{}", key, ttl);
+                //ttl 为-1时未设置过期,不足一秒时为0
+                let ttl = if ttl < 0 { 0 } else { ttl.max(1) as u64 };
+                Some((data, ttl))
+            }
+            Err(err) => {
+                warn!("redis cache decode {} fail:{}", key, err);
+                None
+            }
+        }
+    }
+    /// * `time` - 缓存时间,单位秒
+    pub(crate) async fn set(&self, key: &str, data: &T, time: u64) {
+        let data = match (self.encode)(data) {
+            Ok(data) => data,
+            Err(err) => {
+                warn!("redis cache encode {} fail:{}", key, err);
+                return;
+            }
+        };
+        match self.redis.get().await {
+            Ok(mut redis) => {
+                if let Err(err) = redis
+                    .set_ex::<_, _, ()>(self.data_key(key), data, time.max(1) as usize)
+                    .await
+                {
+                    warn!("redis cache set {} fail:{}", key, err);
+                }
+            }
+            Err(err) => warn!("redis cache set conn fail:{}", err),
+        }
+    }
+    pub(crate) async fn del(&self, key: &str) {
+        match self.redis.get().await {
+            Ok(mut redis) => {
+                if let Err(err) = redis.del::<_, ()>(self.data_key(key)).await {
+                    warn!("redis cache del {} fail:{}", key, err);
+                }
+            }
+            Err(err) => warn!("redis cache del conn fail:{}", err),
+        }
+    }
+    /// 延迟再次删除,清理前已在加载的请求可能把旧数据写回REDIS
+    /// * `delay` - 延迟时间,单位毫秒
+    pub(crate) fn del_delay(&self, key: &str, delay: u64) {
+        let redis = self.redis.clone();
+        let data_key = self.data_key(key);
+        let key = key.to_owned();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            match redis.get().await {
+                Ok(mut redis) => {
+                    if let Err(err) = redis.del::<_, ()>(data_key).await {
+                        warn!("redis cache delay del {} fail:{}", key, err);
+                    }
+                }
+                Err(err) => warn!("redis cache delay del conn fail:{}", err),
+            }
+        });
+    }
+    //删除该缓存的全部数据
+    pub(crate) async fn del_all(&self) {
+        let mut redis = match self.redis.get().await {
+            Ok(redis) => redis,
+            Err(err) => {
+                warn!("redis cache del all conn fail:{}", err);
+                return;
+            }
+        };
+        let pattern = format!("lsys-cache:{}:*", self.cache_name);
+        let keys = match redis.scan_match::<_, String>(pattern).await {
+            Ok(mut iter) => {
+                let mut keys = vec![];
+                while let Some(key) = iter.next_item().await {
+                    keys.push(key);
+                }
+                keys
+            }
+            Err(err) => {
+                warn!("redis cache scan {} fail:{}", self.cache_name, err);
+                return;
+            }
+        };
+        for chunk in keys.chunks(100) {
+            if let Err(err) = redis.del::<_, ()>(chunk).await {
+                warn!("redis cache del all {} fail:{}", self.cache_name, err);
+            }
+        }
+    }
+    /// 锁定数据加载,已被其他服务器锁定时返回false
+    /// REDIS不可用时返回true,直接加载
+    /// * `timeout` - 锁定超时,单位毫秒
+    pub(crate) async fn load_lock(&self, key: &str, timeout: u64) -> bool {
+        let mut redis = match self.redis.get().await {
+            Ok(redis) => redis,
+            Err(_) => return true,
+        };
+        let lock: Result<Option<String>, _> = redis::cmd("SET")
+            .arg(self.lock_key(key))
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg(timeout)
+            .query_async(&mut redis)
+            .await;
+        lock.map(|e| e.is_some()).unwrap_or(true)
+    }
+    pub(crate) async fn load_unlock(&self, key: &str) {
+        if let Ok(mut redis) = self.redis.get().await {
+            if let Err(err) = redis.del::<_, ()>(self.lock_key(key)).await {
+                warn!("redis cache unlock {} fail:{}", key, err);
+            }
+        }
+    }
+}
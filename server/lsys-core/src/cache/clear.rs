@@ -1,23 +1,36 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::warn;
 
-use crate::{MsgSendBody, RemoteTask};
+use crate::{ConfigData, ConfigSubscriber, MsgSendBody, RemoteTask};
 
-use super::REMOTE_NOTIFY_TYPE_CACHE;
+use super::{LocalCacheStats, REMOTE_NOTIFY_TYPE_CACHE};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct LocalCacheMessage {
     pub cache_name: String,
     pub message: String,
+    //清理该缓存的全部数据
+    #[serde(default)]
+    pub clear_all: bool,
 }
 impl LocalCacheMessage {
     pub fn new(cache_name: String, message: String) -> Self {
         Self {
             cache_name,
             message,
+            clear_all: false,
+        }
+    }
+    pub fn clear_all(cache_name: String) -> Self {
+        Self {
+            cache_name,
+            message: String::new(),
+            clear_all: true,
         }
     }
 }
@@ -26,15 +39,63 @@ impl LocalCacheMessage {
 pub trait LocalCacheClearItem {
     fn cache_name(&self) -> &str;
     async fn clear_from_message(&self, msg: &str) -> Result<(), String>;
+    /// 删除本地全部缓存
+    async fn clear_all_from_message(&self);
+    /// 清理缓存并通知其他服务器清理
+    /// * `key` - 缓存KEY,为None时清理全部
+    async fn clear(&self, key: Option<&str>) -> Result<(), String>;
+    /// 缓存运行统计
+    async fn stats(&self) -> LocalCacheStats;
+    /// 启用或停用缓存
+    async fn set_enable(&self, enable: bool);
 }
 
 /// 订阅远程通知清理本地缓存
+#[derive(Clone)]
 pub struct LocalCacheClear {
-    cache_list: Vec<Box<dyn LocalCacheClearItem + Sync + Send + 'static>>,
+    cache_list: Arc<Vec<Box<dyn LocalCacheClearItem + Sync + Send + 'static>>>,
 }
 impl LocalCacheClear {
     pub fn new(cache_list: Vec<Box<dyn LocalCacheClearItem + Sync + Send + 'static>>) -> Self {
-        LocalCacheClear { cache_list }
+        LocalCacheClear {
+            cache_list: Arc::new(cache_list),
+        }
+    }
+    /// 所有缓存的运行统计
+    pub async fn stats(&self) -> Vec<LocalCacheStats> {
+        let mut out = Vec::with_capacity(self.cache_list.len());
+        for item in self.cache_list.iter() {
+            out.push(item.stats().await);
+        }
+        out
+    }
+    /// 清理所有服务器上的指定缓存
+    /// * `cache_name` - 缓存名
+    /// * `key` - 缓存KEY,为None时清理全部
+    pub async fn clear(&self, cache_name: &str, key: Option<&str>) -> Result<(), String> {
+        match self
+            .cache_list
+            .iter()
+            .find(|e| e.cache_name() == cache_name)
+        {
+            Some(item) => item.clear(key).await,
+            None => Err(format!("not find cache name:{}", cache_name)),
+        }
+    }
+    /// 启用或停用所有缓存
+    pub async fn set_enable(&self, enable: bool) {
+        for item in self.cache_list.iter() {
+            item.set_enable(enable).await;
+        }
+    }
+}
+
+//配置重新加载时按 use_cache 启用或停用缓存
+#[async_trait]
+impl ConfigSubscriber for LocalCacheClear {
+    async fn reload(&self, config: &ConfigData) {
+        let use_cache = config.find(None).get_bool("use_cache").unwrap_or(false);
+        self.set_enable(use_cache).await;
     }
 }
 
@@ -48,7 +109,10 @@ impl RemoteTask for LocalCacheClear {
             serde_json::from_value::<LocalCacheMessage>(msg.data).map_err(|e| e.to_string())?;
         for user_cache_type in self.cache_list.iter() {
             if user_cache_type.cache_name() == cache_msg.cache_name {
-                if let Err(e) = user_cache_type.clear_from_message(&cache_msg.message).await {
+                if cache_msg.clear_all {
+                    user_cache_type.clear_all_from_message().await;
+                } else if let Err(e) = user_cache_type.clear_from_message(&cache_msg.message).await
+                {
                     warn!("user cache clear parse fail:{}", e);
                 }
                 return Ok(None);
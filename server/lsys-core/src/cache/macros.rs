@@ -2,19 +2,10 @@
 macro_rules! impl_cache_fetch_one {
     ($fn:ident,$dao_field:ident,$cache_field:ident,$fetch_type:ty,$result:ty) => {
         pub async fn $fn(&self, id: &$fetch_type) -> $result {
-            match self.$dao_field.$cache_field.get(&id).await {
-                Some(data) => Ok(data),
-                None => match self.$dao_field.$fn(&id).await {
-                    Ok(data) => {
-                        self.$dao_field
-                            .$cache_field
-                            .set(id.to_owned(), data.clone(), 0)
-                            .await;
-                        Ok(data)
-                    }
-                    Err(e) => Err(e),
-                },
-            }
+            self.$dao_field
+                .$cache_field
+                .get_or_load(id, || self.$dao_field.$fn(id))
+                .await
         }
     };
 }
@@ -1,23 +1,33 @@
 mod clear;
 #[macro_use]
 mod macros;
+mod cache_redis;
+mod stats;
 use hashlink::LruCache;
 
 use std::{
+    collections::HashMap,
+    future::Future,
     str::FromStr,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
+use serde::{de::DeserializeOwned, Serialize};
 use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
 pub use clear::*;
+pub use cache_redis::*;
+pub use stats::*;
 
 use crate::{now_time, IntoFluentMessage, LocalExecType, RemoteNotify};
 
 pub const REMOTE_NOTIFY_TYPE_CACHE: u8 = 101;
+//未指定缓存数量时的默认缓存数量
+const DEFAULT_CACHE_SIZE: usize = 100;
 
 #[derive(Clone, Debug)]
 pub struct CacheData<T: Clone> {
@@ -35,6 +45,8 @@ pub struct LocalCacheConfig {
     pub cache_size: usize,
     /// 缓存刷新时间
     pub refresh_time: u64,
+    /// REDIS二级缓存时间,设置为0不使用REDIS缓存
+    pub redis_time: u64,
 }
 
 impl LocalCacheConfig {
@@ -42,10 +54,30 @@ impl LocalCacheConfig {
         Self {
             cache_name,
             cache_time: save_time.unwrap_or(120)+10,
-            cache_size:cache_size.unwrap_or(100),
+            cache_size:cache_size.unwrap_or(DEFAULT_CACHE_SIZE),
             refresh_time: save_time.unwrap_or(120),
+            redis_time: 0,
         }
     }
+    /// 使用REDIS二级缓存,需配合 LocalCache::with_redis 使用
+    /// * `redis_time` - REDIS缓存时间,单位秒
+    pub fn use_redis(mut self, redis_time: u64) -> Self {
+        self.redis_time = redis_time;
+        self
+    }
+}
+
+//加载数据时REDIS锁定时间,单位毫秒
+const LOAD_LOCK_TIME: u64 = 3000;
+//其他服务器加载数据时,等待其加载结果的次数及间隔
+const LOAD_WAIT_TIMES: usize = 10;
+const LOAD_WAIT_TIME: u64 = 50;
+
+//加载中的KEY
+struct LoadingKey {
+    lock: Mutex<()>,
+    //加载期间被清理时递增,加载完成后不变才写入缓存
+    version: AtomicU64,
 }
 
 /// 本地数据缓存
@@ -55,9 +87,15 @@ where
     T: Clone,
 {
     cache_config: LocalCacheConfig,
+    //当前缓存数量,为0时不使用缓存,配置重新加载时可修改
+    cache_size: AtomicUsize,
     cache_data: Mutex<LruCache<K, CacheData<T>>>,
     refresh_lock: AtomicBool,
     remote_notify: Arc<RemoteNotify>,
+    redis: Option<LocalCacheRedis<T>>,
+    //加载中的KEY,同一KEY同时只加载一次
+    loading: parking_lot::Mutex<HashMap<String, Arc<LoadingKey>>>,
+    counter: LocalCacheCounter,
 }
 
 impl<K, T> LocalCache<K, T>
@@ -72,22 +110,105 @@ where
         LocalCache {
             remote_notify,
             cache_config,
+            cache_size: AtomicUsize::new(cache_config.cache_size),
             refresh_lock: AtomicBool::new(false),
             cache_data: Mutex::new(LruCache::new(cache_config.cache_size)),
+            redis: None,
+            loading: parking_lot::Mutex::new(HashMap::new()),
+            counter: LocalCacheCounter::default(),
         }
     }
+    /// 启用REDIS二级缓存,需 LocalCacheConfig::use_redis 设置缓存时间
+    /// 本地缓存未命中时从REDIS读取,REDIS未命中时再从数据源加载
+    pub fn with_redis(mut self, redis: deadpool_redis::Pool) -> Self
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        if self.cache_config.redis_time > 0 {
+            self.redis = Some(LocalCacheRedis::new(redis, self.cache_config.cache_name));
+        }
+        self
+    }
     pub fn config(&self) -> &LocalCacheConfig {
         &self.cache_config
     }
-    pub async fn get(&self, key: &K) -> Option<T> {
-        if self.cache_config.cache_size == 0 {
+    fn cache_size(&self) -> usize {
+        self.cache_size.load(Ordering::Relaxed)
+    }
+    /// 启用或停用缓存,停用时清空本地缓存
+    /// 启用时使用创建时的缓存数量,创建时未启用则使用默认数量
+    pub async fn set_enable(&self, enable: bool) {
+        let cache_size = match (enable, self.cache_config.cache_size) {
+            (false, _) => 0,
+            (true, 0) => DEFAULT_CACHE_SIZE,
+            (true, size) => size,
+        };
+        let mut lc = self.cache_data.lock().await;
+        self.cache_size.store(cache_size, Ordering::Relaxed);
+        if cache_size == 0 {
+            lc.clear();
+        }
+        lc.set_capacity(cache_size);
+    }
+    pub async fn get(&self, key: &K) -> Option<T>
+    where
+        K: Clone,
+    {
+        if self.cache_size() == 0 {
             return None;
         }
+        match self.local_get(key).await {
+            Ok(Some(data)) => {
+                LocalCacheCounter::incr(&self.counter.hits);
+                Some(data)
+            }
+            Ok(None) => {
+                LocalCacheCounter::incr(&self.counter.refresh);
+                None
+            }
+            Err(_) => {
+                //本地缓存不存在时从REDIS读取
+                let data = match self.redis.as_ref() {
+                    Some(redis) => redis.get(&key.to_string()).await,
+                    None => None,
+                };
+                match data {
+                    Some((data, ttl)) => {
+                        LocalCacheCounter::incr(&self.counter.redis_hits);
+                        //本地缓存时间不超过REDIS剩余时间
+                        self.local_set(key.clone(), data.clone(), ttl).await;
+                        Some(data)
+                    }
+                    None => {
+                        LocalCacheCounter::incr(&self.counter.misses);
+                        None
+                    }
+                }
+            }
+        }
+    }
+    /// 缓存运行统计
+    pub async fn stats(&self) -> LocalCacheStats {
+        LocalCacheStats {
+            cache_name: self.cache_config.cache_name.to_owned(),
+            cache_time: self.cache_config.cache_time,
+            cache_size: self.cache_size(),
+            size: self.cache_data.lock().await.len(),
+            use_redis: self.redis.is_some(),
+            hits: LocalCacheCounter::get(&self.counter.hits),
+            misses: LocalCacheCounter::get(&self.counter.misses),
+            redis_hits: LocalCacheCounter::get(&self.counter.redis_hits),
+            evictions: LocalCacheCounter::get(&self.counter.evictions),
+            refresh: LocalCacheCounter::get(&self.counter.refresh),
+        }
+    }
+    //本地缓存读取,缓存不存在时返回错误,需刷新时返回None
+    async fn local_get(&self, key: &K) -> Result<Option<T>, ()> {
         let mut lc = self.cache_data.lock().await;
         if let Some(ua) = lc.get(key) {
             if self.cache_config.cache_time==0{
                 debug!("get cache :{} msg:cache hit",key.to_string());
-                return Some(ua.data.to_owned());
+                return Ok(Some(ua.data.to_owned()));
             }
             let now_time = now_time().unwrap_or_default();
             debug!(
@@ -96,11 +217,7 @@ where
                 ua.time_out,
                 self.cache_config.refresh_time,
                 now_time,
-                if ua.time_out > now_time {
-                    ua.time_out - now_time
-                } else {
-                    0
-                }
+                ua.time_out.saturating_sub(now_time)
             );
             if ua.time_out > now_time {
                 if self.cache_config.refresh_time > 0
@@ -111,30 +228,42 @@ where
                         .unwrap_or(false)
                 {
                     debug!("get cache :{} msg:cache refresh",key.to_string());
-                    return None;
+                    return Ok(None);
                 }
                 debug!("get cache :{} msg:cache hit",key.to_string());
-                return Some(ua.data.to_owned());
+                return Ok(Some(ua.data.to_owned()));
             }
             debug!("get cache :{} msg:cache timeout",key.to_string());
             lc.remove(key);
         }
         debug!("get cache :{} msg:no cache",key.to_string());
-        None
+        Err(())
     }
-    pub async fn set(&self, key: K, data: T, mut set_time: u64) {
-        if self.cache_config.cache_size == 0 {
+    pub async fn set(&self, key: K, data: T, set_time: u64) {
+        if self.cache_size() == 0 {
             return;
         }
+        if let Some(redis) = self.redis.as_ref() {
+            let redis_time = if set_time == 0 || set_time > self.cache_config.redis_time {
+                self.cache_config.redis_time
+            } else {
+                set_time
+            };
+            redis.set(&key.to_string(), &data, redis_time).await;
+        }
+        self.local_set(key, data, set_time).await;
+    }
+    async fn local_set(&self, key: K, data: T, mut set_time: u64) {
         if self.cache_config.cache_time==0{
             debug!("save cache :{} msg:save finsh",key.to_string());
-            self.cache_data.lock().await.insert(
+            self.local_insert(
                 key,
                 CacheData {
                     time_out: 0,
                     data,
                 },
-            );
+            )
+            .await;
             return;
         }
         let now_time = now_time().unwrap_or_default();
@@ -143,28 +272,82 @@ where
             set_time = cache_time;
         }
         debug!("save cache :{} msg:save finsh,timeout:{} refresh clear:{}",key.to_string(),now_time + set_time,self.cache_config.refresh_time>0);
-        self.cache_data.lock().await.insert(
+        self.local_insert(
             key,
             CacheData {
                 time_out: now_time + set_time,
                 data,
             },
-        );
+        )
+        .await;
         if self.cache_config.refresh_time > 0 {
             self.refresh_lock.store(false, Ordering::Relaxed);
         }
     }
+    async fn local_insert(&self, key: K, data: CacheData<T>) {
+        let mut lc = self.cache_data.lock().await;
+        if !lc.contains_key(&key) && lc.len() >= lc.capacity() {
+            LocalCacheCounter::incr(&self.counter.evictions);
+        }
+        lc.insert(key, data);
+    }
     pub async fn del(&self, key: &K) {
-        if self.cache_config.cache_size == 0 {
+        if self.cache_size() == 0 {
             return;
         }
         self.cache_data.lock().await.remove(key);
+        if let Some(item) = self.loading.lock().get(&key.to_string()) {
+            item.version.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    /// 删除本地全部缓存
+    pub async fn del_all(&self) {
+        if self.cache_size() == 0 {
+            return;
+        }
+        self.cache_data.lock().await.clear();
+        for item in self.loading.lock().values() {
+            item.version.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    /// 清理全部缓存并通知其他服务器清理
+    pub async fn clear_all(&self) {
+        if self.cache_size() == 0 {
+            return;
+        }
+        self.del_all().await;
+        if let Some(redis) = self.redis.as_ref() {
+            redis.del_all().await;
+        }
+        let send_msg = LocalCacheMessage::clear_all(self.cache_config.cache_name.to_string());
+        if let Err(err) = self
+            .remote_notify
+            .call(
+                REMOTE_NOTIFY_TYPE_CACHE,
+                send_msg,
+                None,
+                LocalExecType::RemoteExec,
+                None,
+            )
+            .await
+        {
+            warn!(
+                "notify clear all cache error:{}",
+                err.to_fluent_message().default_format()
+            );
+        }
     }
     pub async fn clear(&self, key: &K) {
-        if self.cache_config.cache_size == 0 {
+        if self.cache_size() == 0 {
             return;
         }
         self.del(key).await;
+        if let Some(redis) = self.redis.as_ref() {
+            let key_str = key.to_string();
+            redis.del(&key_str).await;
+            //其他服务器清理前已开始的加载可能写回旧数据,超过加载锁定时间后再删除一次
+            redis.del_delay(&key_str, LOAD_LOCK_TIME);
+        }
         let send_msg =
             LocalCacheMessage::new(self.cache_config.cache_name.to_string(), key.to_string());
         if let Err(err) = self
@@ -184,4 +367,103 @@ where
             );
         }
     }
+    /// 获取缓存,不存在时通过 load 加载并写入缓存
+    /// 同一KEY同时只有一个加载,其他请求等待加载结果
+    /// 启用REDIS时同一KEY在各服务器间也只有一个加载
+    pub async fn get_or_load<F, Fut, E>(&self, key: &K, load: F) -> Result<T, E>
+    where
+        K: Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Some(data) = self.get(key).await {
+            return Ok(data);
+        }
+        if self.cache_size() == 0 {
+            return load().await;
+        }
+        let key_str = key.to_string();
+        let key_lock = self
+            .loading
+            .lock()
+            .entry(key_str.clone())
+            .or_insert_with(|| {
+                Arc::new(LoadingKey {
+                    lock: Mutex::new(()),
+                    version: AtomicU64::new(0),
+                })
+            })
+            .clone();
+        let out = self.load_lock(key, &key_str, &key_lock, load).await;
+        let mut loading = self.loading.lock();
+        //仅剩当前及表中引用时移除
+        if Arc::strong_count(&key_lock) <= 2 {
+            loading.remove(&key_str);
+        }
+        out
+    }
+    async fn load_lock<F, Fut, E>(
+        &self,
+        key: &K,
+        key_str: &str,
+        key_lock: &LoadingKey,
+        load: F,
+    ) -> Result<T, E>
+    where
+        K: Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let _guard = match key_lock.lock.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                //等待其他请求加载完成,加载成功时直接使用其结果
+                let guard = key_lock.lock.lock().await;
+                if let Ok(Some(data)) = self.local_get(key).await {
+                    return Ok(data);
+                }
+                guard
+            }
+        };
+        let version = key_lock.version.load(Ordering::Relaxed);
+        let redis = match self.redis.as_ref() {
+            Some(redis) => redis,
+            None => {
+                let data = load().await?;
+                self.load_set(key, key_lock, version, &data).await;
+                return Ok(data);
+            }
+        };
+        let is_lock = redis.load_lock(key_str, LOAD_LOCK_TIME).await;
+        if !is_lock {
+            //其他服务器加载中,等待其写入REDIS
+            for _ in 0..LOAD_WAIT_TIMES {
+                tokio::time::sleep(Duration::from_millis(LOAD_WAIT_TIME)).await;
+                if let Some((data, ttl)) = redis.get(key_str).await {
+                    self.local_set(key.clone(), data.clone(), ttl).await;
+                    return Ok(data);
+                }
+            }
+            debug!("get cache :{} msg:wait remote load timeout", key_str);
+        }
+        let out = load().await;
+        if let Ok(data) = &out {
+            self.load_set(key, key_lock, version, data).await;
+        }
+        if is_lock {
+            redis.load_unlock(key_str).await;
+        }
+        out
+    }
+    //写入加载结果,加载期间该KEY被清理时不写入,避免旧数据覆盖清理
+    async fn load_set(&self, key: &K, key_lock: &LoadingKey, version: u64, data: &T)
+    where
+        K: Clone,
+    {
+        if key_lock.version.load(Ordering::Relaxed) != version {
+            debug!("get cache :{} msg:cleared on load, skip save", key.to_string());
+            return;
+        }
+        self.set(key.clone(), data.clone(), 0).await;
+    }
 }
@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+
+//缓存运行统计,为当前服务器数据
+#[derive(Serialize, Clone, Debug)]
+pub struct LocalCacheStats {
+    pub cache_name: String,
+    //缓存时间
+    pub cache_time: u64,
+    //缓存总数量
+    pub cache_size: usize,
+    //当前缓存数量
+    pub size: usize,
+    //是否使用REDIS二级缓存
+    pub use_redis: bool,
+    //命中次数
+    pub hits: u64,
+    //未命中次数
+    pub misses: u64,
+    //本地未命中但从REDIS命中次数
+    pub redis_hits: u64,
+    //缓存数量超出被移除次数
+    pub evictions: u64,
+    //需刷新缓存的次数
+    pub refresh: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct LocalCacheCounter {
+    pub hits: AtomicU64,
+    pub misses: AtomicU64,
+    pub redis_hits: AtomicU64,
+    pub evictions: AtomicU64,
+    pub refresh: AtomicU64,
+}
+
+impl LocalCacheCounter {
+    pub fn incr(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn get(counter: &AtomicU64) -> u64 {
+        counter.load(Ordering::Relaxed)
+    }
+}
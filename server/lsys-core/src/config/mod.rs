@@ -1,13 +1,46 @@
+mod notify;
 mod result;
+mod subscriber;
+pub use notify::*;
 pub use result::*;
+pub use subscriber::*;
 
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use config::builder::DefaultState;
+use parking_lot::RwLock;
+use tracing::{info, warn};
+
+use crate::IntoFluentMessage;
+
+//解析后的配置数据
+pub struct ConfigData {
+    def_config: Arc<config::Config>,
+    configs: HashMap<String, Arc<config::Config>>,
+}
+
+impl ConfigData {
+    pub fn find(&self, crate_name: Option<&str>) -> &Arc<config::Config> {
+        match crate_name {
+            Some(name) => self.configs.get(name),
+            None => Some(&self.def_config),
+        }
+        .unwrap_or(&self.def_config)
+    }
+}
 
 pub struct Config {
-    def_config: config::Config,
-    configs: HashMap<String, config::Config>,
+    path: PathBuf,
+    app_config: String,
+    crate_config: Option<Vec<String>>,
+    data: RwLock<Arc<ConfigData>>,
+    subscribers: RwLock<Vec<Arc<dyn ConfigSubscriber>>>,
+    reload_lock: tokio::sync::Mutex<()>,
 }
 
 impl Config {
@@ -30,18 +63,35 @@ impl Config {
         app_config: &str,
         crate_config: Option<&[&str]>,
     ) -> Result<Self, ConfigError> {
+        let crate_config =
+            crate_config.map(|e| e.iter().map(|t| t.to_string()).collect::<Vec<_>>());
+        let data = Self::load(path.as_ref(), app_config, &crate_config).await?;
+        Ok(Config {
+            path: path.as_ref().to_path_buf(),
+            app_config: app_config.to_owned(),
+            crate_config,
+            data: RwLock::new(Arc::new(data)),
+            subscribers: RwLock::new(vec![]),
+            reload_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+    async fn load(
+        path: &Path,
+        app_config: &str,
+        crate_config: &Option<Vec<String>>,
+    ) -> Result<ConfigData, ConfigError> {
         let default_config = Self::default_config(&path, app_config).build()?;
         let mut crate_configs = HashMap::new();
         match crate_config {
             Some(config_name) => {
                 for item in config_name {
-                    let file_path = path.as_ref().to_path_buf().join(format!("./{}.toml", item));
+                    let file_path = path.to_path_buf().join(format!("./{}.toml", item));
                     let crate_config = Self::default_config(&path, app_config)
                         .add_source(config::File::from(file_path));
-                    crate_configs.insert(item.to_string(), crate_config.build()?);
+                    crate_configs.insert(item.to_string(), Arc::new(crate_config.build()?));
                 }
             }
-            None => match tokio::fs::read_dir(path.as_ref()).await {
+            None => match tokio::fs::read_dir(path).await {
                 Ok(mut dir) => {
                     while let Some(fileentry) = dir.next_entry().await? {
                         if !fileentry.file_type().await?.is_file() {
@@ -62,25 +112,85 @@ impl Config {
                         }
                         let crate_config = Self::default_config(&path, app_config)
                             .add_source(config::File::from(file_path));
-                        crate_configs.insert(file_name, crate_config.build()?);
+                        crate_configs.insert(file_name, Arc::new(crate_config.build()?));
                     }
                 }
                 Err(err) => {
-                    tracing::error!("fluent dir:{:?} on {:?}", err, path.as_ref());
+                    tracing::error!("fluent dir:{:?} on {:?}", err, path);
                 }
             },
         }
-        Ok(Config {
-            def_config: default_config,
+        Ok(ConfigData {
+            def_config: Arc::new(default_config),
             configs: crate_configs,
         })
     }
-    pub fn find(&self, crate_name: Option<&str>) -> &config::Config {
-        match crate_name {
-            Some(name) => self.configs.get(name),
-            None => Some(&self.def_config),
+    /// 获取配置,重新加载后再次获取可得到新配置
+    pub fn find(&self, crate_name: Option<&str>) -> Arc<config::Config> {
+        self.data.read().find(crate_name).clone()
+    }
+    /// 当前的全部配置数据
+    pub fn data(&self) -> Arc<ConfigData> {
+        self.data.read().clone()
+    }
+    /// 添加配置重新加载的订阅
+    pub fn subscribe(&self, subscriber: Arc<dyn ConfigSubscriber>) {
+        self.subscribers.write().push(subscriber);
+    }
+    /// 重新读取配置文件
+    /// 所有订阅校验通过后才替换当前配置,并通知所有订阅
+    pub async fn reload(&self) -> Result<(), ConfigError> {
+        let _lock = self.reload_lock.lock().await;
+        let data = Self::load(&self.path, &self.app_config, &self.crate_config).await?;
+        let subscribers = self.subscribers.read().clone();
+        for item in subscribers.iter() {
+            item.validate(&data).map_err(ConfigError::Validate)?;
+        }
+        let data = Arc::new(data);
+        *self.data.write() = data.clone();
+        for item in subscribers.iter() {
+            item.reload(&data).await;
+        }
+        info!("config reload from:{:?}", self.path);
+        Ok(())
+    }
+    //配置目录中配置文件的修改时间
+    async fn files_mtime(&self) -> Vec<(PathBuf, SystemTime)> {
+        let mut out = vec![];
+        let mut dir = match tokio::fs::read_dir(&self.path).await {
+            Ok(dir) => dir,
+            Err(_) => return out,
+        };
+        while let Ok(Some(fileentry)) = dir.next_entry().await {
+            let file_path = fileentry.path();
+            if file_path.extension().unwrap_or_default() != "toml" {
+                continue;
+            }
+            if let Ok(mtime) = fileentry.metadata().await.and_then(|e| e.modified()) {
+                out.push((file_path, mtime));
+            }
+        }
+        out.sort();
+        out
+    }
+    /// 监听配置文件变化,变化后重新加载配置
+    /// * `interval` - 检测间隔
+    pub async fn watch(&self, interval: Duration) {
+        let mut last = self.files_mtime().await;
+        loop {
+            tokio::time::sleep(interval).await;
+            let now = self.files_mtime().await;
+            if now == last {
+                continue;
+            }
+            last = now;
+            if let Err(err) = self.reload().await {
+                warn!(
+                    "config reload fail:{}",
+                    err.to_fluent_message().default_format()
+                );
+            }
         }
-        .unwrap_or(&self.def_config)
     }
 }
 
@@ -90,3 +200,35 @@ macro_rules! config {
         $config_mgr.find(Some(env!("CARGO_PKG_NAME")))
     };
 }
+
+#[tokio::test]
+async fn test_config_reload() {
+    let dir = std::env::temp_dir().join(format!("lsys-config-{}", std::process::id()));
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    tokio::fs::write(dir.join("app.toml"), "log_level = \"info\"\n")
+        .await
+        .unwrap();
+    let config = Config::new(&dir, "app", None).await.unwrap();
+    config.subscribe(Arc::new(
+        ConfigSubscriberFn::new(Box::new(|_| Ok(()))).with_validate(Box::new(
+            |config| match config.find(None).get_string("log_level") {
+                Ok(level) if level == "bad" => Err("bad log level".to_string()),
+                _ => Ok(()),
+            },
+        )),
+    ));
+    tokio::fs::write(dir.join("app.toml"), "log_level = \"bad\"\n")
+        .await
+        .unwrap();
+    assert!(matches!(
+        config.reload().await,
+        Err(ConfigError::Validate(_))
+    ));
+    assert_eq!(config.find(None).get_string("log_level").unwrap(), "info");
+    tokio::fs::write(dir.join("app.toml"), "log_level = \"debug\"\n")
+        .await
+        .unwrap();
+    config.reload().await.unwrap();
+    assert_eq!(config.find(None).get_string("log_level").unwrap(), "debug");
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::info;
+
+use crate::{
+    AppCore, IntoFluentMessage, LocalExecType, MsgResultBody, MsgSendBody, RemoteNotify,
+    RemoteNotifyError, RemoteTask, ReplyWait,
+};
+
+pub const REMOTE_NOTIFY_TYPE_CONFIG: u8 = 105;
+
+/// 订阅远程通知重新加载本机配置
+pub struct ConfigRemoteReload {
+    app_core: Arc<AppCore>,
+}
+
+impl ConfigRemoteReload {
+    pub fn new(app_core: Arc<AppCore>) -> Self {
+        Self { app_core }
+    }
+}
+
+#[async_trait]
+impl RemoteTask for ConfigRemoteReload {
+    fn msg_type(&self) -> u8 {
+        REMOTE_NOTIFY_TYPE_CONFIG
+    }
+    async fn run(&self, msg: MsgSendBody) -> Result<Option<Value>, String> {
+        info!("config reload by host:{}", msg.from_host);
+        self.app_core
+            .config
+            .reload()
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?;
+        Ok(None)
+    }
+}
+
+/// 通知所有服务器重新加载配置
+/// 返回各服务器的执行结果,未在超时前返回的服务器不包含在结果中
+/// * `timeout` - 等待各服务器执行结果的超时,单位毫秒
+pub async fn config_cluster_reload(
+    remote_notify: &RemoteNotify,
+    timeout: u64,
+) -> Result<Vec<MsgResultBody>, RemoteNotifyError> {
+    remote_notify
+        .call(
+            REMOTE_NOTIFY_TYPE_CONFIG,
+            (),
+            None,
+            LocalExecType::RemoteExec,
+            Some(ReplyWait::new(timeout)),
+        )
+        .await
+}
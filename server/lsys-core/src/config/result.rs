@@ -5,6 +5,7 @@ use crate::{fluent_message, FluentMessage, IntoFluentMessage};
 pub enum ConfigError {
     Io(std::io::Error),
     Config(config::ConfigError),
+    Validate(String),
 }
 
 impl IntoFluentMessage for ConfigError {
@@ -12,6 +13,7 @@ impl IntoFluentMessage for ConfigError {
         match self {
             ConfigError::Io(err) => fluent_message!("file-error", err),
             ConfigError::Config(err) => fluent_message!("config-error", err),
+            ConfigError::Validate(err) => fluent_message!("config-validate-error", err),
         }
     }
 }
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+
+use super::ConfigData;
+
+// 配置重新加载的订阅
+#[async_trait]
+pub trait ConfigSubscriber: Send + Sync {
+    /// 校验新配置,返回错误时放弃此次重新加载
+    fn validate(&self, _config: &ConfigData) -> Result<(), String> {
+        Ok(())
+    }
+    /// 新配置已生效
+    async fn reload(&self, config: &ConfigData);
+}
+
+type ConfigReloadFn = Box<dyn Fn(&ConfigData) -> Result<(), String> + Send + Sync>;
+
+/// 通过函数实现的订阅
+/// 同步应用新配置的订阅,如日志等级
+pub struct ConfigSubscriberFn {
+    validate: Option<ConfigReloadFn>,
+    reload: ConfigReloadFn,
+}
+
+impl ConfigSubscriberFn {
+    /// * `reload` - 应用新配置,返回错误时仅记录日志
+    pub fn new(reload: ConfigReloadFn) -> Self {
+        Self {
+            validate: None,
+            reload,
+        }
+    }
+    pub fn with_validate(mut self, validate: ConfigReloadFn) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+}
+
+#[async_trait]
+impl ConfigSubscriber for ConfigSubscriberFn {
+    fn validate(&self, config: &ConfigData) -> Result<(), String> {
+        match &self.validate {
+            Some(validate) => validate(config),
+            None => Ok(()),
+        }
+    }
+    async fn reload(&self, config: &ConfigData) {
+        if let Err(err) = (self.reload)(config) {
+            tracing::warn!("config subscriber reload fail:{}", err);
+        }
+    }
+}
@@ -1,16 +1,45 @@
 use fluent::FluentResource;
 use intl_memoizer::concurrent::IntlLangMemoizer;
+use parking_lot::RwLock;
+use serde::Serialize;
 
 use crate::FluentMessage;
 
+use super::{ftl_message_keys, FluentOverrideItem, FluentOverrideLoader};
 use crate::FluentBundleError;
-use std::{collections::HashMap, path::Path, str::FromStr, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 use tokio::io::AsyncReadExt;
 use unic_langid::LanguageIdentifier;
+
+//消息KEY的翻译情况
+#[derive(Serialize, Clone, Debug)]
+pub struct FluentMessageKey {
+    pub crate_name: String,
+    pub key: String,
+    //已翻译的语言
+    pub langs: Vec<String>,
+    //未翻译的语言
+    pub missing: Vec<String>,
+}
+
+//语言 => 翻译文件名 => 消息KEY
+type FluentKeyData = BTreeMap<String, BTreeMap<String, BTreeSet<String>>>;
+
 pub struct FluentMgr {
+    path: PathBuf,
+    app_fluent: String,
+    crate_fluent: Option<Vec<String>>,
     default_bundle: Arc<FluentBundle>,
     default_lang: &'static str,
-    bundle_data: HashMap<String, Arc<FluentBundle>>,
+    bundle_data: RwLock<HashMap<String, Arc<FluentBundle>>>,
+    key_data: RwLock<FluentKeyData>,
+    override_loader: Option<Arc<dyn FluentOverrideLoader>>,
+    reload_lock: tokio::sync::Mutex<()>,
 }
 
 impl FluentMgr {
@@ -32,6 +61,7 @@ impl FluentMgr {
     fn init_create_bundle(
         lang: LanguageIdentifier,
         flt_data: &[&str],
+        override_data: &[&str],
     ) -> Result<fluent::bundle::FluentBundle<FluentResource, IntlLangMemoizer>, FluentBundleError>
     {
         let mut bundle = fluent::bundle::FluentBundle::new_concurrent(vec![lang.clone()]);
@@ -51,16 +81,25 @@ impl FluentMgr {
                 }
             }
         }
+        //覆盖的消息优先于文件中的消息
+        for flt_str in override_data {
+            match FluentResource::try_new(flt_str.to_string()) {
+                Ok(res) => bundle.add_resource_overriding(res),
+                Err((_, err)) => {
+                    tracing::warn!("fluent override res:{:?}", err);
+                }
+            }
+        }
         Ok(bundle)
     }
-
-    pub async fn new<P: AsRef<Path>>(
-        path: P,
-        app_fluent: &str,
-        crate_fluent: Option<&[&str]>,
-    ) -> Result<Self, FluentBundleError> {
+    async fn init_load(
+        &self,
+        overrides: &[FluentOverrideItem],
+    ) -> Result<(HashMap<String, Arc<FluentBundle>>, FluentKeyData), FluentBundleError> {
         let mut fluents: HashMap<String, Arc<FluentBundle>> = HashMap::new();
-        let path = path.as_ref();
+        let mut key_data = FluentKeyData::new();
+        let app_fluent = self.app_fluent.as_str();
+        let path = self.path.as_path();
         match tokio::fs::read_dir(path).await {
             Ok(mut dir) => {
                 while let Some(entry) = dir.next_entry().await? {
@@ -89,7 +128,7 @@ impl FluentMgr {
 
                     let mut fluent_item = vec![];
 
-                    match crate_fluent {
+                    match &self.crate_fluent {
                         Some(fluent_name) => {
                             for item in fluent_name {
                                 fluent_item.push((
@@ -122,19 +161,74 @@ impl FluentMgr {
                             }
                         }
                     }
+
+                    let lang_keys = key_data.entry(lang_str.clone()).or_default();
+                    let lang_override = overrides
+                        .iter()
+                        .filter(|e| e.lang == lang_str)
+                        .collect::<Vec<_>>();
+                    let pub_keys = ftl_message_keys(&pub_str);
+                    let pub_override = lang_override
+                        .iter()
+                        .filter(|e| e.crate_name == app_fluent)
+                        .collect::<Vec<_>>();
+                    lang_keys.insert(
+                        app_fluent.to_owned(),
+                        pub_keys
+                            .into_iter()
+                            .chain(pub_override.iter().map(|e| e.key.to_owned()))
+                            .collect(),
+                    );
+
+                    let mut ftl_data = vec![];
+                    for (file_path, file_name) in fluent_item {
+                        let file_path = file_path.as_path();
+                        let ftl_string = Self::init_read_file(file_path).await?;
+                        ftl_data.push((file_name, ftl_string));
+                    }
+                    //仅存在覆盖消息的翻译文件
+                    for item in lang_override.iter() {
+                        if item.crate_name != app_fluent
+                            && !ftl_data.iter().any(|e| e.0 == item.crate_name)
+                        {
+                            ftl_data.push((item.crate_name.to_owned(), "".to_string()));
+                        }
+                    }
+
+                    let pub_override_ftl =
+                        pub_override.iter().map(|e| e.to_ftl()).collect::<Vec<_>>();
                     let mut bundles = FluentBundle {
                         fluent_bundles: HashMap::new(),
                         default_bundle: Some(Self::init_create_bundle(
                             lang_den.clone(),
                             &[&pub_str],
+                            &pub_override_ftl
+                                .iter()
+                                .map(|e| e.as_str())
+                                .collect::<Vec<_>>(),
                         )?),
                     };
-                    for (file_path, file_name) in fluent_item {
-                        let file_path = file_path.as_path();
-                        let ftl_string = Self::init_read_file(file_path).await?;
-                        let bundle =
-                            Self::init_create_bundle(lang_den.clone(), &[&ftl_string, &pub_str])?;
-                        bundles.fluent_bundles.insert(file_name, bundle);
+                    for (file_name, ftl_string) in ftl_data {
+                        let mut keys = ftl_message_keys(&ftl_string)
+                            .into_iter()
+                            .collect::<BTreeSet<_>>();
+                        //通用覆盖消息不覆盖该文件中已存在的消息
+                        let mut override_ftl = pub_override
+                            .iter()
+                            .filter(|e| !keys.contains(&e.key))
+                            .map(|e| e.to_ftl())
+                            .collect::<Vec<_>>();
+                        for item in lang_override.iter().filter(|e| e.crate_name == file_name) {
+                            keys.insert(item.key.to_owned());
+                            override_ftl.push(item.to_ftl());
+                        }
+                        let bundle = Self::init_create_bundle(
+                            lang_den.clone(),
+                            &[&ftl_string, &pub_str],
+                            &override_ftl.iter().map(|e| e.as_str()).collect::<Vec<_>>(),
+                        )?;
+                        bundles.fluent_bundles.insert(file_name.clone(), bundle);
+                        lang_keys.insert(file_name, keys);
                     }
                     fluents.insert(lang_str, Arc::new(bundles));
                 }
@@ -143,19 +237,56 @@ impl FluentMgr {
                 tracing::error!("fluent dir:{:?} on {:?}", err, path);
             }
         }
-        Ok(FluentMgr {
-            bundle_data: fluents,
+        Ok((fluents, key_data))
+    }
+
+    pub async fn new<P: AsRef<Path>>(
+        path: P,
+        app_fluent: &str,
+        crate_fluent: Option<&[&str]>,
+    ) -> Result<Self, FluentBundleError> {
+        let mgr = FluentMgr {
+            path: path.as_ref().to_path_buf(),
+            app_fluent: app_fluent.to_owned(),
+            crate_fluent: crate_fluent.map(|e| e.iter().map(|t| t.to_string()).collect()),
+            bundle_data: RwLock::new(HashMap::new()),
+            key_data: RwLock::new(FluentKeyData::new()),
             default_lang: "en-US",
             default_bundle: Arc::new(FluentBundle {
                 fluent_bundles: HashMap::new(),
                 default_bundle: None,
             }),
-        })
+            override_loader: None,
+            reload_lock: tokio::sync::Mutex::new(()),
+        };
+        let (bundle_data, key_data) = mgr.init_load(&[]).await?;
+        *mgr.bundle_data.write() = bundle_data;
+        *mgr.key_data.write() = key_data;
+        Ok(mgr)
+    }
+    /// 设置覆盖翻译消息的加载器,调用 reload 后生效
+    pub fn with_override_loader(mut self, loader: Arc<dyn FluentOverrideLoader>) -> Self {
+        self.override_loader = Some(loader);
+        self
+    }
+    /// 重新加载翻译文件及覆盖的翻译消息
+    /// 加载失败时保留原翻译数据
+    pub async fn reload(&self) -> Result<(), FluentBundleError> {
+        let _lock = self.reload_lock.lock().await;
+        let overrides = match &self.override_loader {
+            Some(loader) => loader.load().await.map_err(FluentBundleError::System)?,
+            None => vec![],
+        };
+        let (bundle_data, key_data) = self.init_load(&overrides).await?;
+        *self.bundle_data.write() = bundle_data;
+        *self.key_data.write() = key_data;
+        Ok(())
     }
     pub fn locale(&self, lang: Option<&str>) -> Arc<FluentBundle> {
+        let bundle_data = self.bundle_data.read();
         match lang {
-            Some(lang) => self.bundle_data.get(lang).unwrap_or_else(|| {
-                self.bundle_data
+            Some(lang) => bundle_data.get(lang).unwrap_or_else(|| {
+                bundle_data
                     .get(self.default_lang)
                     .unwrap_or(&self.default_bundle)
             }),
@@ -163,6 +294,42 @@ impl FluentMgr {
         }
         .to_owned()
     }
+    /// 已加载的语言
+    pub fn langs(&self) -> Vec<String> {
+        self.key_data.read().keys().map(|e| e.to_owned()).collect()
+    }
+    /// 所有消息KEY及各语言的翻译情况
+    /// * `crate_name` - 翻译文件名,为None时返回全部
+    pub fn message_keys(&self, crate_name: Option<&str>) -> Vec<FluentMessageKey> {
+        let key_data = self.key_data.read();
+        let mut all_keys = BTreeMap::<(&str, &str), Vec<String>>::new();
+        for (lang, crate_keys) in key_data.iter() {
+            for (tmp_crate, keys) in crate_keys.iter() {
+                if crate_name.map(|e| e != tmp_crate).unwrap_or(false) {
+                    continue;
+                }
+                for key in keys {
+                    all_keys
+                        .entry((tmp_crate.as_str(), key.as_str()))
+                        .or_default()
+                        .push(lang.to_owned());
+                }
+            }
+        }
+        all_keys
+            .into_iter()
+            .map(|((crate_name, key), langs)| FluentMessageKey {
+                crate_name: crate_name.to_owned(),
+                key: key.to_owned(),
+                missing: key_data
+                    .keys()
+                    .filter(|e| !langs.contains(e))
+                    .map(|e| e.to_owned())
+                    .collect(),
+                langs,
+            })
+            .collect()
+    }
 }
 
 pub struct FluentBundle {
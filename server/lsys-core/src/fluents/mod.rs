@@ -1,7 +1,11 @@
 mod bundle;
 mod message;
+mod notify;
+mod overlay;
 
 pub use bundle::*;
 pub use message::*;
+pub use notify::*;
+pub use overlay::*;
 mod result;
 pub use result::*;
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::info;
+
+use crate::{
+    IntoFluentMessage, LocalExecType, MsgResultBody, MsgSendBody, RemoteNotify, RemoteNotifyError,
+    RemoteTask, ReplyWait,
+};
+
+use super::FluentMgr;
+
+pub const REMOTE_NOTIFY_TYPE_FLUENT: u8 = 106;
+
+/// 订阅远程通知重新加载本机翻译
+pub struct FluentRemoteReload {
+    fluent: Arc<FluentMgr>,
+}
+
+impl FluentRemoteReload {
+    pub fn new(fluent: Arc<FluentMgr>) -> Self {
+        Self { fluent }
+    }
+}
+
+#[async_trait]
+impl RemoteTask for FluentRemoteReload {
+    fn msg_type(&self) -> u8 {
+        REMOTE_NOTIFY_TYPE_FLUENT
+    }
+    async fn run(&self, msg: MsgSendBody) -> Result<Option<Value>, String> {
+        info!("fluent reload by host:{}", msg.from_host);
+        self.fluent
+            .reload()
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?;
+        Ok(None)
+    }
+}
+
+/// 通知所有服务器重新加载翻译
+/// 返回各服务器的执行结果,未在超时前返回的服务器不包含在结果中
+/// * `timeout` - 等待各服务器执行结果的超时,单位毫秒
+pub async fn fluent_cluster_reload(
+    remote_notify: &RemoteNotify,
+    timeout: u64,
+) -> Result<Vec<MsgResultBody>, RemoteNotifyError> {
+    remote_notify
+        .call(
+            REMOTE_NOTIFY_TYPE_FLUENT,
+            (),
+            None,
+            LocalExecType::RemoteExec,
+            Some(ReplyWait::new(timeout)),
+        )
+        .await
+}
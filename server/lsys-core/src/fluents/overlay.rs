@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::FluentBundleError;
+
+//覆盖文件中的翻译消息,如后台编辑的翻译
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FluentOverrideItem {
+    pub lang: String,
+    //为通用翻译文件名时覆盖所有翻译文件中的该消息
+    pub crate_name: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl FluentOverrideItem {
+    //转为FTL格式内容,多行消息按FTL格式缩进
+    pub(crate) fn to_ftl(&self) -> String {
+        let value = self
+            .value
+            .lines()
+            .filter(|e| !e.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n    ");
+        format!("{} = {}\n", self.key, value)
+    }
+    /// 检查消息KEY及内容是否为合法的FTL消息
+    pub fn check(&self) -> Result<(), FluentBundleError> {
+        let mut chars = self.key.chars();
+        let key_ok = chars
+            .next()
+            .map(|e| e.is_ascii_alphabetic())
+            .unwrap_or(false)
+            && chars.all(|e| e.is_ascii_alphanumeric() || e == '-' || e == '_');
+        if !key_ok {
+            return Err(FluentBundleError::System(format!(
+                "message key is invalid:{}",
+                self.key
+            )));
+        }
+        if self.value.trim().is_empty() {
+            return Err(FluentBundleError::System(format!(
+                "message value is empty:{}",
+                self.key
+            )));
+        }
+        //内容中不能包含其他消息
+        let ftl = self.to_ftl();
+        if ftl_message_keys(&ftl) != vec![self.key.to_owned()] {
+            return Err(FluentBundleError::System(format!(
+                "message value is invalid:{}",
+                self.key
+            )));
+        }
+        fluent::FluentResource::try_new(ftl).map_err(|(_, err)| {
+            FluentBundleError::System(
+                err.into_iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<String>>()
+                    .join(";"),
+            )
+        })?;
+        Ok(())
+    }
+}
+
+/// 加载覆盖的翻译消息
+#[async_trait]
+pub trait FluentOverrideLoader: Sync + Send {
+    async fn load(&self) -> Result<Vec<FluentOverrideItem>, String>;
+}
+
+//FTL内容中的消息KEY,不包含以-开头的术语
+pub(crate) fn ftl_message_keys(ftl: &str) -> Vec<String> {
+    ftl.lines()
+        .filter(|e| e.starts_with(|c: char| c.is_ascii_alphabetic()))
+        .filter_map(|e| e.split_once('='))
+        .map(|(key, _)| key.trim())
+        .filter(|key| {
+            key.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+        .map(|e| e.to_owned())
+        .collect()
+}
+
+#[test]
+fn test_fluent_override() {
+    let ftl =
+        "# comment\n-term = t\napp-error = error {$msg}\nmulti =\n    line a\n    line b = x\n";
+    assert_eq!(ftl_message_keys(ftl), vec!["app-error", "multi"]);
+    let item = FluentOverrideItem {
+        lang: "zh_CN".to_string(),
+        crate_name: "app".to_string(),
+        key: "app-error".to_string(),
+        value: "line a\nline b".to_string(),
+    };
+    assert_eq!(item.to_ftl(), "app-error = line a\n    line b\n");
+    assert!(item.check().is_ok());
+    let bad = FluentOverrideItem {
+        key: "1bad".to_string(),
+        ..item.clone()
+    };
+    assert!(bad.check().is_err());
+    let bad = FluentOverrideItem {
+        value: "{ $msg".to_string(),
+        ..item
+    };
+    assert!(bad.check().is_err());
+}
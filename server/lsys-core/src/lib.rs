@@ -2,9 +2,11 @@ mod app_core;
 pub mod cache;
 mod config;
 mod fluents;
+mod limit;
 
 mod listen_notify;
 mod macros;
+mod migrate;
 mod remote_notify;
 mod task;
 mod utils;
@@ -13,8 +15,10 @@ mod valid_code;
 pub use app_core::*;
 pub use config::*;
 pub use fluents::*;
+pub use limit::*;
 
 pub use listen_notify::*;
+pub use migrate::*;
 pub use remote_notify::*;
 pub use task::*;
 pub use utils::*;
@@ -0,0 +1,359 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use redis::{AsyncCommands, Script};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use super::{LimitError, LimitResult};
+use crate::{rand_str, RandType};
+
+//锁不存在时加锁并返回递增的防护令牌,已存在返回0
+const LOCK_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[1]) == 1 then
+    return 0
+end
+local fence = redis.call('INCR', KEYS[2])
+redis.call('SET', KEYS[1], ARGV[1] .. ':' .. fence, 'PX', ARGV[2])
+return fence
+"#;
+
+//仍为当前持有者时延长租期
+const RENEW_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+//仍为当前持有者时删除锁
+const UNLOCK_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+//等待锁时的重试间隔
+const LOCK_RETRY_TIME: u64 = 50;
+
+// 基于REDIS的分布式锁
+// 持有期间在后台按租期的1/3自动续期,续期失败时标记为已丢失
+// 本地按 最后成功续期时间+租期-安全余量 计算截止时间,超过后即视为已丢失,不等待REDIS中的锁过期
+// 每次加锁返回递增的防护令牌,写入外部资源时携带该令牌,资源方拒绝比已见令牌小的写入,
+// 以防止锁过期后旧持有者的延迟写入覆盖新持有者的数据
+#[derive(Clone)]
+pub struct RedisLock {
+    redis: deadpool_redis::Pool,
+    prefix: String,
+}
+
+impl RedisLock {
+    /// * `prefix` - REDIS KEY 前缀
+    pub fn new(redis: deadpool_redis::Pool, prefix: &str) -> Self {
+        Self {
+            redis,
+            prefix: prefix.to_owned(),
+        }
+    }
+    fn lock_key(&self, key: &str) -> String {
+        format!("{}lock-{}", self.prefix, key)
+    }
+    fn fence_key(&self, key: &str) -> String {
+        format!("{}lock-fence-{}", self.prefix, key)
+    }
+    /// 尝试加锁,锁已被持有时返回None
+    /// * `key` - 锁名
+    /// * `lease` - 租期,持有者异常退出时锁在租期后自动释放
+    pub async fn try_lock(
+        &self,
+        key: &str,
+        lease: Duration,
+    ) -> LimitResult<Option<RedisLockGuard>> {
+        let lease = lease.max(Duration::from_millis(LOCK_RETRY_TIME));
+        let owner = rand_str(RandType::UpperHex, 32);
+        let lock_key = self.lock_key(key);
+        let mut redis = self.redis.get().await?;
+        let lock_start = Instant::now();
+        let fence: u64 = Script::new(LOCK_SCRIPT)
+            .key(&lock_key)
+            .key(self.fence_key(key))
+            .arg(&owner)
+            .arg(lease.as_millis() as u64)
+            .invoke_async(&mut redis)
+            .await?;
+        if fence == 0 {
+            return Ok(None);
+        }
+        let value = format!("{}:{}", owner, fence);
+        let state = Arc::new(LockState::new(lock_start, lease));
+        let renew_handle = tokio::spawn(lock_renew(
+            self.redis.clone(),
+            lock_key.clone(),
+            value.clone(),
+            lease,
+            state.clone(),
+        ));
+        Ok(Some(RedisLockGuard {
+            redis: self.redis.clone(),
+            name: key.to_owned(),
+            lock_key,
+            value,
+            fence,
+            lease,
+            state,
+            renew_handle: Some(renew_handle),
+        }))
+    }
+    /// 加锁,锁已被持有时等待
+    /// * `key` - 锁名
+    /// * `lease` - 租期
+    /// * `wait` - 最长等待时间,超过返回 LimitError::LockTimeout
+    pub async fn lock(
+        &self,
+        key: &str,
+        lease: Duration,
+        wait: Duration,
+    ) -> LimitResult<RedisLockGuard> {
+        let start = Instant::now();
+        loop {
+            if let Some(guard) = self.try_lock(key, lease).await? {
+                return Ok(guard);
+            }
+            if start.elapsed() >= wait {
+                return Err(LimitError::LockTimeout(key.to_owned()));
+            }
+            tokio::time::sleep(Duration::from_millis(LOCK_RETRY_TIME)).await;
+        }
+    }
+    /// 指定锁最后发放的防护令牌,未加锁过返回0
+    pub async fn fence(&self, key: &str) -> LimitResult<u64> {
+        let mut redis = self.redis.get().await?;
+        let fence: Option<u64> = redis.get(self.fence_key(key)).await?;
+        Ok(fence.unwrap_or(0))
+    }
+}
+
+//持有者与续期任务共享的锁状态
+struct LockState {
+    //本地计算的租约截止时间
+    deadline: parking_lot::Mutex<Instant>,
+    lost: AtomicBool,
+}
+
+impl LockState {
+    fn new(start: Instant, lease: Duration) -> Self {
+        Self {
+            deadline: parking_lot::Mutex::new(Self::lease_deadline(start, lease)),
+            lost: AtomicBool::new(false),
+        }
+    }
+    //以发起请求的时间计算截止时间,并预留租期的1/5作为时钟误差及网络延迟的余量
+    fn lease_deadline(start: Instant, lease: Duration) -> Instant {
+        start + lease - lease / 5
+    }
+    fn renewed(&self, start: Instant, lease: Duration) {
+        *self.deadline.lock() = Self::lease_deadline(start, lease);
+    }
+    //距离截止时间的剩余时间
+    fn remaining(&self) -> Duration {
+        self.deadline
+            .lock()
+            .saturating_duration_since(Instant::now())
+    }
+    fn set_lost(&self) {
+        self.lost.store(true, Ordering::Release);
+    }
+    fn is_lost(&self) -> bool {
+        if self.lost.load(Ordering::Acquire) {
+            return true;
+        }
+        if self.remaining().is_zero() {
+            self.set_lost();
+            return true;
+        }
+        false
+    }
+}
+
+async fn lock_renew_once(
+    redis: &deadpool_redis::Pool,
+    lock_key: &str,
+    value: &str,
+    lease: Duration,
+) -> LimitResult<bool> {
+    let mut redis = redis.get().await?;
+    let renew: u64 = Script::new(RENEW_SCRIPT)
+        .key(lock_key)
+        .arg(value)
+        .arg(lease.as_millis() as u64)
+        .invoke_async(&mut redis)
+        .await?;
+    Ok(renew == 1)
+}
+
+async fn lock_renew(
+    redis: deadpool_redis::Pool,
+    lock_key: String,
+    value: String,
+    lease: Duration,
+    state: Arc<LockState>,
+) {
+    loop {
+        //续期失败时在截止时间到达时唤醒并标记丢失
+        tokio::time::sleep((lease / 3).min(state.remaining())).await;
+        if state.is_lost() {
+            warn!("lock {} is lost on lease deadline", lock_key);
+            return;
+        }
+        let start = Instant::now();
+        match lock_renew_once(&redis, &lock_key, &value, lease).await {
+            Ok(true) => state.renewed(start, lease),
+            Ok(false) => {
+                warn!("lock {} is lost", lock_key);
+                state.set_lost();
+                return;
+            }
+            Err(err) => {
+                warn!("lock {} renew fail:{:?}", lock_key, err);
+            }
+        }
+    }
+}
+
+/// 已获取的锁,释放时调用 unlock,未调用时在 drop 后异步释放
+pub struct RedisLockGuard {
+    redis: deadpool_redis::Pool,
+    name: String,
+    lock_key: String,
+    value: String,
+    fence: u64,
+    lease: Duration,
+    state: Arc<LockState>,
+    renew_handle: Option<JoinHandle<()>>,
+}
+
+impl RedisLockGuard {
+    /// 锁名
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// 本次加锁的防护令牌
+    pub fn fence(&self) -> u64 {
+        self.fence
+    }
+    /// 锁是否已丢失,超过本地租约截止时间也视为已丢失
+    pub fn is_lost(&self) -> bool {
+        self.state.is_lost()
+    }
+    /// 锁已丢失时返回 LimitError::LockLost,在执行受保护操作前调用
+    pub fn check(&self) -> LimitResult<()> {
+        if self.is_lost() {
+            return Err(LimitError::LockLost(self.name.clone()));
+        }
+        Ok(())
+    }
+    /// 立即续期,锁已丢失时返回 LimitError::LockLost
+    pub async fn renew(&self) -> LimitResult<()> {
+        self.check()?;
+        let start = Instant::now();
+        if !lock_renew_once(&self.redis, &self.lock_key, &self.value, self.lease).await? {
+            self.state.set_lost();
+            return Err(LimitError::LockLost(self.name.clone()));
+        }
+        self.state.renewed(start, self.lease);
+        Ok(())
+    }
+    /// 释放锁,返回释放时是否仍持有该锁
+    pub async fn unlock(mut self) -> LimitResult<bool> {
+        if let Some(handle) = self.renew_handle.take() {
+            handle.abort();
+        }
+        let res = lock_release(&self.redis, &self.lock_key, &self.value).await;
+        self.state.set_lost();
+        res
+    }
+}
+
+async fn lock_release(
+    redis: &deadpool_redis::Pool,
+    lock_key: &str,
+    value: &str,
+) -> LimitResult<bool> {
+    let mut redis = redis.get().await?;
+    let del: u64 = Script::new(UNLOCK_SCRIPT)
+        .key(lock_key)
+        .arg(value)
+        .invoke_async(&mut redis)
+        .await?;
+    Ok(del == 1)
+}
+
+impl Drop for RedisLockGuard {
+    fn drop(&mut self) {
+        let handle = match self.renew_handle.take() {
+            Some(handle) => handle,
+            None => return,
+        };
+        handle.abort();
+        if self.is_lost() {
+            return;
+        }
+        if let Ok(rt) = tokio::runtime::Handle::try_current() {
+            let redis = self.redis.clone();
+            let lock_key = self.lock_key.clone();
+            let value = self.value.clone();
+            rt.spawn(async move {
+                if let Err(err) = lock_release(&redis, &lock_key, &value).await {
+                    warn!("lock {} release fail:{:?}", lock_key, err);
+                }
+            });
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_redis_lock() {
+    let app_core = crate::AppCore::init(
+        &format!("{}/../examples/lsys-actix-web", env!("CARGO_MANIFEST_DIR")),
+        &format!(
+            "{}/../examples/lsys-actix-web/config",
+            env!("CARGO_MANIFEST_DIR")
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+    let lock = RedisLock::new(
+        app_core.create_redis().await.unwrap(),
+        &format!("test-{}-", rand_str(RandType::LowerHex, 8)),
+    );
+    let lease = Duration::from_millis(500);
+    //持有期间其他请求无法加锁
+    let mut guard1 = lock.try_lock("a", lease).await.unwrap().unwrap();
+    assert!(lock.try_lock("a", lease).await.unwrap().is_none());
+    assert_eq!(lock.fence("a").await.unwrap(), guard1.fence());
+    //后台续期时超过租期仍持有
+    tokio::time::sleep(lease * 2).await;
+    guard1.check().unwrap();
+    assert!(lock.try_lock("a", lease).await.unwrap().is_none());
+    //停止续期后租期结束即丢失,其他请求可加锁并得到更大的防护令牌
+    guard1.renew_handle.take().unwrap().abort();
+    tokio::time::sleep(lease + lease / 2).await;
+    assert!(guard1.is_lost());
+    assert!(matches!(guard1.check(), Err(LimitError::LockLost(_))));
+    let guard2 = lock.try_lock("a", lease).await.unwrap().unwrap();
+    assert!(guard2.fence() > guard1.fence());
+    //非持有者释放失败,不影响当前持有者
+    assert!(!guard1.unlock().await.unwrap());
+    assert!(lock.try_lock("a", lease).await.unwrap().is_none());
+    guard2.check().unwrap();
+    assert!(guard2.unlock().await.unwrap());
+    let guard3 = lock.try_lock("a", lease).await.unwrap().unwrap();
+    assert!(guard3.unlock().await.unwrap());
+}
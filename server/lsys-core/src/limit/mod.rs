@@ -0,0 +1,7 @@
+mod lock;
+mod rate;
+mod result;
+
+pub use lock::*;
+pub use rate::*;
+pub use result::*;
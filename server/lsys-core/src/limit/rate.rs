@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use redis::{AsyncCommands, Script};
+use serde::Serialize;
+
+use super::LimitResult;
+use crate::{rand_str, RandType};
+
+//滑动窗口: 有序集合记录窗口内每次请求的时间,单位毫秒,时间以REDIS服务器为准
+//返回 {是否允许,剩余次数,需等待毫秒数}
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local t = redis.call('TIME')
+local now = tonumber(t[1]) * 1000 + math.floor(tonumber(t[2]) / 1000)
+local window = tonumber(ARGV[1])
+local limit = tonumber(ARGV[2])
+local cost = tonumber(ARGV[3])
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+local count = redis.call('ZCARD', KEYS[1])
+if count + cost <= limit then
+    for i = 1, cost do
+        redis.call('ZADD', KEYS[1], now, t[1] .. t[2] .. '-' .. ARGV[4] .. '-' .. i)
+    end
+    redis.call('PEXPIRE', KEYS[1], window)
+    return {1, limit - count - cost, 0}
+end
+if cost > limit then
+    return {0, limit - count, 0}
+end
+local first = redis.call('ZRANGE', KEYS[1], count + cost - limit - 1, count + cost - limit - 1, 'WITHSCORES')
+local retry = 0
+if first[2] then
+    retry = tonumber(first[2]) + window - now
+end
+return {0, limit - count, retry}
+"#;
+
+//令牌桶: 哈希记录剩余令牌及最后更新时间,单位毫秒,时间以REDIS服务器为准
+//返回 {是否允许,剩余令牌,需等待毫秒数}
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local t = redis.call('TIME')
+local now = tonumber(t[1]) * 1000 + math.floor(tonumber(t[2]) / 1000)
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2]) / tonumber(ARGV[3])
+local cost = tonumber(ARGV[4])
+local data = redis.call('HMGET', KEYS[1], 'tokens', 'time')
+local tokens = tonumber(data[1])
+local last = tonumber(data[2])
+if tokens == nil or last == nil then
+    tokens = capacity
+    last = now
+end
+if now > last then
+    tokens = math.min(capacity, tokens + (now - last) * rate)
+end
+local allowed = 0
+local retry = 0
+if tokens >= cost then
+    tokens = tokens - cost
+    allowed = 1
+elseif cost <= capacity then
+    retry = math.ceil((cost - tokens) / rate)
+end
+redis.call('HMSET', KEYS[1], 'tokens', tokens, 'time', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity / rate) + 1000)
+return {allowed, math.floor(tokens), retry}
+"#;
+
+/// 限流检测结果
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// 是否允许本次请求
+    pub allowed: bool,
+    /// 剩余可用次数
+    pub remaining: u64,
+    /// 不允许时需等待的时间,单位毫秒,为0表示无法通过等待满足
+    pub retry_after: u64,
+}
+
+impl RateLimitStatus {
+    fn from_script(data: (i64, i64, i64)) -> Self {
+        Self {
+            allowed: data.0 == 1,
+            remaining: data.1.max(0) as u64,
+            retry_after: data.2.max(0) as u64,
+        }
+    }
+}
+
+// 滑动窗口限流
+// 任意时间窗口内的请求次数不超过限制,适用于按账号或IP限制发送次数等场景
+#[derive(Clone)]
+pub struct RateLimitSlidingWindow {
+    redis: deadpool_redis::Pool,
+    prefix: String,
+    limit: u64,
+    window: Duration,
+}
+
+impl RateLimitSlidingWindow {
+    /// * `prefix` - REDIS KEY 前缀
+    /// * `limit` - 窗口内最大请求次数
+    /// * `window` - 窗口时间,最小1毫秒
+    pub fn new(redis: deadpool_redis::Pool, prefix: &str, limit: u64, window: Duration) -> Self {
+        Self {
+            redis,
+            prefix: prefix.to_owned(),
+            limit,
+            window: window.max(Duration::from_millis(1)),
+        }
+    }
+    fn limit_key(&self, key: &str) -> String {
+        format!("{}rate-window-{}", self.prefix, key)
+    }
+    /// 检测并记录一次请求
+    /// * `key` - 限流KEY,如 用户ID 或 IP
+    pub async fn check(&self, key: &str) -> LimitResult<RateLimitStatus> {
+        self.check_cost(key, 1).await
+    }
+    /// 检测并记录多次请求,不允许时不记录
+    /// * `key` - 限流KEY
+    /// * `cost` - 本次请求计为的次数
+    pub async fn check_cost(&self, key: &str, cost: u64) -> LimitResult<RateLimitStatus> {
+        let mut redis = self.redis.get().await?;
+        let data: (i64, i64, i64) = Script::new(SLIDING_WINDOW_SCRIPT)
+            .key(self.limit_key(key))
+            .arg(self.window.as_millis() as u64)
+            .arg(self.limit)
+            .arg(cost)
+            .arg(rand_str(RandType::LowerHex, 8))
+            .invoke_async(&mut redis)
+            .await?;
+        Ok(RateLimitStatus::from_script(data))
+    }
+    /// 清除限流记录
+    pub async fn reset(&self, key: &str) -> LimitResult<()> {
+        let mut redis = self.redis.get().await?;
+        redis.del::<_, ()>(self.limit_key(key)).await?;
+        Ok(())
+    }
+}
+
+// 令牌桶限流
+// 令牌按固定速率补充,允许不超过桶容量的突发请求
+#[derive(Clone)]
+pub struct RateLimitTokenBucket {
+    redis: deadpool_redis::Pool,
+    prefix: String,
+    capacity: u64,
+    refill: u64,
+    period: Duration,
+}
+
+impl RateLimitTokenBucket {
+    /// * `prefix` - REDIS KEY 前缀
+    /// * `capacity` - 桶容量,即最大突发请求数
+    /// * `refill` - 每个周期补充的令牌数,最小为1
+    /// * `period` - 补充周期,最小1毫秒
+    pub fn new(
+        redis: deadpool_redis::Pool,
+        prefix: &str,
+        capacity: u64,
+        refill: u64,
+        period: Duration,
+    ) -> Self {
+        Self {
+            redis,
+            prefix: prefix.to_owned(),
+            capacity,
+            refill: refill.max(1),
+            period: period.max(Duration::from_millis(1)),
+        }
+    }
+    fn limit_key(&self, key: &str) -> String {
+        format!("{}rate-bucket-{}", self.prefix, key)
+    }
+    /// 获取一个令牌
+    /// * `key` - 限流KEY,如 用户ID 或 IP
+    pub async fn check(&self, key: &str) -> LimitResult<RateLimitStatus> {
+        self.check_cost(key, 1).await
+    }
+    /// 获取多个令牌,令牌不足时不扣除
+    /// * `key` - 限流KEY
+    /// * `cost` - 需要的令牌数
+    pub async fn check_cost(&self, key: &str, cost: u64) -> LimitResult<RateLimitStatus> {
+        let mut redis = self.redis.get().await?;
+        let data: (i64, i64, i64) = Script::new(TOKEN_BUCKET_SCRIPT)
+            .key(self.limit_key(key))
+            .arg(self.capacity)
+            .arg(self.refill)
+            .arg(self.period.as_millis() as u64)
+            .arg(cost)
+            .invoke_async(&mut redis)
+            .await?;
+        Ok(RateLimitStatus::from_script(data))
+    }
+    /// 清除限流记录,桶恢复为满
+    pub async fn reset(&self, key: &str) -> LimitResult<()> {
+        let mut redis = self.redis.get().await?;
+        redis.del::<_, ()>(self.limit_key(key)).await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limit() {
+    let app_core = crate::AppCore::init(
+        &format!("{}/../examples/lsys-actix-web", env!("CARGO_MANIFEST_DIR")),
+        &format!(
+            "{}/../examples/lsys-actix-web/config",
+            env!("CARGO_MANIFEST_DIR")
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+    let redis = app_core.create_redis().await.unwrap();
+    let prefix = format!("test-{}-", rand_str(RandType::LowerHex, 8));
+    //滑动窗口: 窗口内超过次数后拒绝,并返回需等待时间
+    let window = RateLimitSlidingWindow::new(redis.clone(), &prefix, 2, Duration::from_secs(2));
+    assert_eq!(window.check("a").await.unwrap().remaining, 1);
+    assert_eq!(window.check("a").await.unwrap().remaining, 0);
+    let status = window.check("a").await.unwrap();
+    assert!(!status.allowed);
+    assert!(status.retry_after > 0 && status.retry_after <= 2000);
+    assert!(window.check("b").await.unwrap().allowed);
+    tokio::time::sleep(Duration::from_millis(status.retry_after + 50)).await;
+    assert!(window.check("a").await.unwrap().allowed);
+    window.reset("a").await.unwrap();
+    window.reset("b").await.unwrap();
+    //令牌桶: 令牌用完后拒绝,按速率补充后可再次获取
+    let bucket =
+        RateLimitTokenBucket::new(redis.clone(), &prefix, 2, 1, Duration::from_millis(500));
+    assert!(bucket.check("a").await.unwrap().allowed);
+    assert!(bucket.check("a").await.unwrap().allowed);
+    let status = bucket.check("a").await.unwrap();
+    assert!(!status.allowed);
+    assert!(status.retry_after > 0 && status.retry_after <= 500);
+    assert_eq!(bucket.check_cost("a", 3).await.unwrap().retry_after, 0);
+    tokio::time::sleep(Duration::from_millis(status.retry_after + 50)).await;
+    assert!(bucket.check("a").await.unwrap().allowed);
+    assert!(!bucket.check("a").await.unwrap().allowed);
+    bucket.reset("a").await.unwrap();
+}
@@ -0,0 +1,38 @@
+use deadpool_redis::PoolError;
+use redis::RedisError;
+
+use crate::{fluent_message, FluentMessage, IntoFluentMessage};
+
+#[derive(Debug)]
+pub enum LimitError {
+    Redis(RedisError),
+    RedisPool(PoolError),
+    //锁已过期或被其他持有者获取
+    LockLost(String),
+    //等待获取锁超时
+    LockTimeout(String),
+}
+
+impl IntoFluentMessage for LimitError {
+    fn to_fluent_message(&self) -> FluentMessage {
+        match self {
+            LimitError::Redis(err) => fluent_message!("redis-error", err),
+            LimitError::RedisPool(err) => fluent_message!("redis-error", err),
+            LimitError::LockLost(key) => fluent_message!("lock-lost", key),
+            LimitError::LockTimeout(key) => fluent_message!("lock-timeout", key),
+        }
+    }
+}
+
+impl From<RedisError> for LimitError {
+    fn from(err: RedisError) -> Self {
+        LimitError::Redis(err)
+    }
+}
+impl From<PoolError> for LimitError {
+    fn from(err: PoolError) -> Self {
+        LimitError::RedisPool(err)
+    }
+}
+
+pub type LimitResult<T> = Result<T, LimitError>;
@@ -202,6 +202,13 @@ impl<T: WaitItem + Serialize + DeserializeOwned + Debug> WaitNotify<T> {
     }
 }
 
+#[cfg(test)]
+impl crate::WaitItem for u64 {
+    fn eq(&self, other: &Self) -> bool {
+        *self == *other
+    }
+}
+
 #[tokio::test]
 async fn test_listen_notify() {
     let app_core = AppCore::init(
@@ -214,11 +221,6 @@ async fn test_listen_notify() {
     )
     .await
     .unwrap();
-    impl crate::WaitItem for u64 {
-        fn eq(&self, other: &Self) -> bool {
-            *self == *other
-        }
-    }
     let notify = std::sync::Arc::new(WaitNotify::<u64>::new(
         "sms",
         app_core.create_redis().await.unwrap(),
@@ -0,0 +1,328 @@
+mod result;
+mod split;
+
+pub use result::*;
+use split::split_sql;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{MySql, Pool, Row};
+
+use crate::now_time;
+
+//建表SQL中使用的表前缀,执行时替换为配置的表前缀
+const MIGRATE_SQL_PREFIX: &str = "yaf_";
+
+// 数据库迁移
+// 各模块的迁移按版本顺序执行,执行后记录校验值,已执行的迁移不能再修改
+#[derive(Clone, Debug)]
+pub struct MigrateItem {
+    pub crate_name: &'static str,
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+impl MigrateItem {
+    /// * `crate_name` - 模块名,一般为 env!("CARGO_PKG_NAME")
+    /// * `version` - 版本号,同模块内递增
+    /// * `sql` - 迁移SQL,一般为 include_str!("../migrations/xxx.sql")
+    pub fn new(
+        crate_name: &'static str,
+        version: u32,
+        name: &'static str,
+        sql: &'static str,
+    ) -> Self {
+        Self {
+            crate_name,
+            version,
+            name,
+            sql,
+        }
+    }
+    //忽略换行符差异
+    pub fn checksum(&self) -> String {
+        let sql = self.sql.replace("\r\n", "\n");
+        Sha256::digest(sql.trim().as_bytes())
+            .iter()
+            .map(|e| format!("{:02x}", e))
+            .collect()
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrateState {
+    //已执行
+    Applied,
+    //未执行
+    Pending,
+    //已执行但内容已修改
+    Changed,
+    //已执行但代码中不存在
+    Missing,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MigrateStatus {
+    pub crate_name: String,
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+    pub state: MigrateState,
+    pub apply_time: Option<u64>,
+}
+
+struct MigrateRecord {
+    crate_name: String,
+    version: u32,
+    name: String,
+    checksum: String,
+    apply_time: u64,
+}
+
+pub struct Migrate {
+    db: Pool<MySql>,
+    table_prefix: String,
+    items: Vec<MigrateItem>,
+}
+
+impl Migrate {
+    /// * `table_prefix` - 表前缀,即 database_table_prefix 配置
+    /// * `items` - 迁移列表,按传入的模块顺序及模块内版本顺序执行
+    pub fn new(db: Pool<MySql>, table_prefix: &str, items: Vec<MigrateItem>) -> Self {
+        let mut crate_list: Vec<&str> = vec![];
+        for item in items.iter() {
+            if !crate_list.contains(&item.crate_name) {
+                crate_list.push(item.crate_name);
+            }
+        }
+        let mut items = items;
+        items.sort_by_key(|e| {
+            (
+                crate_list.iter().position(|t| *t == e.crate_name),
+                e.version,
+            )
+        });
+        Self {
+            db,
+            table_prefix: table_prefix.to_owned(),
+            items,
+        }
+    }
+    fn table_name(&self) -> String {
+        format!("{}migrations", self.table_prefix)
+    }
+    async fn init_table(&self) -> MigrateResult<()> {
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS `{}` (
+                `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+                `crate_name` varchar(64) NOT NULL COMMENT '模块名',
+                `version` int unsigned NOT NULL COMMENT '版本号',
+                `name` varchar(64) NOT NULL COMMENT '迁移名',
+                `checksum` varchar(64) NOT NULL COMMENT '迁移SQL校验值',
+                `apply_time` bigint unsigned NOT NULL COMMENT '执行时间',
+                PRIMARY KEY (`id`),
+                UNIQUE KEY `crate_version_IDX` (`crate_name`, `version`) USING BTREE
+            ) ENGINE = InnoDB CHARSET = utf8mb4 COMMENT = '数据库迁移记录'",
+            self.table_name()
+        );
+        sqlx::query(&sql).execute(&self.db).await?;
+        Ok(())
+    }
+    async fn records(&self) -> MigrateResult<Vec<MigrateRecord>> {
+        self.init_table().await?;
+        let sql = format!(
+            "select crate_name,version,name,checksum,apply_time from `{}` order by id asc",
+            self.table_name()
+        );
+        let rows = sqlx::query(&sql).fetch_all(&self.db).await?;
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            out.push(MigrateRecord {
+                crate_name: row.try_get("crate_name")?,
+                version: row.try_get("version")?,
+                name: row.try_get("name")?,
+                checksum: row.try_get("checksum")?,
+                apply_time: row.try_get("apply_time")?,
+            });
+        }
+        Ok(out)
+    }
+    /// 所有迁移的执行情况
+    pub async fn status(&self) -> MigrateResult<Vec<MigrateStatus>> {
+        let records = self.records().await?;
+        let mut out = Vec::with_capacity(self.items.len());
+        for item in self.items.iter() {
+            let checksum = item.checksum();
+            let record = records
+                .iter()
+                .find(|e| e.crate_name == item.crate_name && e.version == item.version);
+            let (state, apply_time) = match record {
+                Some(record) if record.checksum == checksum => {
+                    (MigrateState::Applied, Some(record.apply_time))
+                }
+                Some(record) => (MigrateState::Changed, Some(record.apply_time)),
+                None => (MigrateState::Pending, None),
+            };
+            out.push(MigrateStatus {
+                crate_name: item.crate_name.to_owned(),
+                version: item.version,
+                name: item.name.to_owned(),
+                checksum,
+                state,
+                apply_time,
+            });
+        }
+        //只检查传入模块,未传入的模块可能未启用
+        for record in records {
+            if !self.items.iter().any(|e| e.crate_name == record.crate_name) {
+                continue;
+            }
+            if self
+                .items
+                .iter()
+                .any(|e| e.crate_name == record.crate_name && e.version == record.version)
+            {
+                continue;
+            }
+            out.push(MigrateStatus {
+                crate_name: record.crate_name,
+                version: record.version,
+                name: record.name,
+                checksum: record.checksum,
+                state: MigrateState::Missing,
+                apply_time: Some(record.apply_time),
+            });
+        }
+        Ok(out)
+    }
+    /// 未执行的迁移
+    pub async fn pending(&self) -> MigrateResult<Vec<MigrateStatus>> {
+        Ok(self
+            .status()
+            .await?
+            .into_iter()
+            .filter(|e| e.state == MigrateState::Pending)
+            .collect())
+    }
+    fn check_status(status: &[MigrateStatus]) -> MigrateResult<()> {
+        for item in status {
+            match item.state {
+                MigrateState::Changed => {
+                    return Err(MigrateError::Checksum {
+                        crate_name: item.crate_name.to_owned(),
+                        version: item.version,
+                    })
+                }
+                MigrateState::Missing => {
+                    return Err(MigrateError::Missing {
+                        crate_name: item.crate_name.to_owned(),
+                        version: item.version,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    /// 检查已执行的迁移未被修改且不存在未执行的迁移
+    pub async fn verify(&self) -> MigrateResult<()> {
+        let status = self.status().await?;
+        Self::check_status(&status)?;
+        let pending = status
+            .iter()
+            .filter(|e| e.state == MigrateState::Pending)
+            .count();
+        if pending > 0 {
+            return Err(MigrateError::Pending(pending));
+        }
+        Ok(())
+    }
+    async fn add_record(&self, item: &MigrateItem) -> MigrateResult<()> {
+        let sql = format!(
+            "insert into `{}` (crate_name,version,name,checksum,apply_time) values (?,?,?,?,?)",
+            self.table_name()
+        );
+        sqlx::query(&sql)
+            .bind(item.crate_name)
+            .bind(item.version)
+            .bind(item.name)
+            .bind(item.checksum())
+            .bind(now_time().unwrap_or_default())
+            .execute(&self.db)
+            .await?;
+        Ok(())
+    }
+    fn pending_items(&self, status: &[MigrateStatus]) -> Vec<&MigrateItem> {
+        self.items
+            .iter()
+            .filter(|item| {
+                status.iter().any(|e| {
+                    e.state == MigrateState::Pending
+                        && e.crate_name == item.crate_name
+                        && e.version == item.version
+                })
+            })
+            .collect()
+    }
+    /// 执行所有未执行的迁移,返回本次执行的迁移
+    /// 存在已修改的迁移时不执行
+    /// MySQL的DDL语句不支持事务,迁移中途失败时需手动处理已执行的语句
+    pub async fn apply(&self) -> MigrateResult<Vec<MigrateStatus>> {
+        let status = self.status().await?;
+        Self::check_status(&status)?;
+        let mut out = vec![];
+        for item in self.pending_items(&status) {
+            let sql = if self.table_prefix == MIGRATE_SQL_PREFIX {
+                item.sql.to_owned()
+            } else {
+                item.sql.replace(MIGRATE_SQL_PREFIX, &self.table_prefix)
+            };
+            for stmt in split_sql(&sql) {
+                sqlx::query(stmt)
+                    .execute(&self.db)
+                    .await
+                    .map_err(|err| MigrateError::Apply {
+                        crate_name: item.crate_name.to_owned(),
+                        version: item.version,
+                        err,
+                    })?;
+            }
+            self.add_record(item).await?;
+            tracing::info!(
+                "migrate apply {}:{} {}",
+                item.crate_name,
+                item.version,
+                item.name
+            );
+            out.push(MigrateStatus {
+                crate_name: item.crate_name.to_owned(),
+                version: item.version,
+                name: item.name.to_owned(),
+                checksum: item.checksum(),
+                state: MigrateState::Applied,
+                apply_time: now_time().ok(),
+            });
+        }
+        Ok(out)
+    }
+    /// 将所有未执行的迁移标记为已执行但不执行SQL
+    /// 用于已手动导入 tables.sql 的数据库
+    pub async fn baseline(&self) -> MigrateResult<Vec<MigrateStatus>> {
+        let status = self.status().await?;
+        Self::check_status(&status)?;
+        let mut out = vec![];
+        for item in self.pending_items(&status) {
+            self.add_record(item).await?;
+            out.push(MigrateStatus {
+                crate_name: item.crate_name.to_owned(),
+                version: item.version,
+                name: item.name.to_owned(),
+                checksum: item.checksum(),
+                state: MigrateState::Applied,
+                apply_time: now_time().ok(),
+            });
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{fluent_message, FluentMessage, IntoFluentMessage};
+
+#[derive(Debug)]
+pub enum MigrateError {
+    Sqlx(sqlx::Error),
+    //已执行的迁移内容被修改
+    Checksum {
+        crate_name: String,
+        version: u32,
+    },
+    //数据库中已执行但代码中不存在的迁移
+    Missing {
+        crate_name: String,
+        version: u32,
+    },
+    //存在未执行的迁移
+    Pending(usize),
+    //执行迁移SQL失败
+    Apply {
+        crate_name: String,
+        version: u32,
+        err: sqlx::Error,
+    },
+}
+
+impl IntoFluentMessage for MigrateError {
+    fn to_fluent_message(&self) -> FluentMessage {
+        match self {
+            MigrateError::Sqlx(err) => fluent_message!("sqlx-error", err),
+            MigrateError::Checksum {
+                crate_name,
+                version,
+            } => fluent_message!("migrate-checksum-error", {
+                "crate_name": crate_name,
+                "version": version
+            }),
+            MigrateError::Missing {
+                crate_name,
+                version,
+            } => fluent_message!("migrate-missing-error", {
+                "crate_name": crate_name,
+                "version": version
+            }),
+            MigrateError::Pending(num) => fluent_message!("migrate-pending-error", num),
+            MigrateError::Apply {
+                crate_name,
+                version,
+                err,
+            } => fluent_message!("migrate-apply-error", {
+                "crate_name": crate_name,
+                "version": version,
+                "msg": err
+            }),
+        }
+    }
+}
+
+impl From<sqlx::Error> for MigrateError {
+    fn from(err: sqlx::Error) -> Self {
+        MigrateError::Sqlx(err)
+    }
+}
+
+pub type MigrateResult<T> = Result<T, MigrateError>;
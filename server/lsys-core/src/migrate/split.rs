@@ -0,0 +1,72 @@
+//按分号拆分SQL语句,忽略字符串及注释中的分号
+pub(crate) fn split_sql(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut out = vec![];
+    let mut start = 0;
+    let mut i = 0;
+    let mut quote: Option<u8> = None;
+    while i < bytes.len() {
+        let c = bytes[i];
+        match quote {
+            Some(q) => {
+                if c == b'\\' {
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                b'\'' | b'"' | b'`' => quote = Some(c),
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'#' => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i += 2;
+                    while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                b';' => {
+                    out.push(&sql[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+    if start < sql.len() {
+        out.push(&sql[start..]);
+    }
+    out.into_iter()
+        .filter(|e| !is_empty_sql(e))
+        .map(|e| e.trim())
+        .collect()
+}
+
+//仅包含空白及注释
+fn is_empty_sql(sql: &str) -> bool {
+    sql.lines()
+        .map(|e| e.trim())
+        .all(|e| e.is_empty() || e.starts_with("--") || e.starts_with('#'))
+}
+
+#[test]
+fn test_split_sql() {
+    let sql = "-- a;b\nCREATE TABLE `t` (`a` int COMMENT 'x;y') COMMENT = 'it''s;';\n/* c; */ INSERT INTO t VALUES ('\\';');\n-- end";
+    let out = split_sql(sql);
+    assert_eq!(out.len(), 2);
+    assert_eq!(
+        out[0],
+        "-- a;b\nCREATE TABLE `t` (`a` int COMMENT 'x;y') COMMENT = 'it''s;'"
+    );
+    assert_eq!(out[1], "/* c; */ INSERT INTO t VALUES ('\\';')");
+}
@@ -11,15 +11,15 @@ use snowflake::SnowflakeIdGenerator;
 use tokio::sync::{mpsc, Mutex, RwLock,};
 use tokio::time::Duration;
 
-use futures_util::StreamExt;
+use tracing::{debug, info, warn};
 
-use tracing::{debug, error, info, warn};
-
-use crate::AppCore;
+use crate::{AppCore, IntoFluentMessage, TraceContext};
+mod node;
 mod result;
+mod transport;
+pub use node::*;
 pub use result::*;
-use crate::IntoFluentMessage;
-use redis::AsyncCommands as _;
+pub use transport::*;
 //发送消息
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MsgSendBody {
@@ -30,6 +30,9 @@ pub struct MsgSendBody {
    pub target_host: Option<String>,
    pub ignore_local: bool,
    pub reply: bool,
+   //发送方的请求追踪上下文
+   #[serde(default)]
+   pub trace: TraceContext,
 }
 //执行结果
 #[derive(Serialize, Deserialize, Clone)]
@@ -53,13 +56,12 @@ pub trait RemoteTask:Sync+Send{
 }
 
 pub struct RemoteNotify {
-    channel_name: &'static str,
-    app_core: Arc<AppCore>,
     hostname: String,
-    redis: deadpool_redis::Pool,
+    transport: Box<dyn RemoteNotifyTransport>,
     callback: Mutex<HashMap<i64, mpsc::Sender<MsgResultBody>>>,
     id_generator: Mutex<SnowflakeIdGenerator>,
     run_list: RwLock<Vec<Box<dyn RemoteTask>>>,
+    nodes: RemoteNodes,
 }
 
 impl RemoteNotify {
@@ -72,16 +74,56 @@ impl RemoteNotify {
             .unwrap_or_default()
             .to_string_lossy()
             .to_string();
-        let id_generator = Mutex::new(app_core.create_snowflake_id_generator());
-        Ok(Self {
-            channel_name,
-            app_core,
-            redis,
+        let id_generator = app_core.create_snowflake_id_generator();
+        Ok(Self::with_transport(
             hostname,
             id_generator,
+            Box::new(RemoteNotifyRedisTransport::new(channel_name, app_core, redis)),
+        ))
+    }
+    /// 单机模式,使用进程内消息传输,消息通知不依赖REDIS
+    pub fn new_memory(app_core: &AppCore) -> Self {
+        let hostname = hostname::get()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        Self::with_transport(
+            hostname,
+            app_core.create_snowflake_id_generator(),
+            Box::new(RemoteNotifyMemoryTransport::default()),
+        )
+    }
+    /// 使用指定消息传输创建
+    /// * `hostname` - 本节点主机名,需在所有节点中唯一
+    /// * `id_generator` - 消息ID生成器
+    /// * `transport` - 消息传输,如单机时使用 RemoteNotifyMemoryTransport
+    pub fn with_transport(
+        hostname: String,
+        id_generator: SnowflakeIdGenerator,
+        transport: Box<dyn RemoteNotifyTransport>,
+    ) -> Self {
+        Self {
+            nodes: RemoteNodes::new(hostname.clone()),
+            hostname,
+            transport,
+            id_generator: Mutex::new(id_generator),
             callback: Mutex::new(HashMap::new()),
             run_list:RwLock::new(vec![]),
-        })
+        }
+    }
+    //本节点主机名
+    pub fn hostname(&self) -> &str {
+        &self.hostname
+    }
+    /// 设置本节点信息,通过心跳发送给其他节点
+    /// * `version` - 程序版本
+    /// * `features` - 启用的功能
+    pub fn set_node_info(&self, version: &str, features: Vec<String>) {
+        self.nodes.set_local(version, features);
+    }
+    /// 在线节点列表,包含本节点
+    pub fn nodes(&self) -> Vec<RemoteNodeInfo> {
+        self.nodes.list()
     }
 }
 
@@ -93,10 +135,21 @@ pub enum LocalExecType {
     IgnoreLocal, //目标包含本机的忽略本机执行
 }
 pub struct ReplyWait {
-    pub max_node: usize,
+    pub max_node: usize,//等待返回的节点数,为0时按在线节点数等待
     pub timeout: u64,
 }
 
+impl ReplyWait {
+    /// 等待所有目标节点返回,节点数由在线节点自动计算
+    /// * `timeout` - 超时,单位毫秒
+    pub fn new(timeout: u64) -> Self {
+        Self {
+            max_node: 0,
+            timeout,
+        }
+    }
+}
+
 impl RemoteNotify {
    
     pub async fn call<T: Serialize>(
@@ -118,6 +171,30 @@ impl RemoteNotify {
             //等于不做任何操作
             return Ok(out);
         }
+        let ignore_local = match local_exe_type {
+            LocalExecType::RemoteExec => false,
+            LocalExecType::LocalExec | LocalExecType::IgnoreLocal => true,
+        };
+        //未指定等待节点数时,按会执行该消息的节点数等待
+        //通过消息执行时所有节点执行,否则仅指定的其他节点执行
+        let reply_wait = match reply_wait {
+            Some(wait) if wait.max_node == 0 => {
+                let max_node = match (&target_host, ignore_local) {
+                    (_, false) => self.nodes.count(),
+                    (Some(_), true) => 1,
+                    (None, true) => 0,
+                };
+                if max_node == 0 {
+                    None
+                } else {
+                    Some(ReplyWait {
+                        max_node,
+                        timeout: wait.timeout,
+                    })
+                }
+            }
+            wait => wait,
+        };
         let msg_id = self.id_generator.lock().await.generate();
         let msg = MsgSendBody {
             data: serde_json::json!(data),
@@ -125,12 +202,11 @@ impl RemoteNotify {
             id: msg_id,
             from_host: self.hostname.clone(),
             target_host: target_host.to_owned(),
-            ignore_local: match local_exe_type {
-                LocalExecType::RemoteExec => false,
-                LocalExecType::LocalExec | LocalExecType::IgnoreLocal => true,
-            },
+            ignore_local,
             reply: reply_wait.is_some(),
+            trace: TraceContext::current(),
         };
+        let mut reply_rx = None;
         if local_exe_type==LocalExecType::RemoteExec//本机通过消息执行
             || match &target_host {
                 Some(th) => self.hostname != *th,
@@ -139,15 +215,16 @@ impl RemoteNotify {
         {
             match  serde_json::to_string(&MsgBody::Send(msg.to_owned())){
                 Ok(send_msg) => {
-                    let mut redis = self
-                        .redis
-                        .get()
-                        .await?;
-                    let res: Result<(), _> = redis.publish(self.channel_name, send_msg).await;
-                    if let Err(err) = res {
-                        warn!("notify redis clear cache fail :{}", err);
-                        return Err(RemoteNotifyError::Redis(err));
-                    };
+                    //需等待结果时先注册回调再发送,避免执行结果先于回调注册返回
+                    if let Some(wait) = &reply_wait {
+                        let (tx, rx) = mpsc::channel(wait.max_node);
+                        self.callback.lock().await.insert(msg_id, tx);
+                        reply_rx = Some(rx);
+                    }
+                    if let Err(err) = self.transport.publish(send_msg).await {
+                        self.callback.lock().await.remove(&msg_id);
+                        return Err(err);
+                    }
                 },
                 Err(err) => {
                     warn!("create notify message fail :{}", err);
@@ -194,8 +271,14 @@ impl RemoteNotify {
         }
         //有远程节点执行且要等待各节点的执行结果
         if let Some(wait) = reply_wait {
-            let (tx, rx) = mpsc::channel(wait.max_node);
-            self.callback.lock().await.insert(msg_id, tx);
+            let rx = match reply_rx {
+                Some(rx) => rx,
+                None => {
+                    let (tx, rx) = mpsc::channel(wait.max_node);
+                    self.callback.lock().await.insert(msg_id, tx);
+                    rx
+                }
+            };
             // 调用 foo，并传入信道的发送端，并使用 timeout 包裹它
             let ret = Self::receiver_data(rx, wait.timeout, wait.max_node).await;
             self.callback.lock().await.remove(&msg_id);
@@ -256,11 +339,15 @@ impl RemoteNotify {
                     info!("ignore target self msg :{}", send.data.to_string());
                     return Ok(());
                 }
+                if send.msg_type == REMOTE_NOTIFY_TYPE_NODE {
+                    return self.node_heartbeat(send).await;
+                }
                 for tmp in self.run_list.read().await.iter() {
                     if tmp.msg_type() == send.msg_type {
                         let reply = send.reply;
                         let reply_id = send.id;
-                        let res = tmp.run(send).await;
+                        let trace = send.trace.clone();
+                        let res = trace.scope("remote_notify", tmp.run(send)).await;
                         if let Err(ref err) = res {
                             info!("run notify fail: {}", err);
                         }
@@ -272,14 +359,8 @@ impl RemoteNotify {
                             };
                             match serde_json::to_string(&MsgBody::Result(msg)){
                                 Ok(send_msg) => {
-                                    let mut redis = self
-                                        .redis
-                                        .get()
-                                        .await
-                                        .map_err(|e| e.to_string())?;
-                                    let res: Result<(), _> = redis.publish(self.channel_name, send_msg).await;
-                                    if let Err(err) = res {
-                                        warn!("reply exec to redis fail :{}", err);
+                                    if let Err(err) = self.transport.publish(send_msg).await {
+                                        warn!("reply exec fail :{}", err.to_fluent_message().default_format());
                                     };
                                 },
                                 Err(err) => {
@@ -307,64 +388,168 @@ impl RemoteNotify {
             }
         }
     }
+    //收到其他节点心跳
+    async fn node_heartbeat(&self, msg: MsgSendBody) -> Result<(), String> {
+        let info =
+            serde_json::from_value::<RemoteNodeInfo>(msg.data).map_err(|e| e.to_string())?;
+        if info.hostname == self.hostname {
+            return Ok(());
+        }
+        let hostname = info.hostname.clone();
+        //新上线节点的广播心跳,立即回复本节点信息,不必等待下次心跳
+        if self.nodes.update(info) && msg.target_host.is_none() {
+            info!("remote node online:{}", hostname);
+            self.call(
+                REMOTE_NOTIFY_TYPE_NODE,
+                self.nodes.local(),
+                Some(hostname),
+                LocalExecType::IgnoreLocal,
+                None,
+            )
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?;
+        }
+        Ok(())
+    }
+    //定时广播本节点心跳
+    async fn node_heartbeat_send(&self) {
+        //等待消息监听完成
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        loop {
+            if let Err(err) = self
+                .call(
+                    REMOTE_NOTIFY_TYPE_NODE,
+                    self.nodes.local(),
+                    None,
+                    LocalExecType::RemoteExec,
+                    None,
+                )
+                .await
+            {
+                warn!(
+                    "send node heartbeat fail:{}",
+                    err.to_fluent_message().default_format()
+                );
+            }
+            tokio::time::sleep(Duration::from_secs(NODE_HEARTBEAT_TIME)).await;
+        }
+    }
     pub async fn push_run(&self,task:Box<dyn RemoteTask>){
         self.run_list.write().await.push(task);
     }
     pub async fn listen(&self) {
-        loop {
-            match self.app_core.create_redis_client() {
-                Ok(redis_client) => {
-                    let con_res = redis_client.get_async_connection().await;
-                    match con_res {
-                        Ok(con) => {
-                            let mut pubsub = con.into_pubsub();
-                            let res = pubsub.subscribe(self.channel_name).await;
-                            if let Err(err) = res {
-                                error!("listen sub fail :{}", err);
-                                continue;
-                            } else {
-                                info!("listen remote channel succ:{}", self.channel_name);
-                            }
-                            let mut pubsub_stream = pubsub.on_message();
-                            loop {
-                                match pubsub_stream.next().await {
-                                    Some(msg) => match msg.get_payload::<String>() {
-                                        Ok(pubsub_msg) => {
-                                            debug!("recv msg:{}", pubsub_msg);
-                                            match serde_json::from_str::<MsgBody>(&pubsub_msg) {
-                                                Ok(msg_body) => {
-                                                    if let Err(err) =
-                                                        self.listen_run(msg_body).await
-                                                    {
-                                                        warn!("run remote msg fail :{}", err);
-                                                    }
-                                                }
-                                                Err(err) => {
-                                                    error!("parse payload fail :{}", err);
-                                                }
-                                            }
-                                        }
-                                        Err(err) => {
-                                            error!("read payload fail :{}", err);
-                                        }
-                                    },
-                                    None => {
-                                        continue;
-                                    }
-                                }
-                            }
-                        }
-                        Err(err) => {
-                            error!("clear conn redis:{}", err);
-                            tokio::time::sleep(Duration::from_secs(1)).await;
+        let (tx, mut rx) = mpsc::channel::<String>(1024);
+        let run = async {
+            while let Some(msg) = rx.recv().await {
+                debug!("recv msg:{}", msg);
+                match serde_json::from_str::<MsgBody>(&msg) {
+                    Ok(msg_body) => {
+                        if let Err(err) = self.listen_run(msg_body).await {
+                            warn!("run remote msg fail :{}", err);
                         }
                     }
-                }
-                Err(err) => {
-                    warn!("create remote notify listen client fail:{}", err.to_fluent_message().default_format());
-                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    Err(err) => {
+                        warn!("parse payload fail :{}", err);
+                    }
                 }
             }
+        };
+        tokio::join!(self.transport.listen(tx), run, self.node_heartbeat_send());
+    }
+}
+
+#[tokio::test]
+async fn test_memory_notify() {
+    struct EchoTask;
+    #[async_trait]
+    impl RemoteTask for EchoTask {
+        fn msg_type(&self) -> u8 {
+            1
         }
+        async fn run(&self, msg: MsgSendBody) -> Result<Option<Value>, String> {
+            Ok(Some(msg.data))
+        }
+    }
+    //同一传输通道模拟两个节点
+    let transport = RemoteNotifyMemoryTransport::default();
+    let node1 = Arc::new(RemoteNotify::with_transport(
+        "node1".to_string(),
+        SnowflakeIdGenerator::new(1, 1),
+        Box::new(transport.clone()),
+    ));
+    let node2 = Arc::new(RemoteNotify::with_transport(
+        "node2".to_string(),
+        SnowflakeIdGenerator::new(1, 2),
+        Box::new(transport),
+    ));
+    for node in [node1.clone(), node2.clone()] {
+        node.push_run(Box::new(EchoTask)).await;
+        tokio::spawn(async move { node.listen().await });
     }
+    //等待节点心跳
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert_eq!(
+        node1
+            .nodes()
+            .iter()
+            .map(|e| e.hostname.as_str())
+            .collect::<Vec<_>>(),
+        vec!["node1", "node2"]
+    );
+    let reply = || Some(ReplyWait::new(1000));
+    //所有节点执行并等待结果
+    let mut res = node1
+        .call(1, "a", None, LocalExecType::RemoteExec, reply())
+        .await
+        .unwrap();
+    res.sort_by(|a, b| a.from_host.cmp(&b.from_host));
+    assert_eq!(
+        res.iter().map(|e| e.from_host.as_str()).collect::<Vec<_>>(),
+        vec!["node1", "node2"]
+    );
+    //仅本机直接执行
+    let res = node1
+        .call(
+            1,
+            "b",
+            Some("node1".to_string()),
+            LocalExecType::LocalExec,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].data, Ok(Some(serde_json::json!("b"))));
+    //本机直接执行,其他节点忽略该消息,无需等待
+    let res = node1
+        .call(1, "e", None, LocalExecType::LocalExec, reply())
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].from_host, "node1");
+    //指定其他节点执行
+    let res = node1
+        .call(
+            1,
+            "c",
+            Some("node2".to_string()),
+            LocalExecType::LocalExec,
+            reply(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].from_host, "node2");
+    //忽略本机
+    let res = node1
+        .call(
+            1,
+            "d",
+            Some("node1".to_string()),
+            LocalExecType::IgnoreLocal,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(res.is_empty());
 }
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::now_time;
+
+pub const REMOTE_NOTIFY_TYPE_NODE: u8 = 104;
+
+//心跳发送间隔,单位秒
+pub(crate) const NODE_HEARTBEAT_TIME: u64 = 10;
+//超过该时间未收到心跳视为离线,单位秒
+pub(crate) const NODE_TIMEOUT: u64 = 30;
+
+//集群节点信息
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RemoteNodeInfo {
+    pub hostname: String,
+    //节点启动时间
+    pub start_time: u64,
+    //节点程序版本
+    pub version: String,
+    //节点启用的功能
+    pub features: Vec<String>,
+    //最后收到心跳的时间,以本机时间为准
+    pub last_time: u64,
+}
+
+// 集群节点注册表
+// 各节点定时通过 RemoteNotify 广播心跳,超时未收到心跳的节点视为离线
+pub(crate) struct RemoteNodes {
+    local: RwLock<RemoteNodeInfo>,
+    nodes: RwLock<HashMap<String, RemoteNodeInfo>>,
+}
+
+impl RemoteNodes {
+    pub fn new(hostname: String) -> Self {
+        let now = now_time().unwrap_or_default();
+        Self {
+            local: RwLock::new(RemoteNodeInfo {
+                hostname,
+                start_time: now,
+                version: String::new(),
+                features: vec![],
+                last_time: now,
+            }),
+            nodes: RwLock::new(HashMap::new()),
+        }
+    }
+    //本节点信息
+    pub fn local(&self) -> RemoteNodeInfo {
+        let mut info = self.local.read().clone();
+        info.last_time = now_time().unwrap_or_default();
+        info
+    }
+    pub fn set_local(&self, version: &str, features: Vec<String>) {
+        let mut info = self.local.write();
+        info.version = version.to_owned();
+        info.features = features;
+    }
+    //更新其他节点心跳,返回是否为新上线节点
+    pub fn update(&self, mut info: RemoteNodeInfo) -> bool {
+        let now = now_time().unwrap_or_default();
+        info.last_time = now;
+        let mut nodes = self.nodes.write();
+        let is_new = nodes
+            .get(&info.hostname)
+            .map(|e| e.last_time + NODE_TIMEOUT < now || e.start_time != info.start_time)
+            .unwrap_or(true);
+        nodes.insert(info.hostname.clone(), info);
+        is_new
+    }
+    //在线节点,包含本节点
+    pub fn list(&self) -> Vec<RemoteNodeInfo> {
+        let now = now_time().unwrap_or_default();
+        let local = self.local();
+        let mut nodes = self.nodes.write();
+        nodes.retain(|_, e| e.last_time + NODE_TIMEOUT >= now);
+        let mut out = nodes
+            .values()
+            .filter(|e| e.hostname != local.hostname)
+            .cloned()
+            .collect::<Vec<_>>();
+        out.push(local);
+        out.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+        out
+    }
+    //在线节点数量,包含本节点
+    pub fn count(&self) -> usize {
+        self.list().len()
+    }
+}
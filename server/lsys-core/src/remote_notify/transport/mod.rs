@@ -0,0 +1,20 @@
+mod transport_memory;
+mod transport_redis;
+pub use transport_memory::*;
+pub use transport_redis::*;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+use super::RemoteNotifyError;
+
+// 远程通知的消息传输
+// 负责:将消息发送到所有节点[包括本节点],接收其他节点发送的消息
+#[async_trait]
+pub trait RemoteNotifyTransport: Send + Sync {
+    /// 发送消息到所有监听的节点
+    async fn publish(&self, msg: String) -> Result<(), RemoteNotifyError>;
+    /// 监听消息,收到的消息写入 `sender`
+    /// 内部循环不退出,连接断开时由实现自行重连
+    async fn listen(&self, sender: mpsc::Sender<String>);
+}
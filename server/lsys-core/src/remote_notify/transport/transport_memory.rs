@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
+
+use super::RemoteNotifyTransport;
+use crate::RemoteNotifyError;
+
+// 基于进程内广播通道的消息传输
+// 用于单机部署或测试,不依赖REDIS
+// 克隆出的实例共用同一通道,可在单进程内模拟多个节点
+#[derive(Clone)]
+pub struct RemoteNotifyMemoryTransport {
+    sender: broadcast::Sender<String>,
+}
+
+impl Default for RemoteNotifyMemoryTransport {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl RemoteNotifyMemoryTransport {
+    /// * `capacity` - 通道缓存消息数量,监听者处理过慢时超出部分将被丢弃
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+}
+
+#[async_trait]
+impl RemoteNotifyTransport for RemoteNotifyMemoryTransport {
+    async fn publish(&self, msg: String) -> Result<(), RemoteNotifyError> {
+        //无监听者时消息直接丢弃,跟REDIS发布行为一致
+        let _ = self.sender.send(msg);
+        Ok(())
+    }
+    async fn listen(&self, sender: mpsc::Sender<String>) {
+        let mut receiver = self.sender.subscribe();
+        loop {
+            match receiver.recv().await {
+                Ok(msg) => {
+                    if sender.send(msg).await.is_err() {
+                        return;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(num)) => {
+                    warn!("memory notify lagged, skip {} msg", num);
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    }
+}
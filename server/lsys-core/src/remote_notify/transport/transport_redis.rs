@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use redis::AsyncCommands as _;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use super::RemoteNotifyTransport;
+use crate::{AppCore, IntoFluentMessage, RemoteNotifyError};
+
+// 基于REDIS发布订阅的消息传输
+pub struct RemoteNotifyRedisTransport {
+    channel_name: &'static str,
+    app_core: Arc<AppCore>,
+    redis: deadpool_redis::Pool,
+}
+
+impl RemoteNotifyRedisTransport {
+    /// * `channel_name` - 发布订阅使用的REDIS频道
+    pub fn new(
+        channel_name: &'static str,
+        app_core: Arc<AppCore>,
+        redis: deadpool_redis::Pool,
+    ) -> Self {
+        Self {
+            channel_name,
+            app_core,
+            redis,
+        }
+    }
+}
+
+#[async_trait]
+impl RemoteNotifyTransport for RemoteNotifyRedisTransport {
+    async fn publish(&self, msg: String) -> Result<(), RemoteNotifyError> {
+        let mut redis = self.redis.get().await?;
+        let res: Result<(), _> = redis.publish(self.channel_name, msg).await;
+        if let Err(err) = res {
+            warn!("notify redis publish fail :{}", err);
+            return Err(RemoteNotifyError::Redis(err));
+        };
+        Ok(())
+    }
+    async fn listen(&self, sender: mpsc::Sender<String>) {
+        loop {
+            match self.app_core.create_redis_client() {
+                Ok(redis_client) => {
+                    let con_res = redis_client.get_async_connection().await;
+                    match con_res {
+                        Ok(con) => {
+                            let mut pubsub = con.into_pubsub();
+                            let res = pubsub.subscribe(self.channel_name).await;
+                            if let Err(err) = res {
+                                error!("listen sub fail :{}", err);
+                                continue;
+                            } else {
+                                info!("listen remote channel succ:{}", self.channel_name);
+                            }
+                            let mut pubsub_stream = pubsub.on_message();
+                            loop {
+                                match pubsub_stream.next().await {
+                                    Some(msg) => match msg.get_payload::<String>() {
+                                        Ok(pubsub_msg) => {
+                                            if sender.send(pubsub_msg).await.is_err() {
+                                                return;
+                                            }
+                                        }
+                                        Err(err) => {
+                                            error!("read payload fail :{}", err);
+                                        }
+                                    },
+                                    None => {
+                                        continue;
+                                    }
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            error!("clear conn redis:{}", err);
+                            tokio::time::sleep(Duration::from_secs(1)).await;
+                        }
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        "create remote notify listen client fail:{}",
+                        err.to_fluent_message().default_format()
+                    );
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+mod task_memory;
+mod task_redis;
+pub use task_memory::*;
+pub use task_redis::*;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use redis::{FromRedisValue, ToRedisArgs};
+use async_trait::async_trait;
+
+use super::{CronMemoryStore, CronRedisStore, CronStore, TaskData, TaskResult};
+use crate::AppCore;
+
+// 任务派发的存储后端
+// 负责:任务触发通知,任务读取锁定,执行中任务记录
+#[async_trait]
+pub trait TaskBackend<I>: Send + Sync {
+    //任务名,用于日志输出
+    fn task_name(&self) -> &str;
+    /// 通知有新任务
+    async fn notify(&self) -> TaskResult<()>;
+    /// 等待任务通知
+    /// * `timeout` - 等待时间,单位秒
+    /// @return 收到通知返回true,超时返回false
+    async fn wait_notify(&self, timeout: usize) -> TaskResult<bool>;
+    /// 锁定任务读取,已被其他执行者锁定时返回false
+    /// * `timeout` - 锁定超时,单位秒
+    async fn read_lock(&self, timeout: usize) -> TaskResult<bool>;
+    /// 解除任务读取锁定
+    async fn read_unlock(&self) -> TaskResult<()>;
+    /// 获取执行中任务
+    async fn task_data(&self) -> TaskResult<HashMap<I, TaskData>>;
+    /// 添加执行中任务
+    async fn task_add(&self, pk: &I, data: &TaskData) -> TaskResult<()>;
+    /// 删除执行中任务
+    async fn task_del(&self, pk: &I) -> TaskResult<()>;
+}
+
+// 任务后端选择
+// 通过配置 task_backend 指定: redis [默认] , memory [单机使用,不依赖REDIS]
+#[derive(Clone)]
+pub enum TaskBackendConfig {
+    Redis {
+        app_core: Arc<AppCore>,
+        redis: deadpool_redis::Pool,
+    },
+    Memory,
+}
+
+impl TaskBackendConfig {
+    pub fn new(app_core: Arc<AppCore>, redis: deadpool_redis::Pool) -> Self {
+        let backend = app_core
+            .config
+            .find(None)
+            .get_string("task_backend")
+            .unwrap_or_default();
+        match backend.as_str() {
+            "memory" => TaskBackendConfig::Memory,
+            _ => TaskBackendConfig::Redis { app_core, redis },
+        }
+    }
+    /// 创建任务后端
+    /// * `list_notify` - 任务触发监听的REDIS KEY
+    /// * `read_lock_key` - 任务读取锁定Redis KEY
+    /// * `task_list_key` - 存放执行中任务的REDIS key,内存后端时作为任务名
+    pub fn create<
+        I: FromRedisValue + ToRedisArgs + Eq + Hash + Send + Sync + Display + Clone + 'static,
+    >(
+        &self,
+        list_notify: String,
+        read_lock_key: String,
+        task_list_key: String,
+    ) -> Box<dyn TaskBackend<I>> {
+        match self {
+            TaskBackendConfig::Redis { app_core, redis } => Box::new(TaskRedisBackend::new(
+                app_core.clone(),
+                redis.clone(),
+                list_notify,
+                read_lock_key,
+                task_list_key,
+            )),
+            TaskBackendConfig::Memory => Box::new(TaskMemoryBackend::new(task_list_key)),
+        }
+    }
+    /// 创建定时任务存储
+    /// * `key_prefix` - 定时任务使用的REDIS KEY前缀
+    pub fn create_cron_store(&self, key_prefix: String) -> Box<dyn CronStore> {
+        match self {
+            TaskBackendConfig::Redis { redis, .. } => {
+                Box::new(CronRedisStore::new(redis.clone(), key_prefix))
+            }
+            TaskBackendConfig::Memory => Box::new(CronMemoryStore::new()),
+        }
+    }
+}
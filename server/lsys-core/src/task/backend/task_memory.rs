@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use super::TaskBackend;
+use crate::{now_time, TaskData, TaskResult};
+
+// 基于进程内存的任务后端
+// 仅适用于单机部署或测试,不依赖REDIS
+pub struct TaskMemoryBackend<I> {
+    task_name: String,
+    notify: Notify,
+    //读取锁定超时时间,0为未锁定
+    read_lock: Mutex<u64>,
+    task_list: Mutex<HashMap<I, TaskData>>,
+}
+
+impl<I> TaskMemoryBackend<I> {
+    /// * `task_name` - 任务名,用于日志输出
+    pub fn new(task_name: String) -> Self {
+        Self {
+            task_name,
+            notify: Notify::new(),
+            read_lock: Mutex::new(0),
+            task_list: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<I: Eq + Hash + Clone + Send + Sync> TaskBackend<I> for TaskMemoryBackend<I> {
+    fn task_name(&self) -> &str {
+        &self.task_name
+    }
+    async fn notify(&self) -> TaskResult<()> {
+        //无等待者时保留一次通知,多次通知合并为一次
+        self.notify.notify_one();
+        Ok(())
+    }
+    async fn wait_notify(&self, timeout: usize) -> TaskResult<bool> {
+        Ok(
+            tokio::time::timeout(Duration::from_secs(timeout as u64), self.notify.notified())
+                .await
+                .is_ok(),
+        )
+    }
+    async fn read_lock(&self, timeout: usize) -> TaskResult<bool> {
+        let now = now_time().unwrap_or_default();
+        let mut lock = self.read_lock.lock();
+        if *lock > now {
+            return Ok(false);
+        }
+        *lock = now + timeout as u64;
+        Ok(true)
+    }
+    async fn read_unlock(&self) -> TaskResult<()> {
+        *self.read_lock.lock() = 0;
+        Ok(())
+    }
+    async fn task_data(&self) -> TaskResult<HashMap<I, TaskData>> {
+        Ok(self.task_list.lock().clone())
+    }
+    async fn task_add(&self, pk: &I, data: &TaskData) -> TaskResult<()> {
+        self.task_list.lock().insert(pk.to_owned(), data.to_owned());
+        Ok(())
+    }
+    async fn task_del(&self, pk: &I) -> TaskResult<()> {
+        self.task_list.lock().remove(pk);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_memory_dispatch() {
+    use crate::{TaskAcquisition, TaskDispatch, TaskExecutor, TaskItem, TaskRecord};
+    struct TestItem(u64);
+    impl TaskItem<u64> for TestItem {
+        fn to_task_pk(&self) -> u64 {
+            self.0
+        }
+    }
+    struct TestAcquisition(Mutex<Vec<u64>>);
+    #[async_trait]
+    impl TaskAcquisition<u64, TestItem> for TestAcquisition {
+        async fn read_send_task(
+            &self,
+            tasking_record: &HashMap<u64, TaskData>,
+            limit: usize,
+        ) -> Result<TaskRecord<u64, TestItem>, String> {
+            let mut data = self.0.lock();
+            let take = data.len().min(limit);
+            let items = data
+                .drain(..take)
+                .filter(|e| !tasking_record.contains_key(e))
+                .map(TestItem)
+                .collect();
+            Ok(TaskRecord::new(items, !data.is_empty()))
+        }
+    }
+    #[derive(Clone)]
+    struct TestExecutor(tokio::sync::mpsc::Sender<u64>);
+    #[async_trait]
+    impl TaskExecutor<u64, TestItem> for TestExecutor {
+        async fn exec(&self, val: TestItem) -> Result<(), String> {
+            self.0.send(val.0).await.map_err(|e| e.to_string())
+        }
+    }
+    let task = std::sync::Arc::new(TaskDispatch::new(
+        Box::new(TaskMemoryBackend::new("test-task".to_string())),
+        Some(2),
+        60,
+        false,
+        60,
+    ));
+    let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+    let dispatch = task.clone();
+    let handle = tokio::spawn(async move {
+        dispatch
+            .dispatch(
+                &TestAcquisition(Mutex::new((1..=5).collect())),
+                TestExecutor(tx),
+            )
+            .await;
+    });
+    task.notify().await.unwrap();
+    let mut res = vec![];
+    for _ in 0..5 {
+        let pk = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        res.push(pk);
+    }
+    res.sort();
+    assert_eq!(res, vec![1, 2, 3, 4, 5]);
+    task.shutdown(Duration::from_secs(5)).await;
+    tokio::time::timeout(Duration::from_secs(1), handle)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(task.task_data().await.unwrap().is_empty());
+}
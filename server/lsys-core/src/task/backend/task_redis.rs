@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::from_utf8;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use redis::aio::Connection;
+use redis::{
+    AsyncCommands, ErrorKind, FromRedisValue, RedisError, RedisResult, ToRedisArgs, Value,
+};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use super::TaskBackend;
+use crate::{AppCore, IntoFluentMessage, TaskData, TaskError, TaskResult};
+
+impl FromRedisValue for TaskData {
+    fn from_redis_value(val: &Value) -> RedisResult<Self> {
+        let valstr = match *val {
+            Value::Data(ref bytes) => from_utf8(bytes)?.to_string(),
+            _ => {
+                return Err(RedisError::from((
+                    ErrorKind::TypeError,
+                    "Response was of incompatible type",
+                    format!(
+                        "Response type not string compatible. (response was {:?})",
+                        val
+                    ),
+                )))
+            }
+        };
+        match serde_json::from_str::<TaskData>(&valstr) {
+            Ok(data) => Ok(data),
+            Err(err) => Err(RedisError::from((
+                ErrorKind::TypeError,
+                "Response was of incompatible type",
+                format!(
+                    "Response type parse error:{}. (response was {:?})",
+                    err, val
+                ),
+            ))),
+        }
+    }
+}
+impl ToRedisArgs for TaskData {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + redis::RedisWrite,
+    {
+        out.write_arg(serde_json::to_string(self).unwrap_or_default().as_bytes())
+    }
+}
+
+// 基于REDIS的任务后端,多台服务器共享任务
+pub struct TaskRedisBackend {
+    app_core: Arc<AppCore>,
+    redis: deadpool_redis::Pool,
+    //任务触发监听的REDIS KEY
+    list_notify: String,
+    //任务读取锁定Redis KEY
+    read_lock_key: String,
+    //存放执行中任务的REDIS key
+    task_list_key: String,
+    //阻塞监听任务通知用的连接,不占用连接池
+    listen_conn: Mutex<Option<Connection>>,
+}
+
+impl TaskRedisBackend {
+    /// * `app_core` - 公共APP句柄,用于创建阻塞监听的REDIS连接
+    /// * `redis` - REDIS连接池
+    /// * `list_notify` - 任务触发监听的REDIS KEY
+    /// * `read_lock_key` - 任务读取锁定Redis KEY
+    /// * `task_list_key` - 存放执行中任务的REDIS key
+    pub fn new(
+        app_core: Arc<AppCore>,
+        redis: deadpool_redis::Pool,
+        list_notify: String,
+        read_lock_key: String,
+        task_list_key: String,
+    ) -> Self {
+        Self {
+            app_core,
+            redis,
+            list_notify,
+            read_lock_key,
+            task_list_key,
+            listen_conn: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl<I: FromRedisValue + ToRedisArgs + Eq + Hash + Send + Sync + Display + 'static> TaskBackend<I>
+    for TaskRedisBackend
+{
+    fn task_name(&self) -> &str {
+        &self.task_list_key
+    }
+    async fn notify(&self) -> TaskResult<()> {
+        let mut redis = self.redis.get().await?;
+        redis.lpush::<_, _, ()>(&self.list_notify, 1).await?;
+        Ok(())
+    }
+    async fn wait_notify(&self, timeout: usize) -> TaskResult<bool> {
+        let mut conn = self.listen_conn.lock().await;
+        let mut redis = match conn.take() {
+            Some(redis) => redis,
+            None => {
+                let client = self
+                    .app_core
+                    .create_redis_client()
+                    .map_err(|e| TaskError::System(e.to_fluent_message()))?;
+                client.get_async_connection().await?
+            }
+        };
+        //出错时丢弃该连接,下次重新连接
+        let block: Option<()> = redis.blpop(&self.list_notify, timeout).await?;
+        if let Err(err) = redis.ltrim::<_, ()>(&self.list_notify, 0, -1).await {
+            warn!("clear list error:{}", err);
+        }
+        *conn = Some(redis);
+        Ok(block.is_some())
+    }
+    async fn read_lock(&self, timeout: usize) -> TaskResult<bool> {
+        let mut redis = self.redis.get().await?;
+        let lock: Option<String> = redis::cmd("SET")
+            .arg(&self.read_lock_key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(timeout)
+            .query_async(&mut redis)
+            .await?;
+        Ok(lock.is_some())
+    }
+    async fn read_unlock(&self) -> TaskResult<()> {
+        let mut redis = self.redis.get().await?;
+        redis.del::<_, ()>(&self.read_lock_key).await?;
+        Ok(())
+    }
+    async fn task_data(&self) -> TaskResult<HashMap<I, TaskData>> {
+        let mut redis = self.redis.get().await?;
+        let redis_data: Option<HashMap<I, TaskData>> = redis.hgetall(&self.task_list_key).await?;
+        Ok(redis_data.unwrap_or_default())
+    }
+    async fn task_add(&self, pk: &I, data: &TaskData) -> TaskResult<()> {
+        let mut redis = self.redis.get().await?;
+        redis
+            .hset::<_, _, _, ()>(&self.task_list_key, pk, data)
+            .await?;
+        Ok(())
+    }
+    async fn task_del(&self, pk: &I) -> TaskResult<()> {
+        let mut redis = self.redis.get().await?;
+        redis.hdel::<_, _, ()>(&self.task_list_key, pk).await?;
+        Ok(())
+    }
+}
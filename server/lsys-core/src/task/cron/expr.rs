@@ -0,0 +1,253 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use std::str::FromStr;
+
+use crate::{fluent_message, FluentMessage};
+
+// 定时表达式
+// 格式: [秒] 分 时 日 月 周
+// 5段时秒固定为0,6段时第一段为秒
+// 每段支持: * , - / 及 ? [等同*]
+// 周: 0-7 , 0跟7都表示周日
+// 另支持: @yearly @monthly @weekly @daily @hourly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    second: u64,
+    minute: u64,
+    hour: u64,
+    day: u64,
+    month: u64,
+    week: u64,
+    //日或周非*时,两者满足其一即可
+    day_any: bool,
+    week_any: bool,
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<(u64, bool), String> {
+    let mut bits = 0u64;
+    let mut any = false;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .map_err(|_| format!("step [{}] wrong", part))?;
+                if step == 0 {
+                    return Err(format!("step [{}] can't be zero", part));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" || range == "?" {
+            if step == 1 {
+                any = true;
+            }
+            (min, max)
+        } else {
+            match range.split_once('-') {
+                Some((start, end)) => (
+                    start
+                        .parse::<u32>()
+                        .map_err(|_| format!("range [{}] wrong", part))?,
+                    end.parse::<u32>()
+                        .map_err(|_| format!("range [{}] wrong", part))?,
+                ),
+                None => {
+                    let val = range
+                        .parse::<u32>()
+                        .map_err(|_| format!("value [{}] wrong", part))?;
+                    //单值带步长时到最大值
+                    if step > 1 {
+                        (val, max)
+                    } else {
+                        (val, val)
+                    }
+                }
+            }
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("[{}] out of range {}-{}", part, min, max));
+        }
+        let mut i = start;
+        while i <= end {
+            bits |= 1 << i;
+            i += step;
+        }
+    }
+    Ok((bits, any))
+}
+
+impl FromStr for CronExpr {
+    type Err = FluentMessage;
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        let expr = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            e => e,
+        };
+        let fields = expr.split_whitespace().collect::<Vec<&str>>();
+        let fields = match fields.len() {
+            5 => {
+                let mut tmp = vec!["0"];
+                tmp.extend(fields);
+                tmp
+            }
+            6 => fields,
+            _ => {
+                return Err(fluent_message!("cron-expr-error",{
+                    "expr":expr,
+                    "msg":"need 5 or 6 fields"
+                }))
+            }
+        };
+        let parse = |index: usize, min: u32, max: u32| {
+            parse_field(fields[index], min, max).map_err(|err| {
+                fluent_message!("cron-expr-error",{
+                    "expr":expr,
+                    "msg":err
+                })
+            })
+        };
+        let (second, _) = parse(0, 0, 59)?;
+        let (minute, _) = parse(1, 0, 59)?;
+        let (hour, _) = parse(2, 0, 23)?;
+        let (day, day_any) = parse(3, 1, 31)?;
+        let (month, _) = parse(4, 1, 12)?;
+        let (mut week, week_any) = parse(5, 0, 7)?;
+        if week & (1 << 7) > 0 {
+            week = (week | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            second,
+            minute,
+            hour,
+            day,
+            month,
+            week,
+            day_any,
+            week_any,
+        })
+    }
+}
+
+impl CronExpr {
+    fn match_date(&self, date: &NaiveDate) -> bool {
+        let day = self.day & (1 << date.day()) > 0;
+        let week = self.week & (1 << date.weekday().num_days_from_sunday()) > 0;
+        match (self.day_any, self.week_any) {
+            (true, true) => true,
+            (false, true) => day,
+            (true, false) => week,
+            (false, false) => day || week,
+        }
+    }
+    //查找晚于指定时间的下一个执行时间点
+    fn next_naive(&self, time: &NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut next = time.with_nanosecond(0)? + Duration::seconds(1);
+        let max_year = next.year() + 5;
+        while next.year() <= max_year {
+            if self.month & (1 << next.month()) == 0 {
+                next = if next.month() == 12 {
+                    NaiveDate::from_ymd_opt(next.year() + 1, 1, 1)?
+                } else {
+                    NaiveDate::from_ymd_opt(next.year(), next.month() + 1, 1)?
+                }
+                .and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.match_date(&next.date()) {
+                next = next.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if self.hour & (1 << next.hour()) == 0 {
+                next = next.date().and_hms_opt(next.hour(), 0, 0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minute & (1 << next.minute()) == 0 {
+                next =
+                    next.date().and_hms_opt(next.hour(), next.minute(), 0)? + Duration::minutes(1);
+                continue;
+            }
+            if self.second & (1 << next.second()) == 0 {
+                next += Duration::seconds(1);
+                continue;
+            }
+            return Some(next);
+        }
+        None
+    }
+    /// 获取指定时间后的下一个执行时间,按本地时区计算
+    /// * `time` - unix时间戳,单位秒
+    pub fn next_time(&self, time: u64) -> Option<u64> {
+        let mut next = Local.timestamp_opt(time as i64, 0).single()?.naive_local();
+        loop {
+            next = self.next_naive(&next)?;
+            //夏令时跳过的时间不存在,继续查找
+            if let Some(dt) = Local.from_local_datetime(&next).earliest() {
+                let ts = dt.timestamp();
+                if ts > time as i64 {
+                    return Some(ts as u64);
+                }
+            }
+        }
+    }
+    /// 获取时间区间(start,end]内所有执行时间点
+    /// * `limit` - 最多返回数量,返回最后的limit个
+    pub fn times_between(&self, start: u64, end: u64, limit: usize) -> Vec<u64> {
+        let mut out = vec![];
+        let mut time = start;
+        while let Some(next) = self.next_time(time) {
+            if next > end {
+                break;
+            }
+            out.push(next);
+            if out.len() > limit {
+                out.remove(0);
+            }
+            time = next;
+        }
+        out
+    }
+}
+
+#[test]
+fn test_cron_expr() {
+    let time = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+    let next = |expr: &str, from: &str| {
+        CronExpr::from_str(expr)
+            .unwrap()
+            .next_naive(&time(from))
+            .unwrap()
+    };
+    assert_eq!(
+        next("*/15 * * * *", "2023-01-01 10:07:12"),
+        time("2023-01-01 10:15:00")
+    );
+    assert_eq!(
+        next("30 2 * * *", "2023-01-31 03:00:00"),
+        time("2023-02-01 02:30:00")
+    );
+    assert_eq!(
+        next("*/10 * * * * *", "2023-12-31 23:59:55"),
+        time("2024-01-01 00:00:00")
+    );
+    //2023-01-02 为周一
+    assert_eq!(
+        next("0 9 * * 1-5", "2022-12-31 10:00:00"),
+        time("2023-01-02 09:00:00")
+    );
+    assert_eq!(
+        next("0 0 29 2 *", "2023-01-01 00:00:00"),
+        time("2024-02-29 00:00:00")
+    );
+    assert_eq!(
+        next("@weekly", "2023-01-02 00:00:00"),
+        time("2023-01-08 00:00:00")
+    );
+    assert!(CronExpr::from_str("* * *").is_err());
+    assert!(CronExpr::from_str("61 * * * *").is_err());
+    assert!(CronExpr::from_str("*/0 * * * *").is_err());
+}
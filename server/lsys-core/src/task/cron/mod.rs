@@ -0,0 +1,323 @@
+mod expr;
+mod store;
+pub use expr::*;
+pub use store::*;
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+use tracing::{debug, info, warn};
+
+use super::{TaskError, TaskResult};
+use crate::{fluent_message, now_time, IntoFluentMessage};
+
+// 定时任务
+// 多台服务器注册同一任务时,每个执行时间点仅一台服务器执行
+#[async_trait]
+pub trait CronJob: Send + Sync {
+    /// 任务名,集群内唯一
+    fn job_name(&self) -> &str;
+    /// 执行时间点锁定时间,单位秒
+    /// 需大于服务器间的时间误差
+    fn lock_timeout(&self) -> usize {
+        3600
+    }
+    /// 执行任务
+    /// * `tick` - 计划执行的时间点
+    async fn run(&self, tick: u64) -> Result<(), String>;
+}
+
+// 错过执行时间点[如服务停止期间]的处理方式
+#[derive(Clone, Copy, Debug)]
+pub enum CronMissedPolicy {
+    //忽略错过的时间点
+    Skip,
+    //仅补执行最后一个错过的时间点
+    RunOnce,
+    //补执行错过的时间点,最多指定次数
+    RunAll(usize),
+}
+
+impl CronMissedPolicy {
+    fn limit(&self) -> usize {
+        match self {
+            CronMissedPolicy::Skip => 0,
+            CronMissedPolicy::RunOnce => 1,
+            CronMissedPolicy::RunAll(max) => *max,
+        }
+    }
+}
+
+//未计算下次执行时间
+const CRON_TICK_INIT: u64 = 0;
+//不会再执行
+const CRON_TICK_NONE: u64 = u64::MAX;
+
+struct CronJobItem {
+    expr_str: String,
+    expr: CronExpr,
+    missed: CronMissedPolicy,
+    job: Arc<dyn CronJob>,
+    next_tick: u64,
+}
+
+//已注册定时任务信息
+#[derive(Clone, Debug)]
+pub struct CronJobInfo {
+    pub job_name: String,
+    pub expr: String,
+    pub missed: CronMissedPolicy,
+    //下次执行时间,未计算或不再执行时为None
+    pub next_tick: Option<u64>,
+}
+
+pub struct CronTask {
+    store: Arc<dyn CronStore>,
+    hostname: String,
+    history_size: usize,
+    jobs: Mutex<Vec<CronJobItem>>,
+    notify: Notify,
+}
+
+impl CronTask {
+    /// * `store` - 定时任务存储,通过 TaskBackendConfig::create_cron_store 创建
+    /// * `history_size` - 每个任务保留的执行记录数量
+    pub fn new(store: Box<dyn CronStore>, history_size: usize) -> Self {
+        Self {
+            store: Arc::from(store),
+            hostname: hostname::get()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            history_size,
+            jobs: Mutex::new(vec![]),
+            notify: Notify::new(),
+        }
+    }
+    /// 注册定时任务
+    /// * `expr` - 定时表达式,格式: [秒] 分 时 日 月 周
+    /// * `missed` - 错过执行时间点的处理方式
+    /// * `job` - 任务实现
+    pub fn add(
+        &self,
+        expr: &str,
+        missed: CronMissedPolicy,
+        job: impl CronJob + 'static,
+    ) -> TaskResult<()> {
+        let cron_expr = CronExpr::from_str(expr).map_err(TaskError::System)?;
+        let mut jobs = self.jobs.lock();
+        if jobs.iter().any(|e| e.job.job_name() == job.job_name()) {
+            return Err(TaskError::System(fluent_message!("cron-job-exist",{
+                "name":job.job_name()
+            })));
+        }
+        jobs.push(CronJobItem {
+            expr_str: expr.to_owned(),
+            expr: cron_expr,
+            missed,
+            job: Arc::new(job),
+            next_tick: CRON_TICK_INIT,
+        });
+        drop(jobs);
+        self.notify.notify_one();
+        Ok(())
+    }
+    /// 已注册的定时任务
+    pub fn jobs(&self) -> Vec<CronJobInfo> {
+        self.jobs
+            .lock()
+            .iter()
+            .map(|e| CronJobInfo {
+                job_name: e.job.job_name().to_owned(),
+                expr: e.expr_str.to_owned(),
+                missed: e.missed,
+                next_tick: if e.next_tick == CRON_TICK_INIT || e.next_tick == CRON_TICK_NONE {
+                    None
+                } else {
+                    Some(e.next_tick)
+                },
+            })
+            .collect()
+    }
+    /// 定时任务执行记录,最新在前
+    pub async fn history(&self, job_name: &str) -> TaskResult<Vec<CronHistory>> {
+        self.store.history(job_name).await
+    }
+    //锁定时间点并执行任务
+    async fn run_job(
+        store: Arc<dyn CronStore>,
+        job: Arc<dyn CronJob>,
+        hostname: String,
+        history_size: usize,
+        tick: u64,
+    ) {
+        let job_name = job.job_name();
+        match store.tick_lock(job_name, tick, job.lock_timeout()).await {
+            Ok(true) => {}
+            Ok(false) => {
+                debug!("cron job {} on {} is run by other host", job_name, tick);
+                return;
+            }
+            Err(err) => {
+                warn!(
+                    "cron job {} on {} lock fail:{}",
+                    job_name,
+                    tick,
+                    err.to_fluent_message().default_format()
+                );
+                return;
+            }
+        }
+        if let Err(err) = store.set_last_tick(job_name, tick).await {
+            warn!(
+                "cron job {} set last tick fail:{}",
+                job_name,
+                err.to_fluent_message().default_format()
+            );
+        }
+        let start_time = now_time().unwrap_or_default();
+        info!("cron job {} on {} start", job_name, tick);
+        let error = match job.run(tick).await {
+            Ok(()) => None,
+            Err(err) => {
+                warn!("cron job {} on {} fail:{}", job_name, tick, err);
+                Some(err)
+            }
+        };
+        let history = CronHistory {
+            host: hostname,
+            tick,
+            start_time,
+            end_time: now_time().unwrap_or_default(),
+            error,
+        };
+        if let Err(err) = store.history_add(job_name, &history, history_size).await {
+            warn!(
+                "cron job {} add history fail:{}",
+                job_name,
+                err.to_fluent_message().default_format()
+            );
+        }
+    }
+    //计算需执行的时间点,并更新下次执行时间
+    async fn due_ticks(&self, now: u64) -> Vec<(Arc<dyn CronJob>, u64)> {
+        //新注册任务,获取最后执行时间用于补执行
+        let init_job = self
+            .jobs
+            .lock()
+            .iter()
+            .filter(|e| e.next_tick == CRON_TICK_INIT)
+            .map(|e| e.job.clone())
+            .collect::<Vec<_>>();
+        let mut last_tick = Vec::with_capacity(init_job.len());
+        for job in init_job {
+            let last = match self.store.last_tick(job.job_name()).await {
+                Ok(last) => last,
+                Err(err) => {
+                    warn!(
+                        "cron job {} get last tick fail:{}",
+                        job.job_name(),
+                        err.to_fluent_message().default_format()
+                    );
+                    None
+                }
+            };
+            last_tick.push((job.job_name().to_owned(), last));
+        }
+        let mut out = vec![];
+        for item in self.jobs.lock().iter_mut() {
+            if item.next_tick == CRON_TICK_INIT {
+                let last = last_tick
+                    .iter()
+                    .find(|e| e.0 == item.job.job_name())
+                    .and_then(|e| e.1);
+                if let Some(last) = last {
+                    let limit = item.missed.limit();
+                    if limit > 0 {
+                        for tick in item.expr.times_between(last, now, limit) {
+                            out.push((item.job.clone(), tick));
+                        }
+                    }
+                }
+            } else if item.next_tick <= now {
+                //执行被延迟时,多个时间点同样按错过处理
+                let limit = item.missed.limit().max(1);
+                for tick in item.expr.times_between(item.next_tick - 1, now, limit) {
+                    out.push((item.job.clone(), tick));
+                }
+            } else {
+                continue;
+            }
+            item.next_tick = item.expr.next_time(now).unwrap_or(CRON_TICK_NONE);
+        }
+        out
+    }
+    /// 执行定时任务,需在后台常驻运行
+    pub async fn dispatch(&self) {
+        loop {
+            let now = now_time().unwrap_or_default();
+            for (job, tick) in self.due_ticks(now).await {
+                tokio::spawn(Self::run_job(
+                    self.store.clone(),
+                    job,
+                    self.hostname.clone(),
+                    self.history_size,
+                    tick,
+                ));
+            }
+            //最长等待60秒,新增任务时立即唤醒
+            let wait = self
+                .jobs
+                .lock()
+                .iter()
+                .map(|e| e.next_tick.saturating_sub(now))
+                .min()
+                .unwrap_or(60)
+                .clamp(1, 60);
+            if tokio::time::timeout(Duration::from_secs(wait), self.notify.notified())
+                .await
+                .is_ok()
+            {
+                debug!("cron job add, recalculate next tick");
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_cron_task() {
+    struct TestJob(tokio::sync::mpsc::Sender<u64>);
+    #[async_trait]
+    impl CronJob for TestJob {
+        fn job_name(&self) -> &str {
+            "test-job"
+        }
+        async fn run(&self, tick: u64) -> Result<(), String> {
+            self.0.send(tick).await.map_err(|e| e.to_string())
+        }
+    }
+    let task = Arc::new(CronTask::new(Box::new(CronMemoryStore::new()), 10));
+    let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+    task.add("* * * * * *", CronMissedPolicy::Skip, TestJob(tx.clone()))
+        .unwrap();
+    assert!(task
+        .add("* * * * * *", CronMissedPolicy::Skip, TestJob(tx))
+        .is_err());
+    let dispatch = task.clone();
+    tokio::spawn(async move {
+        dispatch.dispatch().await;
+    });
+    let tick = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(tick > 0);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let history = task.history("test-job").await.unwrap();
+    assert_eq!(history[0].tick, tick);
+    assert!(history[0].error.is_none());
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use redis::{AsyncCommands, Script};
+use serde::{Deserialize, Serialize};
+
+use crate::TaskResult;
+
+//定时任务执行记录
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CronHistory {
+    //执行的HOST
+    pub host: String,
+    //计划执行时间点
+    pub tick: u64,
+    //开始执行时间
+    pub start_time: u64,
+    //执行完成时间
+    pub end_time: u64,
+    //执行失败时的错误信息
+    pub error: Option<String>,
+}
+
+// 定时任务的存储后端
+// 负责:每个执行时间点的锁定,最后执行时间点,执行记录
+#[async_trait]
+pub trait CronStore: Send + Sync {
+    /// 锁定定时任务的某个执行时间点,已被其他服务器锁定时返回false
+    /// * `timeout` - 锁定超时,单位秒
+    async fn tick_lock(&self, job_name: &str, tick: u64, timeout: usize) -> TaskResult<bool>;
+    /// 最后被锁定执行的时间点
+    async fn last_tick(&self, job_name: &str) -> TaskResult<Option<u64>>;
+    /// 设置最后执行的时间点,仅大于已记录的时间点时写入
+    async fn set_last_tick(&self, job_name: &str, tick: u64) -> TaskResult<()>;
+    /// 添加执行记录
+    /// * `keep` - 保留的记录数量
+    async fn history_add(
+        &self,
+        job_name: &str,
+        history: &CronHistory,
+        keep: usize,
+    ) -> TaskResult<()>;
+    /// 执行记录,最新在前
+    async fn history(&self, job_name: &str) -> TaskResult<Vec<CronHistory>>;
+}
+
+//仅新时间点大于已记录的时间点时写入,防止较慢的服务器回退最后执行时间点
+const SET_LAST_TICK_SCRIPT: &str = r#"
+local last = tonumber(redis.call('HGET', KEYS[1], ARGV[1]))
+if last == nil or last < tonumber(ARGV[2]) then
+    redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+    return 1
+end
+return 0
+"#;
+
+// 基于REDIS的定时任务存储,多台服务器时保证同一时间点仅执行一次
+pub struct CronRedisStore {
+    redis: deadpool_redis::Pool,
+    key_prefix: String,
+}
+
+impl CronRedisStore {
+    /// * `redis` - REDIS连接池
+    /// * `key_prefix` - REDIS KEY前缀
+    pub fn new(redis: deadpool_redis::Pool, key_prefix: String) -> Self {
+        Self { redis, key_prefix }
+    }
+}
+
+#[async_trait]
+impl CronStore for CronRedisStore {
+    async fn tick_lock(&self, job_name: &str, tick: u64, timeout: usize) -> TaskResult<bool> {
+        let mut redis = self.redis.get().await?;
+        let lock: Option<String> = redis::cmd("SET")
+            .arg(format!("{}-lock-{}-{}", self.key_prefix, job_name, tick))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(timeout)
+            .query_async(&mut redis)
+            .await?;
+        Ok(lock.is_some())
+    }
+    async fn last_tick(&self, job_name: &str) -> TaskResult<Option<u64>> {
+        let mut redis = self.redis.get().await?;
+        Ok(redis
+            .hget(format!("{}-last", self.key_prefix), job_name)
+            .await?)
+    }
+    async fn set_last_tick(&self, job_name: &str, tick: u64) -> TaskResult<()> {
+        let mut redis = self.redis.get().await?;
+        Script::new(SET_LAST_TICK_SCRIPT)
+            .key(format!("{}-last", self.key_prefix))
+            .arg(job_name)
+            .arg(tick)
+            .invoke_async::<_, ()>(&mut redis)
+            .await?;
+        Ok(())
+    }
+    async fn history_add(
+        &self,
+        job_name: &str,
+        history: &CronHistory,
+        keep: usize,
+    ) -> TaskResult<()> {
+        let key = format!("{}-history-{}", self.key_prefix, job_name);
+        let mut redis = self.redis.get().await?;
+        redis
+            .lpush::<_, _, ()>(&key, serde_json::to_string(history).unwrap_or_default())
+            .await?;
+        redis
+            .ltrim::<_, ()>(&key, 0, keep.max(1) as isize - 1)
+            .await?;
+        Ok(())
+    }
+    async fn history(&self, job_name: &str) -> TaskResult<Vec<CronHistory>> {
+        let mut redis = self.redis.get().await?;
+        let data: Vec<String> = redis
+            .lrange(format!("{}-history-{}", self.key_prefix, job_name), 0, -1)
+            .await?;
+        Ok(data
+            .iter()
+            .filter_map(|e| serde_json::from_str::<CronHistory>(e).ok())
+            .collect())
+    }
+}
+
+// 基于进程内存的定时任务存储
+// 仅适用于单机部署或测试,重启后执行记录丢失
+#[derive(Default)]
+pub struct CronMemoryStore {
+    last_tick: Mutex<HashMap<String, u64>>,
+    history: Mutex<HashMap<String, Vec<CronHistory>>>,
+}
+
+impl CronMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CronStore for CronMemoryStore {
+    async fn tick_lock(&self, job_name: &str, tick: u64, _timeout: usize) -> TaskResult<bool> {
+        //单进程内时间点递增,已执行过的时间点不再锁定
+        let mut last = self.last_tick.lock();
+        let item = last.entry(job_name.to_owned()).or_default();
+        if *item >= tick {
+            return Ok(false);
+        }
+        *item = tick;
+        Ok(true)
+    }
+    async fn last_tick(&self, job_name: &str) -> TaskResult<Option<u64>> {
+        Ok(self.last_tick.lock().get(job_name).copied())
+    }
+    async fn set_last_tick(&self, job_name: &str, tick: u64) -> TaskResult<()> {
+        let mut last = self.last_tick.lock();
+        let item = last.entry(job_name.to_owned()).or_default();
+        if *item < tick {
+            *item = tick;
+        }
+        Ok(())
+    }
+    async fn history_add(
+        &self,
+        job_name: &str,
+        history: &CronHistory,
+        keep: usize,
+    ) -> TaskResult<()> {
+        let mut data = self.history.lock();
+        let item = data.entry(job_name.to_owned()).or_default();
+        item.insert(0, history.to_owned());
+        item.truncate(keep.max(1));
+        Ok(())
+    }
+    async fn history(&self, job_name: &str) -> TaskResult<Vec<CronHistory>> {
+        Ok(self
+            .history
+            .lock()
+            .get(job_name)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
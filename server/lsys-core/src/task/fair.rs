@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use parking_lot::{Mutex, RwLock};
+
+// 任务的优先级及分组信息,用于公平选取任务
+pub trait TaskFairItem {
+    /// 优先级,数值越大越优先执行
+    fn task_priority(&self) -> i8;
+    /// 任务分组,如应用ID
+    /// 同一优先级下各分组按权重轮流选取
+    fn task_group(&self) -> u64;
+}
+
+// 任务公平选取
+// 按优先级分道,高优先级任务全部选完后才选取低优先级任务
+// 同一优先级内按分组加权轮询,避免某个分组的大量任务阻塞其他分组
+pub struct TaskFair {
+    default_weight: usize,
+    weight: RwLock<HashMap<u64, usize>>,
+    //各优先级最后选取的分组,下次从其后的分组开始选取
+    last_group: Mutex<HashMap<i8, u64>>,
+}
+
+impl Default for TaskFair {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl TaskFair {
+    /// * `default_weight` - 未单独设置权重的分组每轮选取的任务数量
+    pub fn new(default_weight: usize) -> Self {
+        Self {
+            default_weight: default_weight.max(1),
+            weight: RwLock::new(HashMap::new()),
+            last_group: Mutex::new(HashMap::new()),
+        }
+    }
+    /// 设置分组权重,即每轮选取的任务数量
+    /// * `weight` - 为0时恢复为默认权重
+    pub fn set_weight(&self, group: u64, weight: usize) {
+        let mut data = self.weight.write();
+        if weight == 0 {
+            data.remove(&group);
+        } else {
+            data.insert(group, weight);
+        }
+    }
+    /// 分组权重
+    pub fn weight(&self, group: u64) -> usize {
+        self.weight
+            .read()
+            .get(&group)
+            .copied()
+            .unwrap_or(self.default_weight)
+    }
+    /// 从候选任务中选取需执行的任务
+    /// * `items` - 候选任务,同一分组内的任务按执行先后排列
+    /// * `limit` - 最大选取数量
+    pub fn select<T: TaskFairItem>(&self, items: Vec<T>, limit: usize) -> Vec<T> {
+        let mut lanes: BTreeMap<i8, BTreeMap<u64, VecDeque<T>>> = BTreeMap::new();
+        for item in items {
+            lanes
+                .entry(item.task_priority())
+                .or_default()
+                .entry(item.task_group())
+                .or_default()
+                .push_back(item);
+        }
+        let mut out = Vec::with_capacity(limit);
+        let mut last_group = self.last_group.lock();
+        for (priority, lane) in lanes.into_iter().rev() {
+            if out.len() >= limit {
+                break;
+            }
+            //从上次最后选取分组之后开始轮询
+            let start = last_group
+                .get(&priority)
+                .map(|last| lane.keys().filter(|e| *e <= last).count())
+                .unwrap_or(0);
+            let mut groups = lane
+                .into_iter()
+                .map(|(group, items)| (group, self.weight(group), items))
+                .collect::<Vec<_>>();
+            let len = groups.len();
+            groups.rotate_left(start % len);
+            while out.len() < limit && groups.iter().any(|e| !e.2.is_empty()) {
+                for (group, weight, items) in groups.iter_mut() {
+                    if items.is_empty() {
+                        continue;
+                    }
+                    let size = (*weight).min(items.len()).min(limit - out.len());
+                    out.extend(items.drain(..size));
+                    last_group.insert(priority, *group);
+                    if out.len() >= limit {
+                        break;
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+#[test]
+fn test_task_fair() {
+    struct Item(i8, u64, u32);
+    impl TaskFairItem for Item {
+        fn task_priority(&self) -> i8 {
+            self.0
+        }
+        fn task_group(&self) -> u64 {
+            self.1
+        }
+    }
+    let items = || {
+        let mut items = (0..10).map(|i| Item(1, 1, i)).collect::<Vec<_>>();
+        items.push(Item(1, 2, 100));
+        items.push(Item(1, 3, 200));
+        items.push(Item(9, 1, 300));
+        items
+    };
+    let fair = TaskFair::default();
+    let res = fair
+        .select(items(), 4)
+        .iter()
+        .map(|e| e.2)
+        .collect::<Vec<_>>();
+    assert_eq!(res, vec![300, 0, 100, 200]);
+    //下一次从分组3之后开始
+    let res = fair
+        .select(items(), 3)
+        .iter()
+        .map(|e| e.2)
+        .collect::<Vec<_>>();
+    assert_eq!(res, vec![300, 0, 100]);
+    fair.set_weight(1, 3);
+    let res = fair
+        .select(items(), 6)
+        .iter()
+        .map(|e| e.2)
+        .collect::<Vec<_>>();
+    assert_eq!(res, vec![300, 200, 0, 1, 2, 100]);
+}
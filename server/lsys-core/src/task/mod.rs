@@ -1,2 +1,12 @@
+mod backend;
+mod cron;
+mod fair;
+mod monitor;
+mod result;
 mod task_executor;
+pub use backend::*;
+pub use cron::*;
+pub use fair::*;
+pub use monitor::*;
+pub use result::*;
 pub use task_executor::*;
@@ -0,0 +1,259 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::{FromRedisValue, ToRedisArgs};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use super::{TaskDispatch, TaskError, TaskItem, TaskResult};
+use crate::{fluent_message, now_time, LocalExecType, MsgSendBody, RemoteNotify, RemoteTask};
+
+pub const REMOTE_NOTIFY_TYPE_TASK: u8 = 103;
+
+//执行中任务信息
+#[derive(Serialize, Clone, Debug)]
+pub struct TaskRunRecord {
+    //任务ID
+    pub pk: String,
+    //执行任务的HOST
+    pub host: String,
+    //开始执行时间
+    pub time: u64,
+    //已执行时间,单位秒
+    pub age: u64,
+    //是否已超过检测时间,超过后将被重新执行
+    pub is_timeout: bool,
+}
+
+//任务派发运行状态
+//执行中任务为所有服务器数据,其他统计为当前服务器数据
+#[derive(Serialize, Clone, Debug)]
+pub struct TaskSnapshot {
+    pub task_name: String,
+    //统计数据所在HOST
+    pub hostname: String,
+    pub is_check: bool,
+    pub check_timeout: usize,
+    pub task_timeout: usize,
+    //同时执行任务数量
+    pub task_size: usize,
+    //当前服务器执行中任务数量
+    pub task_busy: usize,
+    //最后一次读取任务时间
+    pub last_acquisition_time: u64,
+    //读取任务失败次数
+    pub acquisition_error: u64,
+    //任务执行成功次数
+    pub exec_success: u64,
+    //任务执行失败次数
+    pub exec_error: u64,
+    //所有服务器执行中任务
+    pub run_task: Vec<TaskRunRecord>,
+}
+
+// 可被监控的任务派发
+#[async_trait]
+pub trait TaskMonitorItem: Send + Sync {
+    fn task_name(&self) -> &str;
+    /// 运行状态
+    async fn snapshot(&self) -> TaskResult<TaskSnapshot>;
+    /// 删除执行中任务记录,删除后该任务可被重新获取执行
+    async fn task_release(&self, pk: &str) -> TaskResult<()>;
+    /// 中止当前服务器上指定任务的执行
+    /// @return 当前服务器是否存在该任务
+    fn task_abort(&self, pk: &str) -> bool;
+    /// 停止任务派发,见 TaskDispatch::shutdown
+    async fn task_shutdown(&self, timeout: Duration);
+}
+
+#[async_trait]
+impl<
+        I: FromRedisValue
+            + ToRedisArgs
+            + Eq
+            + Hash
+            + Send
+            + Sync
+            + Display
+            + Clone
+            + FromStr
+            + 'static,
+        T: TaskItem<I> + Sync + 'static,
+    > TaskMonitorItem for TaskDispatch<I, T>
+{
+    fn task_name(&self) -> &str {
+        self.backend.task_name()
+    }
+    async fn snapshot(&self) -> TaskResult<TaskSnapshot> {
+        let now = now_time().unwrap_or_default();
+        let mut run_task = self
+            .backend
+            .task_data()
+            .await?
+            .into_iter()
+            .map(|(pk, data)| TaskRunRecord {
+                pk: pk.to_string(),
+                host: data.host,
+                time: data.time,
+                age: now.saturating_sub(data.time),
+                is_timeout: data.time + (self.check_timeout as u64) < now,
+            })
+            .collect::<Vec<_>>();
+        run_task.sort_by_key(|a| a.time);
+        Ok(TaskSnapshot {
+            task_name: self.backend.task_name().to_owned(),
+            hostname: hostname::get()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            is_check: self.is_check,
+            check_timeout: self.check_timeout,
+            task_timeout: self.task_timeout,
+            task_size: self.task_size,
+            task_busy: self
+                .state
+                .task_ing
+                .lock()
+                .iter()
+                .filter(|(_, abt)| !abt.is_finished())
+                .count(),
+            last_acquisition_time: self.state.last_acquisition.load(Ordering::Relaxed),
+            acquisition_error: self.state.acquisition_error.load(Ordering::Relaxed),
+            exec_success: self.state.exec_success.load(Ordering::Relaxed),
+            exec_error: self.state.exec_error.load(Ordering::Relaxed),
+            run_task,
+        })
+    }
+    async fn task_release(&self, pk: &str) -> TaskResult<()> {
+        let pk = pk.parse::<I>().map_err(|_| {
+            TaskError::System(fluent_message!("task-bad-pk",{
+                "task":self.backend.task_name(),
+                "pk":pk
+            }))
+        })?;
+        self.backend.task_del(&pk).await
+    }
+    fn task_abort(&self, pk: &str) -> bool {
+        let mut find = false;
+        for (tpk, abt) in self.state.task_ing.lock().iter() {
+            if tpk.to_string() == pk && !abt.is_finished() {
+                //中止后由派发循环清理并释放执行数量
+                abt.abort();
+                find = true;
+            }
+        }
+        find
+    }
+    async fn task_shutdown(&self, timeout: Duration) {
+        self.shutdown(timeout).await
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TaskAbortMessage {
+    task_name: String,
+    pk: String,
+}
+
+// 任务派发监控
+pub struct TaskMonitor {
+    remote_notify: Arc<RemoteNotify>,
+    task_list: Vec<Arc<dyn TaskMonitorItem>>,
+}
+
+impl TaskMonitor {
+    pub fn new(remote_notify: Arc<RemoteNotify>, task_list: Vec<Arc<dyn TaskMonitorItem>>) -> Self {
+        Self {
+            remote_notify,
+            task_list,
+        }
+    }
+    /// 远程通知处理,需添加到 RemoteNotify 中
+    pub fn remote_task(&self) -> TaskMonitorRemote {
+        TaskMonitorRemote {
+            task_list: self.task_list.clone(),
+        }
+    }
+    /// 所有任务的运行状态
+    pub async fn snapshot(&self) -> TaskResult<Vec<TaskSnapshot>> {
+        let mut out = Vec::with_capacity(self.task_list.len());
+        for task in self.task_list.iter() {
+            out.push(task.snapshot().await?);
+        }
+        Ok(out)
+    }
+    /// 停止所有任务派发,服务退出前调用
+    /// * `timeout` - 等待执行中任务完成的最长时间
+    pub async fn shutdown(&self, timeout: Duration) {
+        futures_util::future::join_all(
+            self.task_list
+                .iter()
+                .map(|task| task.task_shutdown(timeout)),
+        )
+        .await;
+    }
+    /// 强制释放执行中任务
+    /// 删除执行中记录并通知所有服务器中止该任务
+    pub async fn task_release(&self, task_name: &str, pk: &str) -> TaskResult<()> {
+        let task = self
+            .task_list
+            .iter()
+            .find(|e| e.task_name() == task_name)
+            .ok_or_else(|| {
+                TaskError::System(fluent_message!("task-not-find", { "task": task_name }))
+            })?;
+        task.task_release(pk).await?;
+        info!("release task {}:{}", task_name, pk);
+        self.remote_notify
+            .call(
+                REMOTE_NOTIFY_TYPE_TASK,
+                TaskAbortMessage {
+                    task_name: task_name.to_owned(),
+                    pk: pk.to_owned(),
+                },
+                None,
+                LocalExecType::RemoteExec,
+                None,
+            )
+            .await
+            .map_err(TaskError::RemoteNotify)?;
+        Ok(())
+    }
+}
+
+/// 订阅远程通知中止本机任务
+pub struct TaskMonitorRemote {
+    task_list: Vec<Arc<dyn TaskMonitorItem>>,
+}
+
+#[async_trait]
+impl RemoteTask for TaskMonitorRemote {
+    fn msg_type(&self) -> u8 {
+        REMOTE_NOTIFY_TYPE_TASK
+    }
+    async fn run(&self, msg: MsgSendBody) -> Result<Option<Value>, String> {
+        let msg =
+            serde_json::from_value::<TaskAbortMessage>(msg.data).map_err(|e| e.to_string())?;
+        match self
+            .task_list
+            .iter()
+            .find(|e| e.task_name() == msg.task_name)
+        {
+            Some(task) => {
+                if task.task_abort(&msg.pk) {
+                    info!("abort task {}:{}", msg.task_name, msg.pk);
+                }
+            }
+            None => {
+                warn!("not find abort task name:{}", msg.task_name);
+            }
+        }
+        Ok(None)
+    }
+}
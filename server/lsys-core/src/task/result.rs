@@ -0,0 +1,36 @@
+use deadpool_redis::PoolError;
+use redis::RedisError;
+
+use crate::{fluent_message, FluentMessage, IntoFluentMessage, RemoteNotifyError};
+
+#[derive(Debug)]
+pub enum TaskError {
+    System(FluentMessage),
+    Redis(RedisError),
+    RedisPool(PoolError),
+    RemoteNotify(RemoteNotifyError),
+}
+
+impl IntoFluentMessage for TaskError {
+    fn to_fluent_message(&self) -> FluentMessage {
+        match self {
+            TaskError::System(err) => err.to_owned(),
+            TaskError::Redis(err) => fluent_message!("redis-error", err),
+            TaskError::RedisPool(err) => fluent_message!("redis-error", err),
+            TaskError::RemoteNotify(err) => err.to_fluent_message(),
+        }
+    }
+}
+
+impl From<RedisError> for TaskError {
+    fn from(err: RedisError) -> Self {
+        TaskError::Redis(err)
+    }
+}
+impl From<PoolError> for TaskError {
+    fn from(err: PoolError) -> Self {
+        TaskError::RedisPool(err)
+    }
+}
+
+pub type TaskResult<T> = Result<T, TaskError>;
@@ -1,24 +1,22 @@
 use async_trait::async_trait;
 
-use redis::aio::Connection;
-use redis::{
-    AsyncCommands, ErrorKind, FromRedisValue, RedisError, RedisResult, ToRedisArgs, Value,
-};
+use redis::{FromRedisValue, ToRedisArgs};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::hash::Hash;
 use std::marker::PhantomData;
-use std::str::from_utf8;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::error::TryRecvError;
-use tokio::sync::Mutex;
+use tokio::sync::{watch, Notify};
 use tokio::task::{AbortHandle, JoinSet};
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
-use crate::{now_time, AppCore, IntoFluentMessage};
+use super::{TaskBackend, TaskResult};
+use crate::{now_time, IntoFluentMessage, TraceContext};
 
 //最外层的发送任务派发封装
 //不包含具体的发送逻辑
@@ -31,43 +29,6 @@ pub struct TaskData {
     //执行发送任务时间
     pub time: u64,
 }
-impl FromRedisValue for TaskData {
-    fn from_redis_value(val: &Value) -> RedisResult<Self> {
-        let valstr = match *val {
-            Value::Data(ref bytes) => from_utf8(bytes)?.to_string(),
-            _ => {
-                return Err(RedisError::from((
-                    ErrorKind::TypeError,
-                    "Response was of incompatible type",
-                    format!(
-                        "Response type not string compatible. (response was {:?})",
-                        val
-                    ),
-                )))
-            }
-        };
-        match serde_json::from_str::<TaskData>(&valstr) {
-            Ok(data) => Ok(data),
-            Err(err) => Err(RedisError::from((
-                ErrorKind::TypeError,
-                "Response was of incompatible type",
-                format!(
-                    "Response type parse error:{}. (response was {:?})",
-                    err, val
-                ),
-            ))),
-        }
-    }
-}
-impl ToRedisArgs for TaskData {
-    fn write_redis_args<W>(&self, out: &mut W)
-    where
-        W: ?Sized + redis::RedisWrite,
-    {
-        out.write_arg(serde_json::to_string(self).unwrap_or_default().as_bytes())
-    }
-}
-
 // 任务TRAIT
 pub trait TaskItem<I: FromRedisValue + ToRedisArgs + Eq + Hash + Send + Sync + Display>:
     Send
@@ -82,6 +43,10 @@ pub trait TaskItem<I: FromRedisValue + ToRedisArgs + Eq + Hash + Send + Sync + D
             time: now_time().unwrap_or_default(),
         }
     }
+    /// 创建任务的请求追踪上下文,执行任务时日志包含该上下文的 request_id
+    fn to_trace_context(&self) -> TraceContext {
+        TraceContext::default()
+    }
 }
 
 // 发送执行
@@ -136,19 +101,52 @@ pub trait TaskAcquisition<
     ) -> Result<TaskRecord<I, T>, String>;
 }
 
+// 任务派发运行状态,仅当前服务器
+pub(crate) struct TaskDispatchState<I> {
+    //最后一次读取任务时间
+    pub(crate) last_acquisition: AtomicU64,
+    //读取任务失败次数
+    pub(crate) acquisition_error: AtomicU64,
+    //任务执行成功次数
+    pub(crate) exec_success: AtomicU64,
+    //任务执行失败次数
+    pub(crate) exec_error: AtomicU64,
+    //当前服务器执行中任务,任务数据跟任务处理关联数组
+    pub(crate) task_ing: parking_lot::Mutex<Vec<(I, AbortHandle)>>,
+    //是否已停止派发
+    is_stop: AtomicBool,
+    //停止派发通知
+    stop_notify: Notify,
+    //派发是否运行中
+    running: watch::Sender<bool>,
+}
+
+impl<I> TaskDispatchState<I> {
+    fn new() -> Self {
+        Self {
+            last_acquisition: AtomicU64::new(0),
+            acquisition_error: AtomicU64::new(0),
+            exec_success: AtomicU64::new(0),
+            exec_error: AtomicU64::new(0),
+            task_ing: parking_lot::Mutex::new(vec![]),
+            is_stop: AtomicBool::new(false),
+            stop_notify: Notify::new(),
+            running: watch::channel(false).0,
+        }
+    }
+}
+
 // 发送任务抽象实现
 pub struct TaskDispatch<
     I: FromRedisValue + ToRedisArgs + Eq + Hash + Send + Sync + Display + Clone,
     T: TaskItem<I>,
 > {
-    //任务触发监听的REDIS KEY
-    list_notify: String,
-    //任务读取锁定Redis KEY
-    read_lock_key: String,
+    //任务通知,锁定,执行中任务存储后端
+    pub(crate) backend: Arc<dyn TaskBackend<I>>,
+    //运行状态
+    pub(crate) state: Arc<TaskDispatchState<I>>,
     //任务读取锁定超时,大于等于check_timeout ,task_timeout
     read_lock_timeout: usize,
-    //存放执行中任务的REDIS key
-    task_list_key: String,
     //是否定时检测遗漏发送任务
     pub is_check: bool,
     //定时检测遗漏发送任务时间
@@ -159,7 +157,6 @@ pub struct TaskDispatch<
     pub task_size: usize,
     //每次获取记录数量,等于 同时执行任务数量
     pub read_size: usize,
-    marker_i: PhantomData<I>,
     marker_t: PhantomData<T>,
 }
 
@@ -168,18 +165,13 @@ impl<
         T: TaskItem<I>,
     > TaskDispatch<I, T>
 {
-    /// * `list_notify` - 任务触发监听的REDIS KEY
-    /// * `read_lock_key` - 任务读取锁定Redis KEY
-    /// * `task_list_key` - 存放执行中任务的REDIS key
+    /// * `backend` - 任务存储后端,见 TaskBackendConfig
     /// * `task_size` - 同时发送任务数量,默认等于CPU数量2倍
     /// * `task_timeout` - 任务最大执行时间
     /// * `is_check` - 是否定时检测遗漏发送任务
     /// * `check_timeout` - 当使用任务检测时的时间间隔，大于等于任务最大执行时间
-    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        list_notify: String,
-        read_lock_key: String,
-        task_list_key: String,
+        backend: Box<dyn TaskBackend<I>>,
         task_size: Option<usize>,
         task_timeout: usize,
         is_check: bool,
@@ -198,75 +190,120 @@ impl<
         };
         let read_lock_timeout = check_timeout;
         Self {
-            list_notify,
-            read_lock_key,
+            backend: Arc::from(backend),
+            state: Arc::new(TaskDispatchState::new()),
             read_lock_timeout,
-            task_list_key,
             is_check,
             check_timeout,
             task_timeout,
             task_size,
             read_size: task_size,
-            marker_i: PhantomData,
             marker_t: PhantomData,
         }
     }
 }
 
 impl<
-        I: FromRedisValue + ToRedisArgs + Eq + Hash + Send + Sync + Display + Clone,
+        I: FromRedisValue + ToRedisArgs + Eq + Hash + Send + Sync + Display + Clone + 'static,
         T: TaskItem<I> + 'static, // 实在不想细细折腾，直接 'static ，毕竟T也没打算带用带引用
     > TaskDispatch<I, T>
 {
     /// 通知发送模块进行发送操作
-    /// * `redis` - 存放发送任务的RDIS
-    pub async fn notify(&self, redis: &mut Connection) -> Result<(), RedisError> {
-        redis.lpush(&self.list_notify, 1).await
+    pub async fn notify(&self) -> TaskResult<()> {
+        self.backend.notify().await
     }
     /// 获得发送中任务信息
-    /// * `redis` - 存放发送任务的RDIS
-    pub async fn task_data(
-        &self,
-        redis: &mut Connection,
-    ) -> Result<HashMap<I, TaskData>, RedisError> {
-        let redis_data_opt: Result<Option<HashMap<I, TaskData>>, _> =
-            redis.hgetall(&self.task_list_key).await;
-        redis_data_opt.map(|data| data.unwrap_or_default())
+    pub async fn task_data(&self) -> TaskResult<HashMap<I, TaskData>> {
+        self.backend.task_data().await
     }
     // 任务执行
     #[allow(clippy::too_many_arguments)]
     async fn run_task<E: TaskExecutor<I, T> + 'static>(
         task_set: &mut JoinSet<()>,
-        task_ing: &mut Vec<(I, AbortHandle)>,
+        task_backend: &Arc<dyn TaskBackend<I>>,
+        task_state: &Arc<TaskDispatchState<I>>,
         v: T,
-        task_list_key: String,
+        task_name: String,
         task_executor: E,
         run_size: &mut usize,
     ) {
+        if task_state.is_stop.load(Ordering::Relaxed) {
+            //停止中,不再执行新任务,释放执行中记录以便其他服务器执行
+            let pk = v.to_task_pk();
+            if let Err(err) = task_backend.task_del(&pk).await {
+                warn!(
+                    "release task:{} fail[{}]:{}",
+                    task_name,
+                    pk,
+                    err.to_fluent_message().default_format()
+                );
+            }
+            return;
+        }
         //任务大小减一
         //把run_size 放到这里减,方便后期扩展,如启动任务失败时可不加
         *run_size -= 1;
         let pk = v.to_task_pk();
-        debug!("add async task start [{}]:{}", task_list_key, pk);
+        debug!("add async task start [{}]:{}", task_name, pk);
         //并行发送任务
-        let abort = task_set.spawn(async move {
+        let exec_state = task_state.clone();
+        let trace = v.to_trace_context();
+        let trace_name = task_name.clone();
+        let abort = task_set.spawn(trace.scope(&trace_name, async move {
             let pk = v.to_task_pk();
-            debug!("async task start [{}]:{}", task_list_key, pk);
+            debug!("async task start [{}]:{}", task_name, pk);
             if let Err(err) = task_executor.exec(v).await {
+                exec_state.exec_error.fetch_add(1, Ordering::Relaxed);
                 warn!("async task exec fail :{}", err);
+            } else {
+                exec_state.exec_success.fetch_add(1, Ordering::Relaxed);
             }
-            debug!("async task end [{}]:{}", task_list_key, pk);
-        });
+            debug!("async task end [{}]:{}", task_name, pk);
+        }));
         debug!("add async task end :{}", pk);
-        task_ing.push((pk, abort));
+        task_state.task_ing.lock().push((pk, abort));
+    }
+    // 清理已完成任务的执行中记录
+    // @return 已完成任务数量
+    async fn clear_finish_task(
+        backend: &Arc<dyn TaskBackend<I>>,
+        task_state: &Arc<TaskDispatchState<I>>,
+    ) -> usize {
+        //查找已完成任务列表
+        let finsih_pk = {
+            let mut task_ing = task_state.task_ing.lock();
+            let mut finsih_pk = Vec::with_capacity(task_ing.len());
+            task_ing.retain(|(pk, abt)| {
+                if abt.is_finished() {
+                    finsih_pk.push(pk.to_owned());
+                    false
+                } else {
+                    true
+                }
+            });
+            finsih_pk
+        };
+        //存在处理完任务,删除执行中记录
+        for pk in finsih_pk.iter() {
+            match backend.task_del(pk).await {
+                Ok(()) => {
+                    debug!("clear runing task:{}", pk);
+                }
+                Err(err) => {
+                    warn!(
+                        "clear runing task fail:{}",
+                        err.to_fluent_message().default_format()
+                    );
+                }
+            }
+        }
+        finsih_pk.len()
     }
     /// 获得发送中任务信息
-    /// * `app_core` - 公共APP句柄,用于创建REDIS
     /// * `task_reader` - 任务读取实现
     /// * `task_executor` - 任务发送实现
     pub async fn dispatch<R: TaskAcquisition<I, T>, E: TaskExecutor<I, T> + 'static>(
         &self,
-        app_core: Arc<AppCore>,
         task_reader: &R,
         task_executor: E,
     ) {
@@ -276,43 +313,22 @@ impl<
         }
         let (channel_sender, mut channel_receiver) =
             tokio::sync::mpsc::channel::<T>(self.task_size);
-        let task_list_key = self.task_list_key.clone();
-        let redis_client = match app_core.create_redis_client() {
-            Ok(redis_client) => redis_client,
-            Err(err) => {
-                error!(
-                    "create redis fail:{}",
-                    err.to_fluent_message().default_format()
-                );
-                return;
-            }
-        };
-        let task_redis_client = redis_client.clone();
+        let task_name = self.backend.task_name().to_owned();
+        let task_backend = self.backend.clone();
+        let task_state = self.state.clone();
         let max_size = self.task_size;
-        debug!(
-            "Concurrent send max[{}]:{} task",
-            task_list_key, self.task_size
-        );
+        debug!("Concurrent send max[{}]:{} task", task_name, self.task_size);
         //从channel 中拿数据并发送
-        tokio::spawn(async move {
-            //连接REDIS
-            let conn = loop {
-                match task_redis_client.get_tokio_connection_manager().await {
-                    Ok(conn) => break conn,
-                    Err(err) => {
-                        warn!("task redis get fail :{}", err);
-                        sleep(Duration::from_secs(1)).await;
-                    }
-                }
-            };
-            let redis_conn = Arc::new(Mutex::new(conn));
+        let run_task_name = task_name.clone();
+        self.state.running.send_replace(true);
+        let run_handle = tokio::spawn(async move {
+            let task_name = run_task_name;
             let mut run_size = max_size;
             let mut task_empty;
-            let mut task_set = JoinSet::new(); //进行中任务,没法将任务数据在这关联,所以用 task_ing 关联
-            let mut task_ing = vec![]; //任务数据跟任务处理关联数组
+            let mut task_set = JoinSet::new(); //进行中任务,没法将任务数据在这关联,所以用 task_state.task_ing 关联
 
             'task_main: loop {
-                debug!("start send task:{}", task_list_key);
+                debug!("start send task:{}", task_name);
 
                 //从channel 获取任务,不阻塞
                 task_empty = match channel_receiver.try_recv() {
@@ -320,9 +336,10 @@ impl<
                         //获取到任务,执行任务
                         Self::run_task(
                             &mut task_set,
-                            &mut task_ing,
+                            &task_backend,
+                            &task_state,
                             v,
-                            task_list_key.clone(),
+                            task_name.clone(),
                             task_executor.clone(),
                             &mut run_size,
                         )
@@ -330,6 +347,21 @@ impl<
                         false
                     }
                     Err(err) => {
+                        if err == TryRecvError::Disconnected
+                            && task_state.is_stop.load(Ordering::Relaxed)
+                        {
+                            //已停止派发,等待执行中任务完成后退出
+                            info!("task:{} stop,wait runing task", task_name);
+                            while let Some(res) = task_set.join_next().await {
+                                if let Err(err) = res {
+                                    if !err.is_cancelled() {
+                                        error!("task error[stop]:{:?}", err);
+                                    }
+                                }
+                            }
+                            Self::clear_finish_task(&task_backend, &task_state).await;
+                            break 'task_main;
+                        }
                         if err != TryRecvError::Empty {
                             error!("task channel error:{}", err);
                             sleep(Duration::from_secs(1)).await;
@@ -342,36 +374,8 @@ impl<
                 };
                 //未获取到任务,且还有闲置发送
                 if task_empty && run_size > 0 {
-                    //查找已完成任务列表
-                    let mut finsih_pk = Vec::with_capacity(max_size);
-                    task_ing = task_ing
-                        .into_iter()
-                        .filter(|(pk, abt)| {
-                            if abt.is_finished() {
-                                finsih_pk.push(pk.to_owned());
-                                false
-                            } else {
-                                true
-                            }
-                        })
-                        .collect::<Vec<(I, AbortHandle)>>();
                     //未查找到已完成任务,可能上一次已处理完.重新进入等待
-                    if !finsih_pk.is_empty() {
-                        //存在处理完任务,进行REDIS解锁及增加空闲任务数量
-                        //REDIS一次性全部解锁完在释放,省的多次获取锁
-                        let mut redis = redis_conn.lock().await;
-                        for pk in finsih_pk {
-                            run_size += 1;
-                            match redis.hdel(&task_list_key, &pk).await {
-                                Ok(()) => {
-                                    debug!("clear runing task:{}", pk);
-                                }
-                                Err(err) => {
-                                    warn!("clear runing task fail:{}", err);
-                                }
-                            }
-                        }
-                    }
+                    run_size += Self::clear_finish_task(&task_backend, &task_state).await;
 
                     'recv: loop {
                         if task_set.is_empty() {
@@ -380,9 +384,10 @@ impl<
                                 Some(v) => {
                                     Self::run_task(
                                         &mut task_set,
-                                        &mut task_ing,
+                                        &task_backend,
+                                        &task_state,
                                         v,
-                                        task_list_key.clone(),
+                                        task_name.clone(),
                                         task_executor.clone(),
                                         &mut run_size,
                                     )
@@ -403,10 +408,10 @@ impl<
                                         Some(v) => {
                                             Self::run_task(
                                                 &mut task_set,
-                                                &mut task_ing,
+                                                &task_backend,
+                                                &task_state,
                                                 v,
-
-                                                task_list_key.clone(),
+                                                task_name.clone(),
                                                 task_executor.clone(),
                                                 &mut run_size,
                                             )
@@ -426,36 +431,7 @@ impl<
                                             //有任务PANIC了,非稳定版没法捕捉到任务ID,等TOKIO升级后在修改...
                                             error!("task error[select]:{:?}", err);
                                         }
-                                        //查找已完成任务列表
-                                        let mut finsih_pk = Vec::with_capacity(max_size);
-                                        task_ing = task_ing
-                                            .into_iter()
-                                            .filter(|(pk, abt)| {
-                                                if abt.is_finished() {
-                                                    finsih_pk.push(pk.to_owned());
-                                                    false
-                                                } else {
-                                                    true
-                                                }
-                                            })
-                                            .collect::<Vec<(I, AbortHandle)>>();
-                                        //未查找到已完成任务,可能上一次已处理完.重新进入等待
-                                        if !finsih_pk.is_empty() {
-                                            //存在处理完任务,进行REDIS解锁及增加空闲任务数量
-                                            //REDIS一次性全部解锁完在释放,省的多次获取锁
-                                            let mut redis = redis_conn.lock().await;
-                                            for pk in finsih_pk {
-                                                run_size += 1;
-                                                match redis.hdel(&task_list_key, &pk).await {
-                                                    Ok(()) => {
-                                                        debug!("clear runing task[select]:{}", pk);
-                                                    }
-                                                    Err(err) => {
-                                                        warn!("clear runing task fail[select]:{}", err);
-                                                    }
-                                                }
-                                            }
-                                        }
+                                        run_size += Self::clear_finish_task(&task_backend, &task_state).await;
                                     }else{
                                         warn!("[task] select task set is empty");//理论上,永远不会进入这里
                                     }
@@ -472,224 +448,226 @@ impl<
                             //有任务PANIC了,非稳定版没法捕捉到任务ID,等TOKIO升级后在修改...
                             error!("task error:{:?}", err);
                         }
-                        //查找已完成任务列表
-                        let mut finsih_pk = Vec::with_capacity(max_size);
-                        task_ing = task_ing
-                            .into_iter()
-                            .filter(|(pk, abt)| {
-                                if abt.is_finished() {
-                                    finsih_pk.push(pk.to_owned());
-                                    false
-                                } else {
-                                    true
-                                }
-                            })
-                            .collect::<Vec<(I, AbortHandle)>>();
+                        let finish_size = Self::clear_finish_task(&task_backend, &task_state).await;
                         //未查找到已完成任务,可能上一次已处理完.重新进入等待
-                        if finsih_pk.is_empty() {
+                        if finish_size == 0 {
                             continue;
                         }
-                        //存在处理完任务,进行REDIS解锁及增加空闲任务数量
-                        //REDIS一次性全部解锁完在释放,省的多次获取锁
-                        let mut redis = redis_conn.lock().await;
-                        for pk in finsih_pk {
-                            run_size += 1;
-                            match redis.hdel(&task_list_key, &pk).await {
-                                Ok(()) => {
-                                    debug!("clear runing task:{}", pk);
-                                }
-                                Err(err) => {
-                                    warn!("clear runing task fail:{}", err);
-                                }
-                            }
-                        }
+                        run_size += finish_size;
                         break;
                         //退出任务完成检测,进入任务处理流程
                     }
                 }
             }
         });
-        debug!("connect redis {}", self.task_list_key);
         loop {
-            debug!("listen task:{}", self.task_list_key);
-            // redis listen or timeout{
-            // redis clean bad add log
-            // redis lock bad go to listen
-            // redis get task record bad del redis lock and go to listen
-            // self.read_task.get_record(task_ing...) bad del redis lock and go to listen
-            // redis set task record and del redis lock bad go to listen
+            if self.state.is_stop.load(Ordering::Relaxed) {
+                break;
+            }
+            debug!("listen task:{}", task_name);
+            // notify listen or timeout{
+            // lock bad go to listen
+            // get task record bad del lock and go to listen
+            // self.read_task.get_record(task_ing...) bad del lock and go to listen
+            // set task record and del lock bad go to listen
             // next true self.notify() bad add log
             // add record data to self.task_channel_sender
-            match redis_client.get_async_connection().await {
-                Ok(mut redis) => {
-                    let block: Result<Option<()>, _> =
-                        redis.blpop(&self.list_notify, self.check_timeout).await;
-                    match block {
-                        Ok(a) => {
-                            if a.is_none() {
-                                if !self.is_check {
-                                    continue;
-                                }
-                                info!("timeout check task:{}", self.task_list_key);
-                            } else {
-                                debug!("read task data:{}", self.task_list_key);
-                            }
-                        }
-                        Err(err) => {
-                            warn!("read pop error[{}]:{}", self.task_list_key, err);
-                            sleep(Duration::from_secs(1)).await;
-                            continue;
-                        }
-                    };
-
-                    match redis.ltrim(&self.list_notify, 0, -1).await {
-                        Ok(()) => {}
-                        Err(err) => {
-                            warn!("clear list error:{}", err);
-                        }
-                    };
-                    match redis.set_nx(&self.read_lock_key, 1).await {
-                        Ok(()) => {}
-                        Err(err) => {
-                            warn!("lock read error:{}", err);
-                            continue;
-                        }
-                    };
-                    match redis
-                        .expire(&self.read_lock_key, self.read_lock_timeout)
-                        .await
-                    {
-                        Ok(()) => {}
-                        Err(err) => {
-                            match redis.del(&self.read_lock_key).await {
-                                Ok(()) => {}
-                                Err(err) => {
-                                    warn!("expire set fail,delete lock fail:{}", err);
-                                }
-                            };
-                            warn!("set expire error:{}", err);
-                            continue;
-                        }
-                    };
-                    //完成读取锁定
-
-                    //获取当前任务中数据
-                    let redis_data = match self.task_data(&mut redis).await {
-                        Ok(data) => data,
-                        Err(err) => {
-                            warn!("get run task data error:{}", err);
-                            match redis.del(&self.read_lock_key).await {
-                                Ok(()) => {}
-                                Err(err) => {
-                                    warn!("get run task data fail,and read lock error:{}", err);
-                                }
-                            };
-                            continue;
-                        }
-                    };
-                    let nt = now_time().unwrap_or_default();
-                    let mut filter_data = HashMap::new();
-                    //过滤掉超时的任务中的数据
-                    for (k, v) in redis_data {
-                        if v.time + (self.check_timeout as u64) < nt {
-                            match redis.hdel(&self.task_list_key, &k).await {
-                                Ok(()) => {}
-                                Err(err) => {
-                                    warn!("time out clean runing task fail:{}", err);
-                                }
-                            }
+            let wait_res = tokio::select! {
+                res = self.backend.wait_notify(self.check_timeout) => res,
+                _ = self.state.stop_notify.notified() => {
+                    continue;
+                }
+            };
+            match wait_res {
+                Ok(is_notify) => {
+                    if !is_notify {
+                        if !self.is_check {
                             continue;
                         }
-                        filter_data.insert(k, v);
+                        info!("timeout check task:{}", task_name);
+                    } else {
+                        debug!("read task data:{}", task_name);
                     }
-                    debug!(
-                        "on task data:{} total:{}",
-                        self.task_list_key,
-                        filter_data.len()
+                }
+                Err(err) => {
+                    warn!(
+                        "task:{} listen notify fail,try listening:{}",
+                        task_name,
+                        err.to_fluent_message().default_format()
                     );
-                    let task_data = match task_reader
-                        .read_send_task(&filter_data, self.read_size)
-                        .await
-                    {
-                        Ok(data) => data,
-                        Err(err) => {
-                            warn!("read task:{} record error:{}", self.task_list_key, err);
-                            match redis.del(&self.read_lock_key).await {
-                                Ok(()) => {}
-                                Err(err) => {
-                                    warn!("read task fail ,del read lock error:{}", err);
-                                }
-                            };
-                            continue;
-                        }
-                    };
-                    //数据读取完成，解读取锁定
-                    match redis.del(&self.read_lock_key).await {
-                        Ok(()) => {}
-                        Err(err) => {
-                            warn!(
-                                "read task:{} fail ,del read lock error:{}",
-                                self.task_list_key, err
-                            );
-                        }
-                    };
-                    if task_data.result.is_empty() {
-                        //无任务重新监听
-                        info!("not task:{} record data ", self.task_list_key);
-                        continue;
-                    }
-                    //添加任务中的数据
-                    let mut add_task = Vec::with_capacity(task_data.result.len());
+                    sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+            match self.backend.read_lock(self.read_lock_timeout).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    //其他执行者读取中,由其负责读取并通知下一页
+                    debug!("task:{} is reading by other", task_name);
+                    continue;
+                }
+                Err(err) => {
+                    warn!(
+                        "lock read error:{}",
+                        err.to_fluent_message().default_format()
+                    );
+                    continue;
+                }
+            };
+            //完成读取锁定
 
-                    for r in task_data.result {
-                        let i = r.to_task_pk();
-                        let v = r.to_task_data();
-                        match redis.hset(&self.task_list_key, &i, v.clone()).await {
-                            //必须添加成功到发送中才进行发送
-                            Ok(()) => add_task.push(r),
-                            Err(err) => {
-                                warn!("set run task:{} error[{}]:{}", self.task_list_key, i, err);
-                                continue;
-                            }
-                        };
-                    }
-                    //有下一页数据,通知其他执行服务器继续
-                    if task_data.next {
-                        if let Err(err) = self.notify(&mut redis).await {
-                            warn!("notify next task:{} fail:{}", self.task_list_key, err);
-                        }
-                    }
-                    //把数据添加到发送channel
-                    for tmp in add_task {
-                        let pk = tmp.to_task_pk();
-                        if let Err(err) = channel_sender.send(tmp).await {
-                            warn!(
-                                "add task:{} fail ,remove task fail:{}",
-                                self.task_list_key, err
-                            );
-                            match redis.hdel(&self.task_list_key, &pk).await {
-                                Ok(()) => {}
-                                Err(err) => {
-                                    warn!(
-                                        "add task:{} fail ,remove task fail:{}",
-                                        self.task_list_key, err
-                                    );
-                                }
-                            };
-                        } else {
-                            debug!("send task:{} add:{}", self.task_list_key, pk);
-                        }
+            //获取当前任务中数据
+            let run_data = match self.backend.task_data().await {
+                Ok(data) => data,
+                Err(err) => {
+                    warn!(
+                        "get run task data error:{}",
+                        err.to_fluent_message().default_format()
+                    );
+                    if let Err(err) = self.backend.read_unlock().await {
+                        warn!(
+                            "get run task data fail,and read lock error:{}",
+                            err.to_fluent_message().default_format()
+                        );
+                    };
+                    continue;
+                }
+            };
+            let nt = now_time().unwrap_or_default();
+            let mut filter_data = HashMap::new();
+            //过滤掉超时的任务中的数据
+            for (k, v) in run_data {
+                if v.time + (self.check_timeout as u64) < nt {
+                    if let Err(err) = self.backend.task_del(&k).await {
+                        warn!(
+                            "time out clean runing task fail:{}",
+                            err.to_fluent_message().default_format()
+                        );
                     }
-                    debug!("listen next send task :{}", self.task_list_key);
+                    continue;
+                }
+                filter_data.insert(k, v);
+            }
+            debug!("on task data:{} total:{}", task_name, filter_data.len());
+            let task_data = match task_reader
+                .read_send_task(&filter_data, self.read_size)
+                .await
+            {
+                Ok(data) => {
+                    self.state
+                        .last_acquisition
+                        .store(now_time().unwrap_or_default(), Ordering::Relaxed);
+                    data
                 }
                 Err(err) => {
+                    self.state.acquisition_error.fetch_add(1, Ordering::Relaxed);
+                    warn!("read task:{} record error:{}", task_name, err);
+                    if let Err(err) = self.backend.read_unlock().await {
+                        warn!(
+                            "read task fail ,del read lock error:{}",
+                            err.to_fluent_message().default_format()
+                        );
+                    };
+                    continue;
+                }
+            };
+            //数据读取完成，解读取锁定
+            if let Err(err) = self.backend.read_unlock().await {
+                warn!(
+                    "read task:{} fail ,del read lock error:{}",
+                    task_name,
+                    err.to_fluent_message().default_format()
+                );
+            };
+            if task_data.result.is_empty() {
+                //无任务重新监听
+                info!("not task:{} record data ", task_name);
+                continue;
+            }
+            //添加任务中的数据
+            let mut add_task = Vec::with_capacity(task_data.result.len());
+
+            for r in task_data.result {
+                let i = r.to_task_pk();
+                let v = r.to_task_data();
+                match self.backend.task_add(&i, &v).await {
+                    //必须添加成功到发送中才进行发送
+                    Ok(()) => add_task.push(r),
+                    Err(err) => {
+                        warn!(
+                            "set run task:{} error[{}]:{}",
+                            task_name,
+                            i,
+                            err.to_fluent_message().default_format()
+                        );
+                        continue;
+                    }
+                };
+            }
+            //有下一页数据,通知其他执行服务器继续
+            if task_data.next {
+                if let Err(err) = self.notify().await {
                     warn!(
-                        "task:{} connect redis fail,try listening:{}",
-                        self.task_list_key, err
+                        "notify next task:{} fail:{}",
+                        task_name,
+                        err.to_fluent_message().default_format()
                     );
-                    sleep(Duration::from_secs(1)).await;
                 }
             }
+            //把数据添加到发送channel
+            for tmp in add_task {
+                let pk = tmp.to_task_pk();
+                if let Err(err) = channel_sender.send(tmp).await {
+                    warn!("add task:{} fail ,remove task fail:{}", task_name, err);
+                    if let Err(err) = self.backend.task_del(&pk).await {
+                        warn!(
+                            "add task:{} fail ,remove task fail:{}",
+                            task_name,
+                            err.to_fluent_message().default_format()
+                        );
+                    };
+                } else {
+                    debug!("send task:{} add:{}", task_name, pk);
+                }
+            }
+            debug!("listen next send task :{}", task_name);
+        }
+        //关闭channel,执行循环在处理完执行中任务后退出
+        drop(channel_sender);
+        if let Err(err) = run_handle.await {
+            error!("task:{} run loop error:{:?}", task_name, err);
+        }
+        info!("task:{} is stop", task_name);
+        self.state.running.send_replace(false);
+    }
+    /// 停止任务派发
+    /// 不再获取新任务,等待执行中任务完成
+    /// 超过等待时间时中止执行中任务,并释放其执行中记录,以便其他服务器重新执行
+    /// * `timeout` - 等待执行中任务完成的最长时间
+    pub async fn shutdown(&self, timeout: Duration) {
+        let task_name = self.backend.task_name();
+        self.state.is_stop.store(true, Ordering::Relaxed);
+        self.state.stop_notify.notify_one();
+        let mut running = self.state.running.subscribe();
+        if !*running.borrow() {
+            return;
+        }
+        info!("task:{} shutdown,wait runing task", task_name);
+        if tokio::time::timeout(timeout, running.wait_for(|e| !*e))
+            .await
+            .is_ok()
+        {
+            return;
+        }
+        warn!("task:{} shutdown timeout,abort runing task", task_name);
+        for (_, abt) in self.state.task_ing.lock().iter() {
+            abt.abort();
+        }
+        if tokio::time::timeout(Duration::from_secs(5), running.wait_for(|e| !*e))
+            .await
+            .is_err()
+        {
+            warn!("task:{} shutdown fail", task_name);
         }
     }
 }
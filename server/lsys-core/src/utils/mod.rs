@@ -8,3 +8,5 @@ mod op_env;
 pub use op_env::*;
 mod string;
 pub use string::*;
+mod trace;
+pub use trace::*;
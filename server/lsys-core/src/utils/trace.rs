@@ -0,0 +1,77 @@
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use tokio::task::futures::TaskLocalFuture;
+use tracing::{info_span, instrument::Instrumented, Instrument, Span};
+
+use super::RequestEnv;
+
+tokio::task_local! {
+    static TRACE_CONTEXT: TraceContext;
+}
+
+// 请求追踪上下文
+// 在HTTP请求,服务器间通知,后台任务间传递,用于关联同一请求产生的日志及数据
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceContext {
+    #[serde(default)]
+    pub request_id: String,
+}
+
+impl TraceContext {
+    pub fn new(request_id: &str) -> Self {
+        Self {
+            request_id: request_id.to_owned(),
+        }
+    }
+    /// 从请求环境创建,无请求ID时使用当前上下文
+    pub fn from_env(env_data: Option<&RequestEnv>) -> Self {
+        match env_data.and_then(|e| e.request_id.as_ref()) {
+            Some(request_id) if !request_id.is_empty() => Self::new(request_id),
+            _ => Self::current(),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.request_id.is_empty()
+    }
+    /// 当前异步任务所在的追踪上下文,不存在时返回空上下文
+    pub fn current() -> Self {
+        TRACE_CONTEXT.try_with(|e| e.to_owned()).unwrap_or_default()
+    }
+    /// 包含 request_id 字段的 span
+    /// * `name` - 执行内容,如任务名
+    pub fn span(&self, name: &str) -> Span {
+        info_span!("trace", exec = name, request_id = self.request_id.as_str())
+    }
+    /// 在该追踪上下文中执行,期间 current 返回该上下文,日志包含 request_id 字段
+    /// * `name` - 执行内容,如任务名
+    pub fn scope<F: Future>(
+        self,
+        name: &str,
+        fut: F,
+    ) -> TaskLocalFuture<TraceContext, Instrumented<F>> {
+        let span = self.span(name);
+        self.with(fut.instrument(span))
+    }
+    /// 在该追踪上下文中执行,不创建 span,用于已自行创建 span 的场景
+    pub fn with<F: Future>(self, fut: F) -> TaskLocalFuture<TraceContext, F> {
+        TRACE_CONTEXT.scope(self, fut)
+    }
+}
+
+#[tokio::test]
+async fn test_trace_context() {
+    assert!(TraceContext::current().is_empty());
+    let request_id = TraceContext::new("req-1")
+        .scope("test", async {
+            let inner = tokio::spawn(async { TraceContext::current() })
+                .await
+                .unwrap();
+            assert!(inner.is_empty());
+            TraceContext::current().request_id
+        })
+        .await;
+    assert_eq!(request_id, "req-1");
+    let env = RequestEnv::new(None, None, Some("req-2".to_string()), None);
+    assert_eq!(TraceContext::from_env(Some(&env)).request_id, "req-2");
+}
@@ -1,16 +1,47 @@
 use async_trait::async_trait;
-use deadpool_redis::{redis::AsyncCommands, Connection};
+use deadpool_redis::{
+    redis::{AsyncCommands, Script},
+    Connection,
+};
 
 mod result;
+mod totp;
 pub use result::*;
+pub use totp::*;
 
 use crate::{fluent_message, rand_str, RandType};
 const CODE_SAVE_KEY: &str = "valid-save";
+const CODE_FAIL_KEY: &str = "valid-fail";
+
+//占用一次校验次数,递增并刷新锁定时间,返回当前校验次数
+const CODE_FAIL_INCR_SCRIPT: &str = r#"
+local n = redis.call('INCR', KEYS[1])
+redis.call('EXPIRE', KEYS[1], ARGV[1])
+return n
+"#;
+
+//验证码校验失败限制
+#[derive(Clone, Copy, Debug)]
+pub struct ValidCodeCheckLimit {
+    /// 最大校验失败次数,达到后验证码失效并锁定
+    pub max_fail: usize,
+    /// 锁定时间,单位秒,锁定期间不再校验
+    pub lock_time: usize,
+}
+impl Default for ValidCodeCheckLimit {
+    fn default() -> Self {
+        Self {
+            max_fail: 5,
+            lock_time: 600,
+        }
+    }
+}
 
 pub struct ValidCode {
     prefix: String,
     ignore_case: bool,
     redis: deadpool_redis::Pool,
+    check_limit: Option<ValidCodeCheckLimit>,
 }
 #[async_trait]
 pub trait ValidCodeData {
@@ -28,6 +59,25 @@ pub trait ValidCodeData {
         tag: &'t str,
     ) -> ValidCodeResult<()>;
     fn save_time(&self) -> usize;
+    /// 校验提交的验证码,默认与保存的验证码比较
+    /// * `save_code` - 已保存的验证码
+    /// * `code` - 提交的验证码
+    #[allow(clippy::too_many_arguments)]
+    async fn check_code<'t>(
+        &mut self,
+        _redis: &'t mut Connection,
+        _prefix: &'t str,
+        _tag: &'t str,
+        save_code: &'t str,
+        code: &'t str,
+        ignore_case: bool,
+    ) -> ValidCodeResult<bool> {
+        Ok(if ignore_case {
+            save_code.to_lowercase() == code.to_lowercase()
+        } else {
+            save_code == code
+        })
+    }
 }
 
 pub struct ValidCodeTime {
@@ -95,9 +145,9 @@ impl ValidCodeData for ValidCodeDataRandom {
             };
 
         if self.save_time() > 0 && duration_time > 0 {
-            redis.set(change_key.as_str(), out_code.clone()).await?;
+            redis.set::<_, _, ()>(change_key.as_str(), out_code.clone()).await?;
             redis
-                .expire(change_key.as_str(), duration_time as usize)
+                .expire::<_, ()>(change_key.as_str(), duration_time as usize)
                 .await?;
         }
         Ok(out_code)
@@ -110,7 +160,7 @@ impl ValidCodeData for ValidCodeDataRandom {
         tag: &'t str,
     ) -> ValidCodeResult<()> {
         let change_key = CODE_CHANGE_KEY.to_owned() + prefix + tag;
-        redis.del(change_key).await?;
+        redis.del::<_, ()>(change_key).await?;
         Ok(())
     }
 
@@ -131,7 +181,15 @@ impl ValidCode {
             prefix,
             redis,
             ignore_case,
+            check_limit: None,
+        }
+    }
+    /// 限制校验失败次数,防止暴力猜测验证码
+    pub fn with_check_limit(mut self, check_limit: ValidCodeCheckLimit) -> Self {
+        if check_limit.max_fail > 0 {
+            self.check_limit = Some(check_limit);
         }
+        self
     }
     pub async fn get_code(&self, tag: &String) -> ValidCodeResult<(String, usize)> {
         if tag.len() > 255 {
@@ -170,11 +228,11 @@ impl ValidCode {
             out_code
         );
         if code.is_none() || out_code != code.unwrap_or_default() {
-            redis.set(save_key.as_str(), out_code.clone()).await?;
+            redis.set::<_, _, ()>(save_key.as_str(), out_code.clone()).await?;
         }
         let save_time = valid_code_builder.save_time();
         if save_time > 0 {
-            redis.expire(save_key.as_str(), save_time).await?;
+            redis.expire::<_, ()>(save_key.as_str(), save_time).await?;
         }
         Ok((out_code, save_time))
     }
@@ -194,24 +252,35 @@ impl ValidCode {
         let save_key = CODE_SAVE_KEY.to_owned() + self.prefix.as_str() + tag;
         let mut redis = self.redis.get().await?;
         if save_time > 0 {
-            redis.expire(save_key.as_str(), save_time).await?;
+            redis.expire::<_, ()>(save_key.as_str(), save_time).await?;
         }
         Ok((code, save_time))
     }
     pub async fn clear_code<T: ValidCodeData>(
         &self,
-        tag: &String,
+        tag: &str,
         valid_code_builder: &mut T,
     ) -> ValidCodeResult<()> {
         let save_key = CODE_SAVE_KEY.to_owned() + self.prefix.as_str() + tag;
         let mut redis = self.redis.get().await?;
-        redis.del(save_key).await?;
+        redis.del::<_, ()>(save_key).await?;
         valid_code_builder
             .clear_code(&mut redis, &self.prefix, tag)
             .await?;
         Ok(())
     }
     pub async fn check_code(&self, tag: &String, code: &String) -> ValidCodeResult<()> {
+        self.check_code_data(tag, code, &mut ValidCodeDataRandom::default())
+            .await
+    }
+    /// 通过指定的验证码生成方式校验验证码
+    /// 如 ValidCodeDataTotp 由用户密钥计算验证码,而非使用保存的验证码
+    pub async fn check_code_data<T: ValidCodeData + Send>(
+        &self,
+        tag: &String,
+        code: &String,
+        valid_code_builder: &mut T,
+    ) -> ValidCodeResult<()> {
         let (s_code, _) = self.get_code(tag).await?;
         let c_code = code.trim();
         if c_code.is_empty() {
@@ -220,11 +289,48 @@ impl ValidCode {
                 prefix: self.prefix.to_owned(),
             }));
         }
-        if if self.ignore_case {
-            s_code.to_lowercase() != (*c_code).to_lowercase()
-        } else {
-            s_code != *c_code
-        } {
+        let mut redis = self.redis.get().await?;
+        let fail_key = CODE_FAIL_KEY.to_owned() + self.prefix.as_str() + tag;
+        //校验前先原子占用一次校验次数,并发请求不能绕过次数限制
+        let mut fail: usize = 0;
+        if let Some(limit) = self.check_limit {
+            fail = Script::new(CODE_FAIL_INCR_SCRIPT)
+                .key(fail_key.as_str())
+                .arg(limit.lock_time)
+                .invoke_async(&mut redis)
+                .await?;
+            if fail > limit.max_fail {
+                return Err(self.locked_error(&mut redis, &fail_key).await?);
+            }
+        }
+        if !valid_code_builder
+            .check_code(
+                &mut redis,
+                &self.prefix,
+                tag,
+                &s_code,
+                c_code,
+                self.ignore_case,
+            )
+            .await?
+        {
+            if let Some(limit) = self.check_limit {
+                if fail >= limit.max_fail {
+                    //失败次数过多,当前验证码失效
+                    let save_key = CODE_SAVE_KEY.to_owned() + self.prefix.as_str() + tag;
+                    redis.del::<_, ()>(save_key).await?;
+                    valid_code_builder
+                        .clear_code(&mut redis, &self.prefix, tag)
+                        .await?;
+                    tracing::warn!(
+                        "valid-code locked [ prefix: {} tag: {} fail: {} ]",
+                        &self.prefix,
+                        &tag,
+                        fail
+                    );
+                    return Err(self.locked_error(&mut redis, &fail_key).await?);
+                }
+            }
             return Err(ValidCodeError::NotMatch(ValidCodeCheckError {
                 message: fluent_message!("valid-code-not-match",{//format!("your submit code [{}] not match", code)
                     "code":code
@@ -232,6 +338,22 @@ impl ValidCode {
                 prefix: self.prefix.to_owned(),
             }));
         }
+        if self.check_limit.is_some() {
+            redis.del::<_, ()>(fail_key.as_str()).await?;
+        }
         Ok(())
     }
+    async fn locked_error(
+        &self,
+        redis: &mut Connection,
+        fail_key: &str,
+    ) -> ValidCodeResult<ValidCodeError> {
+        let ttl: isize = redis.ttl(fail_key).await?;
+        Ok(ValidCodeError::Locked(ValidCodeCheckError {
+            message: fluent_message!("valid-code-locked", {
+                "time": ttl.max(0)
+            }),
+            prefix: self.prefix.to_owned(),
+        }))
+    }
 }
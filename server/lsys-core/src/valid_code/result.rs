@@ -21,6 +21,8 @@ pub enum ValidCodeError {
     Tag(FluentMessage),
     DelayTimeout(ValidCodeCheckError),
     NotMatch(ValidCodeCheckError),
+    Totp(FluentMessage),
+    Locked(ValidCodeCheckError),
 }
 
 impl IntoFluentMessage for ValidCodeError {
@@ -32,6 +34,8 @@ impl IntoFluentMessage for ValidCodeError {
             ValidCodeError::Tag(err) => err.to_owned(),
             ValidCodeError::DelayTimeout(err) => err.message.clone(),
             ValidCodeError::NotMatch(err) => err.message.clone(),
+            ValidCodeError::Totp(err) => err.to_owned(),
+            ValidCodeError::Locked(err) => err.message.clone(),
         }
     }
 }
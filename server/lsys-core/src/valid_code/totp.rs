@@ -0,0 +1,301 @@
+use async_trait::async_trait;
+use deadpool_redis::{redis::Script, Connection};
+use hmac::{Hmac, Mac};
+
+use super::{ValidCodeData, ValidCodeError, ValidCodeResult};
+use crate::{fluent_message, now_time};
+
+const CODE_TOTP_LAST_KEY: &str = "valid-totp-last";
+
+//记录最后通过校验的计数,不大于已记录计数时返回0
+const CODE_TOTP_LAST_SCRIPT: &str = r#"
+local last = tonumber(redis.call('GET', KEYS[1]))
+if last ~= nil and last >= tonumber(ARGV[1]) then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+return 1
+"#;
+
+const BASE32_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidCodeTotpAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl ValidCodeTotpAlgorithm {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+            Self::Sha512 => "SHA512",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ValidCodeTotpConfig {
+    /// 验证码变化间隔,单位秒
+    pub step: u64,
+    /// 验证码位数
+    pub digits: u32,
+    /// 允许前后偏差的间隔数,用于容忍客户端时间误差
+    pub skew: u64,
+    pub algorithm: ValidCodeTotpAlgorithm,
+}
+
+impl Default for ValidCodeTotpConfig {
+    fn default() -> Self {
+        Self {
+            step: 30,
+            digits: 6,
+            skew: 1,
+            algorithm: ValidCodeTotpAlgorithm::Sha1,
+        }
+    }
+}
+
+// 基于时间的验证码 RFC 6238
+// 验证码由用户密钥及当前时间计算得出,用于身份验证器APP
+// 记录最后通过校验的计数,不大于该计数的验证码不能再使用 RFC 6238 5.2
+pub struct ValidCodeDataTotp {
+    secret: Vec<u8>,
+    config: ValidCodeTotpConfig,
+}
+
+impl ValidCodeDataTotp {
+    /// * `secret` - 用户密钥原始数据
+    pub fn new(secret: Vec<u8>, config: ValidCodeTotpConfig) -> Self {
+        Self { secret, config }
+    }
+    /// * `secret` - BASE32编码的用户密钥,即身份验证器APP中添加的密钥
+    pub fn from_base32(secret: &str, config: ValidCodeTotpConfig) -> ValidCodeResult<Self> {
+        let secret = base32_decode(secret)
+            .ok_or_else(|| ValidCodeError::Totp(fluent_message!("valid-code-totp-secret-error")))?;
+        if secret.is_empty() {
+            return Err(ValidCodeError::Totp(fluent_message!(
+                "valid-code-totp-secret-error"
+            )));
+        }
+        Ok(Self::new(secret, config))
+    }
+    /// 生成BASE32编码的随机密钥
+    pub fn create_secret() -> String {
+        let secret = (0..20).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        base32_encode(&secret)
+    }
+    /// BASE32编码的用户密钥
+    pub fn secret(&self) -> String {
+        base32_encode(&self.secret)
+    }
+    /// 身份验证器APP添加密钥的URL,一般以二维码方式展示
+    /// * `issuer` - 发行方,如站点名
+    /// * `account` - 用户账号
+    pub fn otpauth_url(&self, issuer: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm={}&digits={}&period={}",
+            url_encode(issuer),
+            url_encode(account),
+            self.secret(),
+            url_encode(issuer),
+            self.config.algorithm.name(),
+            self.config.digits,
+            self.config.step
+        )
+    }
+    fn step(&self) -> u64 {
+        self.config.step.max(1)
+    }
+    /// 指定时间的验证码
+    /// * `time` - 时间戳,单位秒
+    pub fn code_at(&self, time: u64) -> String {
+        self.hotp(time / self.step())
+    }
+    //RFC 4226
+    fn hotp(&self, counter: u64) -> String {
+        let msg = counter.to_be_bytes();
+        let hash = match self.config.algorithm {
+            ValidCodeTotpAlgorithm::Sha1 => hmac_hash::<Hmac<sha1::Sha1>>(&self.secret, &msg),
+            ValidCodeTotpAlgorithm::Sha256 => hmac_hash::<Hmac<sha2::Sha256>>(&self.secret, &msg),
+            ValidCodeTotpAlgorithm::Sha512 => hmac_hash::<Hmac<sha2::Sha512>>(&self.secret, &msg),
+        };
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let code = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        let digits = self.config.digits.clamp(1, 9);
+        format!(
+            "{:0width$}",
+            code % 10u32.pow(digits),
+            width = digits as usize
+        )
+    }
+}
+
+//固定时间比较,避免通过响应时间猜测验证码
+fn code_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn hmac_hash<M: Mac + hmac::digest::KeyInit>(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut mac =
+        <M as hmac::digest::KeyInit>::new_from_slice(key).expect("hmac can take key of any size");
+    mac.update(msg);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() * 8).div_ceil(5));
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            out.push(BASE32_CHARS[((buffer >> (bits - 5)) & 0x1f) as usize] as char);
+            bits -= 5;
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_CHARS[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+//忽略空格及填充,不区分大小写
+fn base32_decode(data: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in data.bytes() {
+        if c == b'=' || c == b' ' || c == b'-' {
+            continue;
+        }
+        let val = BASE32_CHARS
+            .iter()
+            .position(|e| *e == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | val as u32;
+        bits += 5;
+        if bits >= 8 {
+            out.push((buffer >> (bits - 8)) as u8);
+            bits -= 8;
+        }
+    }
+    Some(out)
+}
+
+fn url_encode(data: &str) -> String {
+    let mut out = String::with_capacity(data.len());
+    for byte in data.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+#[async_trait]
+impl ValidCodeData for ValidCodeDataTotp {
+    async fn get_code<'t>(
+        &mut self,
+        _redis: &'t mut Connection,
+        _code: &'t Option<String>,
+        _prefix: &'t str,
+        _tag: &'t str,
+    ) -> ValidCodeResult<String> {
+        Ok(self.code_at(now_time().unwrap_or_default()))
+    }
+    async fn clear_code<'t>(
+        &mut self,
+        _redis: &'t mut Connection,
+        _prefix: &'t str,
+        _tag: &'t str,
+    ) -> ValidCodeResult<()> {
+        Ok(())
+    }
+    fn save_time(&self) -> usize {
+        self.step() as usize
+    }
+    async fn check_code<'t>(
+        &mut self,
+        redis: &'t mut Connection,
+        prefix: &'t str,
+        tag: &'t str,
+        _save_code: &'t str,
+        code: &'t str,
+        _ignore_case: bool,
+    ) -> ValidCodeResult<bool> {
+        let counter = now_time().unwrap_or_default() / self.step();
+        let skew = self.config.skew;
+        //比较窗口内全部计数,不提前结束
+        let find = (counter.saturating_sub(skew)..=counter + skew).fold(None, |find, e| {
+            if code_eq(self.hotp(e).as_bytes(), code.as_bytes()) {
+                Some(e)
+            } else {
+                find
+            }
+        });
+        let find_counter = match find {
+            Some(find_counter) => find_counter,
+            None => return Ok(false),
+        };
+        //不大于最后通过计数的验证码视为不匹配,记录在窗口移出前有效
+        let last_key = format!("{}{}{}", CODE_TOTP_LAST_KEY, prefix, tag);
+        let pass: u8 = Script::new(CODE_TOTP_LAST_SCRIPT)
+            .key(last_key)
+            .arg(find_counter)
+            .arg(self.step() * (skew * 2 + 1))
+            .invoke_async(redis)
+            .await?;
+        Ok(pass == 1)
+    }
+}
+
+#[test]
+fn test_valid_code_totp() {
+    //RFC 6238 附录B
+    let config = |algorithm| ValidCodeTotpConfig {
+        step: 30,
+        digits: 8,
+        skew: 1,
+        algorithm,
+    };
+    let sha1 = ValidCodeDataTotp::new(
+        b"12345678901234567890".to_vec(),
+        config(ValidCodeTotpAlgorithm::Sha1),
+    );
+    let sha256 = ValidCodeDataTotp::new(
+        b"12345678901234567890123456789012".to_vec(),
+        config(ValidCodeTotpAlgorithm::Sha256),
+    );
+    let sha512 = ValidCodeDataTotp::new(
+        b"1234567890123456789012345678901234567890123456789012345678901234".to_vec(),
+        config(ValidCodeTotpAlgorithm::Sha512),
+    );
+    assert_eq!(sha1.code_at(59), "94287082");
+    assert_eq!(sha256.code_at(59), "46119246");
+    assert_eq!(sha512.code_at(59), "90693936");
+    assert_eq!(sha1.code_at(1111111109), "07081804");
+    assert_eq!(sha256.code_at(1234567890), "91819424");
+    assert_eq!(sha512.code_at(20000000000), "47863826");
+    let secret = ValidCodeDataTotp::create_secret();
+    let totp = ValidCodeDataTotp::from_base32(&secret, ValidCodeTotpConfig::default()).unwrap();
+    assert_eq!(totp.secret(), secret);
+    assert_eq!(totp.code_at(59).len(), 6);
+    assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+    assert_eq!(base32_decode("mzxw 6ytb oi======").unwrap(), b"foobar");
+    assert!(code_eq(b"123456", b"123456"));
+    assert!(!code_eq(b"123456", b"123457"));
+    assert!(!code_eq(b"123456", b"12345"));
+}
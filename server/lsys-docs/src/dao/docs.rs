@@ -1138,7 +1138,7 @@ impl GitDocs {
             .await;
         Ok(add_id)
     }
-    pub async fn menu_del<'t>(
+    pub async fn menu_del(
         &self,
         menu: &DocMenuModel,
         user_id: &u64,
@@ -1173,7 +1173,7 @@ impl GitDocs {
             .await;
         Ok(())
     }
-    pub async fn menu_list<'t>(&self, tag: &DocGitTagModel) -> GitDocResult<Vec<DocMenuModel>> {
+    pub async fn menu_list(&self, tag: &DocGitTagModel) -> GitDocResult<Vec<DocMenuModel>> {
         let sql = sql_format!(
             "doc_tag_id = {} and status={} order by id desc",
             tag.id,
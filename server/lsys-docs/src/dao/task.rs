@@ -674,10 +674,7 @@ impl GitTask {
                 },
                 Some(host.to_owned()),
                 LocalExecType::LocalExec,
-                Some(ReplyWait {
-                    max_node: 1,
-                    timeout: timeout.to_owned(),
-                }),
+                Some(ReplyWait::new(timeout.to_owned())),
             )
             .await
         {
@@ -2,3 +2,13 @@ pub mod dao;
 pub mod model;
 
 pub use git2::Error as GitError;
+
+/// 数据库迁移,按版本顺序执行
+pub fn migrations() -> Vec<lsys_core::MigrateItem> {
+    vec![lsys_core::MigrateItem::new(
+        env!("CARGO_PKG_NAME"),
+        1,
+        "init",
+        include_str!("../tables.sql"),
+    )]
+}
@@ -133,10 +133,7 @@ impl AreaCodeIndexData for AreaCodeIndexDataDisk {
             }
             let find_start = index.parse::<u64>().unwrap_or(0);
             let (find_index_tmp, start_index, end_index) = index_search(&self.index, &find_start);
-            let find_index = match find_index_tmp {
-                Some(tmp) => tmp,
-                None => return None,
-            };
+            let find_index = find_index_tmp?;
             if find_index > 0 {
                 let mut pref_index = find_index;
                 loop {
@@ -225,7 +222,7 @@ impl AreaCodeIndexData for AreaCodeIndexDataDisk {
             let index = key.parse::<u64>().unwrap_or(0);
             vec_data.push((index, key, value));
         }
-        vec_data.sort_by(|a, b| a.0.cmp(&b.0));
+        vec_data.sort_by_key(|a| a.0);
         let index_data = vec_data.iter().map(|e| e.0).collect::<Vec<u64>>();
         //元素数量，最大key长度，最大value长度，版本信息长度 + 版本内容长度
         let info_len = std::mem::size_of::<AreaCodeInfo>()
@@ -357,10 +354,7 @@ fn mmap_code_tree_childs(index_data: &[u64], mmap: &Mmap, index: &str) -> Option
     }
     let find_start = index.parse::<u64>().unwrap_or(0);
     let (find_index_tmp, start_index, end_index) = index_search(index_data, &find_start);
-    let find_index = match find_index_tmp {
-        Some(tmp) => tmp,
-        None => return None,
-    };
+    let find_index = find_index_tmp?;
     if find_index > 0 {
         let mut prev_index = find_index;
         loop {
@@ -459,10 +453,7 @@ impl AreaCodeIndexTree for AreaCodeIndexTreeDisk {
             None => "",
         };
         if let Some(mmap) = &self.mmap {
-            let tmp = match mmap_code_tree_childs(&self.index, mmap, index) {
-                Some(dat) => dat,
-                None => return None,
-            };
+            let tmp = mmap_code_tree_childs(&self.index, mmap, index)?;
             return Some(
                 tmp.into_iter()
                     .map(|t| {
@@ -503,7 +494,7 @@ impl AreaCodeIndexTree for AreaCodeIndexTreeDisk {
             let index = key.parse::<u64>().unwrap_or(0);
             vec_data.push((index, key, value, max_str_len));
         }
-        vec_data.sort_by(|a, b| a.0.cmp(&b.0));
+        vec_data.sort_by_key(|a| a.0);
         let index_data = vec_data.iter().map(|e| e.0).collect::<Vec<u64>>();
         //index,key_length,sub-code-count,sub-code-len,
         let prefix = std::mem::size_of::<AreaCodeTreeItemPrefix>();
@@ -703,10 +694,7 @@ impl AreaGeoProvider for DiskAreaGeoProvider {
         Ok(out)
     }
     fn get_polygon_data(&self, index: &usize) -> Option<AreaGeoIndexInfo> {
-        let mmap = match self.mmap.as_ref() {
-            Some(t) => t,
-            None => return None,
-        };
+        let mmap = self.mmap.as_ref()?;
         let info_len = std::mem::size_of::<DiskAreaGeoInfo>();
 
         let (
@@ -0,0 +1,18 @@
+-- ----------- lsys-logger 0002 ---------------
+CREATE TABLE `yaf_event_outbox` (
+    `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+    `event_type` varchar(32) NOT NULL COMMENT '事件类型',
+    `event_data` text NOT NULL COMMENT '事件数据',
+    `user_id` bigint unsigned NOT NULL DEFAULT 0 COMMENT '相关用户ID',
+    `source_id` bigint unsigned NOT NULL DEFAULT 0 COMMENT '相关记录ID',
+    `app_id` bigint unsigned NOT NULL DEFAULT 0 COMMENT '所属应用ID',
+    `request_id` varchar(32) NOT NULL DEFAULT '' COMMENT '请求ID',
+    `status` tinyint NOT NULL COMMENT '投递状态',
+    `result` varchar(255) NOT NULL DEFAULT '' COMMENT '错误信息',
+    `try_num` tinyint NOT NULL DEFAULT 0 COMMENT '投递次数',
+    `next_time` bigint unsigned NOT NULL COMMENT '下次投递时间',
+    `publish_time` bigint unsigned NOT NULL DEFAULT 0 COMMENT '最后投递时间',
+    `add_time` bigint unsigned NOT NULL COMMENT '添加时间',
+    PRIMARY KEY (`id`),
+    KEY `status_next` (`status`, `next_time`)
+) ENGINE = InnoDB CHARSET = utf8mb4 COMMENT = '领域事件发件箱';
@@ -1,10 +1,12 @@
+use std::sync::Arc;
+
 use lsys_core::{now_time, LimitParam, RequestEnv};
 
 use sqlx::{MySql, Pool, Transaction};
 use sqlx_model::{executor_option, model_option_set, sql_format, Insert, Select};
 use tracing::{debug, warn};
 
-use super::LoggerResult;
+use super::{EventBus, EventData, LoggerResult};
 use crate::model::{ChangeLogModel, ChangeLogModelRef};
 use sqlx_model::SqlQuote;
 
@@ -16,11 +18,41 @@ pub trait ChangeLogData {
 
 pub struct ChangeLogger {
     db: Pool<MySql>,
+    event_bus: Option<Arc<EventBus>>,
 }
 
 impl ChangeLogger {
     pub fn new(db: Pool<MySql>) -> Self {
-        Self { db }
+        Self {
+            db,
+            event_bus: None,
+        }
+    }
+    /// 启用领域事件,未启用时 add_event 写入的事件将被忽略
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+    /// 通过 EventBus::add 写入领域事件,返回事件ID,未启用时返回0
+    /// * `transaction` - 业务数据所在事务,写入失败时调用方需回滚事务,提交后需调用 notify_event
+    pub async fn add_event<'t, T: EventData>(
+        &self,
+        data: &T,
+        source_id: &Option<u64>,
+        user_id: &Option<u64>,
+        transaction: Option<&mut Transaction<'t, sqlx::MySql>>,
+    ) -> LoggerResult<u64> {
+        let event_bus = match &self.event_bus {
+            Some(event_bus) => event_bus,
+            None => return Ok(0),
+        };
+        event_bus.add(data, source_id, user_id, transaction).await
+    }
+    /// 事务提交后通知立即投递事务中写入的事件
+    pub async fn notify_event(&self) {
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.notify().await;
+        }
     }
     pub async fn add<'t, T: ChangeLogData>(
         &self,
@@ -0,0 +1,171 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use lsys_core::{
+    now_time, AppCore, IntoFluentMessage, TaskBackendConfig, TaskDispatch, TaskMonitorItem,
+    TraceContext,
+};
+use parking_lot::RwLock;
+use sqlx::{MySql, Pool, Transaction};
+use sqlx_model::{executor_option, model_option_set, Insert};
+use tracing::warn;
+
+use super::{EventTask, EventTaskAcquisition, EventTaskItem, LoggerResult, EVENT_CHECK_TIME};
+use crate::model::{EventOutboxModel, EventOutboxModelRef, EventOutboxStatus};
+
+const EVENT_REDIS_PREFIX: &str = "event-task";
+
+pub trait EventData {
+    fn event_type<'t>() -> &'t str; //事件类型
+    fn encode(&self) -> String; //事件数据,订阅者按事件类型自行解析
+    //事件所属应用ID,仅应用相关事件返回
+    fn app_id(&self) -> Option<u64> {
+        None
+    }
+}
+
+// 事件订阅者
+// 事件至少投递一次:任一订阅者处理失败时整个事件重新投递,订阅者需保证重复处理无副作用
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// 订阅者名,用于日志及错误信息
+    fn name(&self) -> &str;
+    /// 是否处理该类型事件
+    fn accept(&self, event_type: &str) -> bool;
+    /// 处理事件,返回错误时按重试策略重新投递
+    async fn handle(&self, event: &EventOutboxModel) -> Result<(), String>;
+}
+
+// 领域事件总线
+// 事件与业务数据在同一事务中写入发件箱表,由后台任务投递给订阅者
+// 事务中写入的事件在提交后调用 notify 立即投递,未调用时由定时检测投递
+// 添加订阅者后调用 start 启动投递任务
+pub struct EventBus {
+    db: Pool<MySql>,
+    task: Arc<TaskDispatch<u64, EventTaskItem>>,
+    subscribers: Arc<RwLock<Vec<Arc<dyn EventSubscriber>>>>,
+    max_try: u16,
+    is_start: AtomicBool,
+}
+
+impl EventBus {
+    /// * `max_try` - 最大投递次数,默认5次
+    /// * `task_size` - 同时投递事件数量
+    /// * `task_timeout` - 投递最大执行时间及遗漏事件检测间隔,单位秒
+    /// * `is_check` - 是否定时检测未投递事件,事务中写入的事件依赖此检测
+    pub fn new(
+        redis: deadpool_redis::Pool,
+        db: Pool<MySql>,
+        app_core: Arc<AppCore>,
+        max_try: Option<u16>,
+        task_size: Option<usize>,
+        task_timeout: Option<usize>,
+        is_check: bool,
+    ) -> Self {
+        let task_timeout = match task_timeout {
+            Some(t) if t > 0 => t,
+            _ => EVENT_CHECK_TIME as usize,
+        };
+        let task = Arc::new(TaskDispatch::new(
+            TaskBackendConfig::new(app_core, redis).create(
+                format!("{}-event", EVENT_REDIS_PREFIX),
+                format!("{}-event-read-lock", EVENT_REDIS_PREFIX),
+                format!("{}-event-run-task", EVENT_REDIS_PREFIX),
+            ),
+            task_size,
+            task_timeout,
+            is_check,
+            task_timeout,
+        ));
+        Self {
+            db,
+            task,
+            subscribers: Arc::new(RwLock::new(vec![])),
+            max_try: max_try.unwrap_or(5),
+            is_start: AtomicBool::new(false),
+        }
+    }
+    /// 添加订阅者,需在 start 前添加,否则启动后已投递的事件不会投递给该订阅者
+    pub fn subscribe(&self, subscriber: Arc<dyn EventSubscriber>) {
+        if self.is_start.load(Ordering::Acquire) {
+            warn!("event subscriber {} add after start", subscriber.name());
+        }
+        self.subscribers.write().push(subscriber);
+    }
+    /// 写入事件
+    /// * `transaction` - 业务数据所在事务,事件随事务提交,提交后需调用 notify 立即投递
+    pub async fn add<'t, T: EventData>(
+        &self,
+        data: &T,
+        source_id: &Option<u64>,
+        user_id: &Option<u64>,
+        transaction: Option<&mut Transaction<'t, MySql>>,
+    ) -> LoggerResult<u64> {
+        let in_transaction = transaction.is_some();
+        let event_type = T::event_type().to_owned();
+        let event_data = data.encode();
+        let source_id = source_id.unwrap_or_default();
+        let user_id = user_id.unwrap_or_default();
+        let app_id = data.app_id().unwrap_or_default();
+        let request_id = TraceContext::current()
+            .request_id
+            .chars()
+            .take(32)
+            .collect::<String>();
+        let status = EventOutboxStatus::Init as i8;
+        let time = now_time().unwrap_or_default();
+        let new_data = model_option_set!(EventOutboxModelRef, {
+            event_type: event_type,
+            event_data: event_data,
+            source_id: source_id,
+            user_id: user_id,
+            app_id: app_id,
+            request_id: request_id,
+            status: status,
+            next_time: time,
+            add_time: time,
+        });
+        let res = executor_option!(
+            {
+                Insert::<sqlx::MySql, EventOutboxModel, _>::new(new_data)
+                    .execute(db)
+                    .await?
+            },
+            transaction,
+            &self.db,
+            db
+        );
+        if !in_transaction {
+            self.notify().await;
+        }
+        Ok(res.last_insert_id())
+    }
+    /// 通知投递任务立即读取待投递事件
+    pub async fn notify(&self) {
+        if let Err(err) = self.task.notify().await {
+            warn!(
+                "notify event task fail :{}",
+                err.to_fluent_message().default_format()
+            )
+        }
+    }
+    //投递任务,用于查看运行状态
+    pub fn task_monitor(&self) -> Arc<dyn TaskMonitorItem> {
+        self.task.clone()
+    }
+    /// 启动后台投递任务,重复调用时忽略
+    pub fn start(&self) {
+        if self.is_start.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let task = self.task.clone();
+        let acquisition = EventTaskAcquisition::new(self.db.clone());
+        let executor = EventTask::new(self.db.clone(), self.subscribers.clone(), self.max_try);
+        tokio::spawn(async move {
+            task.dispatch(&acquisition, executor).await;
+        });
+    }
+}
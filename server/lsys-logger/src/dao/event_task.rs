@@ -0,0 +1,209 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use lsys_core::{
+    now_time, CronJob, TaskAcquisition, TaskData, TaskExecutor, TaskItem, TaskRecord, TraceContext,
+};
+use parking_lot::RwLock;
+use sqlx::{MySql, Pool};
+use sqlx_model::{sql_format, ModelTableName, Select, SqlExpr, SqlQuote};
+use tracing::warn;
+
+use super::EventSubscriber;
+use crate::model::{EventOutboxModel, EventOutboxStatus};
+
+//未投递事件检测间隔,单位秒
+pub const EVENT_CHECK_TIME: u64 = 60;
+
+//每次重试的延迟时间
+fn next_time_add(now_num: &i8) -> u64 {
+    match now_num {
+        0 => 10,
+        1 => 60,
+        2 => 5 * 60,
+        3 => 30 * 60,
+        4 => 60 * 60,
+        _ => 6 * 60 * 60,
+    }
+}
+
+pub struct EventTaskItem(EventOutboxModel);
+impl TaskItem<u64> for EventTaskItem {
+    fn to_task_pk(&self) -> u64 {
+        self.0.id
+    }
+    fn to_trace_context(&self) -> TraceContext {
+        TraceContext::new(&self.0.request_id)
+    }
+}
+
+pub struct EventTaskAcquisition {
+    db: Pool<MySql>,
+}
+impl EventTaskAcquisition {
+    pub fn new(db: Pool<MySql>) -> Self {
+        Self { db }
+    }
+}
+#[async_trait]
+impl TaskAcquisition<u64, EventTaskItem> for EventTaskAcquisition {
+    async fn read_send_task(
+        &self,
+        tasking_record: &HashMap<u64, TaskData>,
+        limit: usize,
+    ) -> Result<TaskRecord<u64, EventTaskItem>, String> {
+        let mut sql_vec = vec![];
+        sql_vec.push(sql_format!(
+            "status = {} and next_time<={}",
+            EventOutboxStatus::Init as i8,
+            now_time().unwrap_or_default(),
+        ));
+        let ids = tasking_record.keys().copied().collect::<Vec<u64>>();
+        if !ids.is_empty() {
+            sql_vec.push(sql_format!(" id not in ({})", ids));
+        }
+        let mut event_res = Select::type_new::<EventOutboxModel>()
+            .fetch_all_by_where::<EventOutboxModel, _>(
+                &sqlx_model::WhereOption::Where(format!(
+                    "{} order by id asc limit {}",
+                    sql_vec.join(" and "),
+                    limit + 1
+                )),
+                &self.db,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        let next = if event_res.len() > limit {
+            event_res.pop();
+            true
+        } else {
+            false
+        };
+        Ok(TaskRecord::new(
+            event_res.into_iter().map(EventTaskItem).collect(),
+            next,
+        ))
+    }
+}
+
+#[derive(Clone)]
+pub struct EventTask {
+    db: Pool<MySql>,
+    subscribers: Arc<RwLock<Vec<Arc<dyn EventSubscriber>>>>,
+    max_try: u16,
+}
+
+impl EventTask {
+    pub fn new(
+        db: Pool<MySql>,
+        subscribers: Arc<RwLock<Vec<Arc<dyn EventSubscriber>>>>,
+        max_try: u16,
+    ) -> Self {
+        Self {
+            db,
+            subscribers,
+            max_try,
+        }
+    }
+    async fn change_succ_status(&self, event: &EventOutboxModel) {
+        let sql = sql_format!(
+            "UPDATE {} SET status={},try_num=try_num+1,publish_time={} WHERE id={}",
+            EventOutboxModel::table_name(),
+            EventOutboxStatus::Succ as i8,
+            now_time().unwrap_or_default(),
+            event.id,
+        );
+        if let Err(err) = sqlx::query(sql.as_str()).execute(&self.db).await {
+            warn!("change event status to succ fail[{}]{}", event.id, err);
+        }
+    }
+    async fn change_fail_status(&self, event: &EventOutboxModel, msg: &str) {
+        let ntime = now_time().unwrap_or_default();
+        let msg = msg.chars().take(255).collect::<String>();
+        //状态先于次数更新,此时 try_num 为本次投递前的次数
+        let sql = sql_format!(
+            "UPDATE {} SET status={},result={},try_num=try_num+1,next_time={},publish_time={} WHERE id={}",
+            EventOutboxModel::table_name(),
+            SqlExpr(sql_format!(
+                "if (try_num+1>={},{},status)",
+                self.max_try,
+                EventOutboxStatus::Fail as i8
+            )),
+            msg,
+            ntime + next_time_add(&event.try_num),
+            ntime,
+            event.id,
+        );
+        if let Err(err) = sqlx::query(sql.as_str()).execute(&self.db).await {
+            warn!("change event status to fail is fail[{}]{}", event.id, err);
+        }
+    }
+}
+
+#[async_trait]
+impl TaskExecutor<u64, EventTaskItem> for EventTask {
+    async fn exec(&self, val: EventTaskItem) -> Result<(), String> {
+        let subscribers = self
+            .subscribers
+            .read()
+            .iter()
+            .filter(|e| e.accept(&val.0.event_type))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut errors = vec![];
+        for subscriber in subscribers {
+            if let Err(err) = subscriber.handle(&val.0).await {
+                errors.push(format!("{}:{}", subscriber.name(), err));
+            }
+        }
+        if errors.is_empty() {
+            self.change_succ_status(&val.0).await;
+            return Ok(());
+        }
+        let msg = errors.join(";");
+        self.change_fail_status(&val.0, &msg).await;
+        Err(msg)
+    }
+}
+
+//定时清理已投递及投递失败的事件
+pub struct EventOutboxClearCron {
+    db: Pool<MySql>,
+    keep_time: u64,
+}
+
+impl EventOutboxClearCron {
+    /// * `keep_time` - 事件保留时间,单位秒
+    pub fn new(db: Pool<MySql>, keep_time: u64) -> Self {
+        Self { db, keep_time }
+    }
+}
+
+#[async_trait]
+impl CronJob for EventOutboxClearCron {
+    fn job_name(&self) -> &str {
+        "event-outbox-clear"
+    }
+    async fn run(&self, tick: u64) -> Result<(), String> {
+        let clear_time = tick.saturating_sub(self.keep_time);
+        //分批删除,避免长时间锁表
+        let limit = 1000;
+        loop {
+            let sql = sql_format!(
+                "delete from {} where status!={} and publish_time<{} limit {}",
+                EventOutboxModel::table_name(),
+                EventOutboxStatus::Init as i8,
+                clear_time,
+                limit
+            );
+            let res = sqlx::query(sql.as_str())
+                .execute(&self.db)
+                .await
+                .map_err(|e| e.to_string())?;
+            if res.rows_affected() < limit {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
 mod change_log;
 pub use change_log::*;
+mod event_bus;
+mod event_task;
+pub use event_bus::*;
+pub use event_task::*;
 
 mod result;
 pub use result::*;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use sqlx_model::sqlx_model;
+use sqlx_model::{sqlx_model, sqlx_model_status};
 
 #[derive(FromRow, Clone, Debug, Serialize, Deserialize, Default)]
 #[sqlx_model(db_type = "MySql", table_name = "change_logs")]
@@ -49,3 +49,67 @@ pub struct ChangeLogModel {
     #[sqlx(default)]
     pub add_time: u64,
 }
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[sqlx_model_status(field_type = "i8")]
+pub enum EventOutboxStatus {
+    Init = 1, //待投递
+    Succ = 2, //已投递
+    Fail = 3, //超过重试次数
+}
+
+#[derive(FromRow, Clone, Debug, Serialize, Deserialize, Default)]
+#[sqlx_model(db_type = "MySql", table_name = "event_outbox")]
+pub struct EventOutboxModel {
+    /// 事件ID
+    #[sqlx(default)]
+    pub id: u64,
+
+    /// 事件类型
+    #[sqlx(default)]
+    pub event_type: String,
+
+    /// 事件数据
+    #[sqlx(default)]
+    pub event_data: String,
+
+    /// 相关用户ID
+    #[sqlx(default)]
+    pub user_id: u64,
+
+    /// 相关记录ID
+    #[sqlx(default)]
+    pub source_id: u64,
+
+    /// 所属应用ID,非应用相关事件为0
+    #[sqlx(default)]
+    pub app_id: u64,
+
+    /// 产生事件的请求ID
+    #[sqlx(default)]
+    pub request_id: String,
+
+    /// 投递状态
+    #[sqlx(default)]
+    pub status: i8,
+
+    /// 错误信息
+    #[sqlx(default)]
+    pub result: String,
+
+    /// 投递次数
+    #[sqlx(default)]
+    pub try_num: i8,
+
+    /// 下次投递时间
+    #[sqlx(default)]
+    pub next_time: u64,
+
+    /// 最后投递时间
+    #[sqlx(default)]
+    pub publish_time: u64,
+
+    /// 添加时间
+    #[sqlx(default)]
+    pub add_time: u64,
+}
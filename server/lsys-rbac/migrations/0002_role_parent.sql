@@ -0,0 +1,13 @@
+-- ----------- lsys-rbac 0002 ---------------
+CREATE TABLE `yaf_rbac_role_parent` (
+    `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+    `role_id` bigint unsigned NOT NULL COMMENT '角色ID',
+    `parent_role_id` bigint unsigned NOT NULL COMMENT '上级角色ID',
+    `depth` tinyint unsigned NOT NULL COMMENT '层级,1为直接父角色',
+    `status` tinyint NOT NULL COMMENT '状态',
+    `change_user_id` bigint unsigned NOT NULL COMMENT '最后更新用户',
+    `change_time` bigint unsigned NOT NULL COMMENT '最后更改时间',
+    PRIMARY KEY (`id`),
+    KEY `role_id` (`role_id`, `status`),
+    KEY `parent_role_id` (`parent_role_id`, `status`)
+) ENGINE = InnoDB CHARSET = utf8mb4 COMMENT = '角色继承的上级角色';
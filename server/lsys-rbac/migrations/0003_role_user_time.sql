@@ -0,0 +1,5 @@
+-- ----------- lsys-rbac 0003 ---------------
+ALTER TABLE `yaf_rbac_role_user`
+ADD COLUMN `start_time` bigint unsigned NOT NULL DEFAULT 0 COMMENT '角色关联用户生效时间,0为立即生效' AFTER `user_id`,
+ADD KEY `yaf_rbac_role_user_timeout_IDX` (`status`, `timeout`) USING BTREE,
+ADD KEY `yaf_rbac_role_user_start_IDX` (`user_id`, `status`, `start_time`) USING BTREE;
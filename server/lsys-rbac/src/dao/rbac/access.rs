@@ -1,4 +1,4 @@
-use std::{sync::Arc, vec};
+use std::{fmt::Display, sync::Arc, vec};
 
 use lsys_core::fluent_message;
 use tracing::{debug, info};
@@ -25,12 +25,13 @@ pub enum RoleCheckRow {
         priority: i8,
     },
 }
-impl ToString for RoleCheckRow {
-    fn to_string(&self) -> String {
+impl Display for RoleCheckRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RoleCheckRow::ModelRole { role, res_op_id } => match role {
                 Some((role, positivity)) => {
-                    format!(
+                    write!(
+                        f,
                         "res:{} model role[{}]:{} priority:{}",
                         res_op_id,
                         role.id,
@@ -42,14 +43,15 @@ impl ToString for RoleCheckRow {
                         role.priority,
                     )
                 }
-                None => "model role not find".to_string(),
+                None => write!(f, "model role not find"),
             },
             RoleCheckRow::InnerRole {
                 res_op_id,
                 positivity,
                 priority,
             } => {
-                format!(
+                write!(
+                    f,
                     "res:{} inner role:{} priority:{}",
                     res_op_id,
                     if *positivity == RbacRoleOpPositivity::Allow {
@@ -65,7 +67,7 @@ impl ToString for RoleCheckRow {
 }
 
 impl RoleCheckRow {
-    fn res_op_id(&self) -> u64 {
+    pub(crate) fn res_op_id(&self) -> u64 {
         match self {
             RoleCheckRow::ModelRole { role: _, res_op_id } => *res_op_id,
             RoleCheckRow::InnerRole {
@@ -75,7 +77,7 @@ impl RoleCheckRow {
             } => *res_op_id,
         }
     }
-    fn priority(&self) -> i8 {
+    pub(crate) fn priority(&self) -> i8 {
         match self {
             RoleCheckRow::ModelRole { role, res_op_id: _ } => role
                 .as_ref()
@@ -110,6 +112,9 @@ impl RoleCheckData {
     pub fn new(row: Vec<RoleCheckRow>) -> Self {
         Self(row)
     }
+    pub(crate) fn rows(&self) -> &[RoleCheckRow] {
+        &self.0
+    }
     pub fn match_role(&self, find_res_op_id: u64) -> Option<&RoleCheckRow> {
         self.0.iter().find(|&tmp| tmp.res_op_id() == find_res_op_id)
     }
@@ -211,7 +216,7 @@ impl SystemRole {
 impl SystemRoleCheckData for SystemRole {
     fn role_check_data(&self, user_id: u64, check_vec: &[RbacResData]) -> RoleCheckData {
         let mut data = vec![];
-        if self.root_user_id.iter().any(|e| *e == user_id) {
+        if self.root_user_id.contains(&user_id) {
             let mut tmp =
                 Vec::with_capacity(check_vec.iter().fold(0, |acc, res| acc + res.ops.len()));
             //超级用户，返回最高权限且通过检查的权限检测结果
@@ -247,7 +252,7 @@ impl SystemRoleCheckData for SystemRole {
         RoleCheckData(data)
     }
     fn filter_check_res(&self, user_id: u64, items: &[CheckResData]) -> Vec<CheckResData> {
-        if self.root_user_id.iter().any(|e| *e == user_id) {
+        if self.root_user_id.contains(&user_id) {
             //超级用户访问，不检查权限是否在数据库里有资源记录
             return vec![];
         }
@@ -266,8 +271,8 @@ impl SystemRoleCheckData for SystemRole {
 /// RBAC对外结构
 pub struct RbacAccess {
     res: Arc<RbacRes>,
-    role: Arc<RbacRole>,
-    system_role: Option<Box<dyn SystemRoleCheckData>>,
+    pub(crate) role: Arc<RbacRole>,
+    pub(crate) system_role: Option<Box<dyn SystemRoleCheckData>>,
 }
 
 impl RbacAccess {
@@ -420,7 +425,7 @@ impl RbacAccess {
                 res_data.ops = res_data
                     .ops
                     .into_iter()
-                    .filter(|e| acc_res.ops.iter().any(|ee| *ee == e.op_key))
+                    .filter(|e| acc_res.ops.contains(&e.op_key))
                     .collect::<Vec<_>>();
             } else {
                 res_data.ops = vec![];
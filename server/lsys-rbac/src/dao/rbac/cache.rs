@@ -2,7 +2,7 @@ use std::{str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
 use lsys_core::{
-    cache::{LocalCache, LocalCacheClearItem},
+    cache::{LocalCache, LocalCacheClearItem, LocalCacheStats},
     IntoFluentMessage,
 };
 
@@ -49,4 +49,47 @@ impl LocalCacheClearItem for RbacLocalCacheClear {
         };
         Ok(())
     }
+    async fn clear_all_from_message(&self) {
+        match self {
+            RbacLocalCacheClear::ResKey(cache) => cache.del_all().await,
+            RbacLocalCacheClear::RoleRelation(cache) => cache.del_all().await,
+            RbacLocalCacheClear::RoleAccess(cache) => cache.del_all().await,
+        }
+    }
+    async fn clear(&self, key: Option<&str>) -> Result<(), String> {
+        match (self, key) {
+            (RbacLocalCacheClear::ResKey(cache), Some(key)) => {
+                cache
+                    .clear(
+                        &ResKey::from_str(key)
+                            .map_err(|e| e.to_fluent_message().default_format())?,
+                    )
+                    .await
+            }
+            (RbacLocalCacheClear::RoleRelation(cache), Some(key)) => {
+                cache.clear(&key.to_string()).await
+            }
+            (RbacLocalCacheClear::RoleAccess(cache), Some(key)) => {
+                cache.clear(&key.to_string()).await
+            }
+            (RbacLocalCacheClear::ResKey(cache), None) => cache.clear_all().await,
+            (RbacLocalCacheClear::RoleRelation(cache), None) => cache.clear_all().await,
+            (RbacLocalCacheClear::RoleAccess(cache), None) => cache.clear_all().await,
+        };
+        Ok(())
+    }
+    async fn stats(&self) -> LocalCacheStats {
+        match self {
+            RbacLocalCacheClear::ResKey(cache) => cache.stats().await,
+            RbacLocalCacheClear::RoleRelation(cache) => cache.stats().await,
+            RbacLocalCacheClear::RoleAccess(cache) => cache.stats().await,
+        }
+    }
+    async fn set_enable(&self, enable: bool) {
+        match self {
+            RbacLocalCacheClear::ResKey(cache) => cache.set_enable(enable).await,
+            RbacLocalCacheClear::RoleRelation(cache) => cache.set_enable(enable).await,
+            RbacLocalCacheClear::RoleAccess(cache) => cache.set_enable(enable).await,
+        }
+    }
 }
@@ -0,0 +1,20 @@
+use lsys_logger::dao::EventData;
+use serde::{Deserialize, Serialize};
+
+//角色变更,action 为 edit 或 del
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RbacRoleChangeEvent {
+    pub action: String,
+    pub role_id: u64,
+    pub user_id: u64,
+    pub name: String,
+}
+
+impl EventData for RbacRoleChangeEvent {
+    fn event_type<'t>() -> &'t str {
+        "rbac-role-change"
+    }
+    fn encode(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+}
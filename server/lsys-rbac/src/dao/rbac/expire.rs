@@ -0,0 +1,200 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_trait::async_trait;
+use lsys_core::{now_time, CronJob, IntoFluentMessage, PageParam};
+use sqlx_model::{model_option_set, sql_format, ModelTableName, Select, SqlQuote, Update};
+
+use crate::model::{RbacRoleUserModel, RbacRoleUserModelRef, RbacRoleUserStatus};
+
+use super::{
+    logger::{LogRoleUser, LogRoleUserAction},
+    RbacRole, UserRbacResult,
+};
+
+//角色用户授权的缓存时间,0为使用默认缓存时间,None 为不缓存
+//缓存需在命中角色用户超时或该用户未生效的角色用户生效时失效
+// * `timeout` - 命中角色用户的超时时间,0为不超时
+// * `next_start` - 该用户最近一个未生效角色用户的生效时间,0为不存在
+pub(crate) fn access_cache_time(nowtime: u64, timeout: u64, next_start: u64) -> Option<u64> {
+    match [timeout, next_start].into_iter().filter(|e| *e > 0).min() {
+        None => Some(0),
+        Some(end) if end > nowtime => Some(end - nowtime),
+        Some(_) => None,
+    }
+}
+
+impl RbacRole {
+    /// 指定用户最近一个未生效角色用户的生效时间,不存在时为0
+    pub(crate) async fn role_user_next_start(&self, user_id: u64) -> UserRbacResult<u64> {
+        let sql = sql_format!(
+            "select start_time from {} where user_id={} and status={} and start_time>{} order by start_time asc limit 1",
+            RbacRoleUserModel::table_name(),
+            user_id,
+            RbacRoleUserStatus::Enable,
+            now_time().unwrap_or(0),
+        );
+        Ok(sqlx::query_scalar::<_, u64>(&sql)
+            .fetch_optional(&self.db)
+            .await?
+            .unwrap_or(0))
+    }
+    fn role_user_expiring_where(&self, role_id: u64, within: u64) -> String {
+        let time = now_time().unwrap_or(0);
+        sql_format!(
+            "role_id={} and status={} and timeout>{} and timeout<={}",
+            role_id,
+            RbacRoleUserStatus::Enable,
+            time,
+            time + within,
+        )
+    }
+    /// 角色中即将超时的用户,按超时时间升序
+    /// * `within` - 从当前起多少秒内超时
+    pub async fn role_user_expiring(
+        &self,
+        role_id: u64,
+        within: u64,
+        page: &Option<PageParam>,
+    ) -> UserRbacResult<Vec<RbacRoleUserModel>> {
+        let mut sql = format!(
+            "{} order by timeout asc,id asc",
+            self.role_user_expiring_where(role_id, within)
+        );
+        if let Some(pdat) = page {
+            sql += format!(" limit {} offset {}", pdat.limit, pdat.offset).as_str();
+        }
+        Ok(Select::type_new::<RbacRoleUserModel>()
+            .fetch_all_by_where::<RbacRoleUserModel, _>(
+                &sqlx_model::WhereOption::Where(sql),
+                &self.db,
+            )
+            .await?)
+    }
+    /// 角色中即将超时的用户数量
+    /// * `within` - 从当前起多少秒内超时
+    pub async fn role_user_expiring_count(&self, role_id: u64, within: u64) -> UserRbacResult<i64> {
+        let sql = format!(
+            "select count(*) as total from {} where {}",
+            RbacRoleUserModel::table_name(),
+            self.role_user_expiring_where(role_id, within)
+        );
+        Ok(sqlx::query_scalar::<_, i64>(&sql)
+            .fetch_one(&self.db)
+            .await?)
+    }
+    /// 删除已超时的角色用户,并记录到变更日志
+    /// 返回删除的角色用户数量
+    /// * `time` - 超时时间不大于此值的角色用户视为已超时
+    pub async fn role_user_clear_expired(&self, time: u64) -> UserRbacResult<u64> {
+        //分批处理,避免长时间锁表
+        let limit = 500;
+        let mut total = 0;
+        loop {
+            let rows = Select::type_new::<RbacRoleUserModel>()
+                .fetch_all_by_where::<RbacRoleUserModel, _>(
+                    &sqlx_model::WhereOption::Where(sql_format!(
+                        "status={} and timeout>0 and timeout<={} order by id asc limit {}",
+                        RbacRoleUserStatus::Enable,
+                        time,
+                        limit
+                    )),
+                    &self.db,
+                )
+                .await?;
+            if rows.is_empty() {
+                break;
+            }
+            let ids = rows.iter().map(|e| e.id).collect::<Vec<_>>();
+            let change = model_option_set!(RbacRoleUserModelRef,{
+                change_user_id:0,
+                change_time:now_time().unwrap_or_default(),
+                status:(RbacRoleUserStatus::Delete as i8)
+            });
+            //按ID删除,不影响同一用户重新添加的角色用户
+            let res = Update::<sqlx::MySql, RbacRoleUserModel, _>::new(change)
+                .execute_by_where(
+                    &sqlx_model::WhereOption::Where(sql_format!(
+                        "id in ({}) and status={}",
+                        ids,
+                        RbacRoleUserStatus::Enable
+                    )),
+                    &self.db,
+                )
+                .await?;
+            total += res.rows_affected();
+            let mut role_users = BTreeMap::<u64, Vec<RbacRoleUserModel>>::new();
+            for row in rows.iter() {
+                role_users
+                    .entry(row.role_id)
+                    .or_default()
+                    .push(row.to_owned());
+            }
+            let role_ids = role_users.keys().copied().collect::<Vec<_>>();
+            let roles = self.find_by_ids(&role_ids).await?;
+            for (role_id, users) in role_users {
+                let role = match roles.get(&role_id) {
+                    Some(role) => role,
+                    None => continue,
+                };
+                let user_ids = users.iter().map(|e| e.user_id).collect::<Vec<_>>();
+                self.role_user_cache_clear(role, &user_ids).await?;
+                self.logger
+                    .add(
+                        &LogRoleUser {
+                            action: LogRoleUserAction::Expire,
+                            name: role.name.clone(),
+                            add_user: None,
+                            del_user: Some(user_ids),
+                        },
+                        &Some(role.id),
+                        &Some(role.user_id),
+                        &Some(0),
+                        None,
+                        None,
+                    )
+                    .await;
+            }
+            if (rows.len() as u64) < limit {
+                break;
+            }
+        }
+        Ok(total)
+    }
+}
+
+//定时删除已超时的角色用户
+pub struct RbacRoleUserExpireCron {
+    role: Arc<RbacRole>,
+}
+
+impl RbacRoleUserExpireCron {
+    pub fn new(role: Arc<RbacRole>) -> Self {
+        Self { role }
+    }
+}
+
+#[async_trait]
+impl CronJob for RbacRoleUserExpireCron {
+    fn job_name(&self) -> &str {
+        "rbac-role-user-expire"
+    }
+    async fn run(&self, tick: u64) -> Result<(), String> {
+        self.role
+            .role_user_clear_expired(tick)
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_access_cache_time() {
+    //不超时且无未生效的角色用户时使用默认缓存时间
+    assert_eq!(access_cache_time(100, 0, 0), Some(0));
+    assert_eq!(access_cache_time(100, 160, 0), Some(60));
+    assert_eq!(access_cache_time(100, 0, 130), Some(30));
+    //取先到达的时间
+    assert_eq!(access_cache_time(100, 160, 130), Some(30));
+    assert_eq!(access_cache_time(100, 120, 130), Some(20));
+    assert_eq!(access_cache_time(100, 100, 0), None);
+}
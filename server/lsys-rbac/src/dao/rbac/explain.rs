@@ -0,0 +1,303 @@
+use serde::Serialize;
+
+use crate::model::{RbacResOpModel, RbacRoleModel, RbacRoleOpPositivity, RbacRoleUserRange};
+
+use super::{
+    AccessRes, RbacAccess, RbacResData, RoleCheckData, RoleCheckRow, RoleRelationKey,
+    UserRbacResult,
+};
+
+//候选角色来源,按检测时的合并顺序排列
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessExplainSource {
+    System,   //系统内置角色
+    Relation, //关系角色
+    AllUser,  //游客角色
+    Login,    //登录用户角色
+    User,     //指定用户角色
+}
+
+//资源操作的候选角色
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessExplainRole {
+    pub source: AccessExplainSource,
+    pub role_id: u64, //角色ID,系统内置角色为0
+    pub role_name: String,
+    pub role_user_id: u64,    //角色所属用户ID
+    pub relation_key: String, //关系角色的关系KEY
+    pub user_range: i8,
+    pub res_op_range: i8,
+    pub priority: i8,
+    pub allow: bool,    //加权或减权
+    pub timeout: u64,   //指定用户角色中该用户的授权超时时间,0为不超时
+    pub selected: bool, //是否为该来源中参与合并的角色
+    pub reason: String, //是否参与合并及是否胜出的原因
+}
+
+impl AccessExplainRole {
+    fn new(
+        source: AccessExplainSource,
+        role: &RbacRoleModel,
+        positivity: RbacRoleOpPositivity,
+        timeout: u64,
+    ) -> Self {
+        Self {
+            source,
+            role_id: role.id,
+            role_name: role.name.clone(),
+            role_user_id: role.user_id,
+            relation_key: role.relation_key.clone(),
+            user_range: role.user_range,
+            res_op_range: role.res_op_range,
+            priority: role.priority,
+            allow: positivity == RbacRoleOpPositivity::Allow,
+            timeout,
+            selected: false,
+            reason: String::new(),
+        }
+    }
+    fn from_check_row(row: &RoleCheckRow) -> Option<Self> {
+        match row {
+            RoleCheckRow::ModelRole { role, .. } => role.as_ref().map(|(role, positivity)| {
+                Self::new(AccessExplainSource::System, role, *positivity, 0)
+            }),
+            RoleCheckRow::InnerRole {
+                positivity,
+                priority,
+                ..
+            } => Some(Self {
+                source: AccessExplainSource::System,
+                role_id: 0,
+                role_name: String::new(),
+                role_user_id: 0,
+                relation_key: String::new(),
+                user_range: 0,
+                res_op_range: 0,
+                priority: *priority,
+                allow: *positivity == RbacRoleOpPositivity::Allow,
+                timeout: 0,
+                selected: false,
+                reason: String::new(),
+            }),
+        }
+    }
+}
+
+//资源操作的检测过程
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessExplainOp {
+    pub res: String, //资源KEY
+    pub res_user_id: u64,
+    pub res_id: u64, //资源ID,资源不存在时为0
+    pub op_key: String,
+    pub op_id: u64, //操作ID,操作不存在时为0
+    pub allow: bool,
+    pub reason: String,
+    pub roles: Vec<AccessExplainRole>, //按合并顺序排列的候选角色
+    pub winner: Option<AccessExplainRole>, //决定结果的角色
+}
+
+impl AccessExplainOp {
+    //按检测时的合并规则计算结果:
+    //每个来源仅排序后的第一个角色参与合并,后合并的角色优先级更高时替换已有结果
+    fn merge(
+        check_item: &RbacResData,
+        res_op: &RbacResOpModel,
+        source_roles: Vec<Vec<AccessExplainRole>>,
+    ) -> Self {
+        let mut winner: Option<AccessExplainRole> = None;
+        let mut roles = vec![];
+        for tmp in source_roles {
+            for (i, mut role) in tmp.into_iter().enumerate() {
+                if i > 0 {
+                    role.reason = "not first of source after sort by priority".to_string();
+                    roles.push(role);
+                    continue;
+                }
+                role.selected = true;
+                match winner.as_ref().map(|e| e.priority) {
+                    None => {
+                        role.reason = "first selected role".to_string();
+                        winner = Some(role.clone());
+                    }
+                    Some(priority) if role.priority > priority => {
+                        role.reason =
+                            format!("priority {} greater than {}", role.priority, priority);
+                        winner = Some(role.clone());
+                    }
+                    Some(priority) => {
+                        role.reason =
+                            format!("priority {} not greater than {}", role.priority, priority);
+                    }
+                }
+                roles.push(role);
+            }
+        }
+        let (allow, reason) = match winner {
+            Some(ref role) => (
+                role.allow,
+                format!(
+                    "{} by {:?} role {}",
+                    if role.allow { "allow" } else { "deny" },
+                    role.source,
+                    role.role_id
+                ),
+            ),
+            None => (false, "no role matched".to_string()),
+        };
+        Self {
+            res: check_item.res.res_key.clone(),
+            res_user_id: check_item.res.user_id,
+            res_id: check_item.res.id,
+            op_key: res_op.op_key.clone(),
+            op_id: res_op.id,
+            allow,
+            reason,
+            roles,
+            winner,
+        }
+    }
+}
+
+//授权检测过程
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessExplain {
+    pub user_id: u64,
+    pub allow: bool,
+    pub ops: Vec<AccessExplainOp>,
+}
+
+impl RbacAccess {
+    /// 解释授权检测过程,检测规则同 check
+    /// 返回每个待检测资源操作的候选角色,及最终决定结果的角色
+    pub async fn check_explain(
+        &self,
+        //0 为游客 或具体的访问用户id
+        user_id: u64,
+        //资源所属于用户跟访问用户的关系KEY数组
+        relation_key_roles: &[RoleRelationKey],
+        //待检测资源需要操作的列表
+        check_vec: &[AccessRes],
+    ) -> UserRbacResult<AccessExplain> {
+        let res_data = self.find_option_res(check_vec).await?;
+        let check_data = match self.system_role {
+            Some(ref sys_role) => sys_role.filter_check_res(user_id, &res_data),
+            None => res_data.clone(),
+        };
+        let mut ops = vec![];
+        let mut res_vec = vec![];
+        for tmp in res_data.iter() {
+            let is_check = check_data.iter().any(|e| {
+                e.check_res.res == tmp.check_res.res && e.check_res.user_id == tmp.check_res.user_id
+            });
+            let find_res = tmp.find_res.as_ref();
+            //资源存在时 check_res.ops 为缺失的操作,资源不存在时为全部操作
+            let mut skip_ops = tmp
+                .check_res
+                .ops
+                .iter()
+                .map(|e| (e.to_owned(), 0))
+                .collect::<Vec<_>>();
+            if let Some(res) = find_res {
+                if is_check {
+                    //由角色决定结果
+                    res_vec.push(res.to_owned());
+                } else {
+                    skip_ops.extend(res.ops.iter().map(|e| (e.op_key.clone(), e.id)));
+                }
+            }
+            for (op_key, op_id) in skip_ops {
+                let reason = if !is_check {
+                    "skip check by system role"
+                } else if find_res.is_none() {
+                    "res not find"
+                } else {
+                    "op not find"
+                };
+                ops.push(AccessExplainOp {
+                    res: tmp.check_res.res.clone(),
+                    res_user_id: tmp.check_res.user_id,
+                    res_id: find_res.map(|e| e.res.id).unwrap_or(0),
+                    op_key,
+                    op_id,
+                    allow: !is_check,
+                    reason: reason.to_string(),
+                    roles: vec![],
+                    winner: None,
+                });
+            }
+        }
+        if !res_vec.is_empty() {
+            let system_data = match self.system_role {
+                Some(ref sys_role) => sys_role.role_check_data(user_id, &res_vec),
+                None => RoleCheckData::new(vec![]),
+            };
+            let rbac_role = self.role.cache();
+            let (relation, all_user, login, user) = if user_id > 0 {
+                tokio::try_join!(
+                    rbac_role.find_role_detail_by_relation(relation_key_roles),
+                    rbac_role.find_role_access_by_public(RbacRoleUserRange::AllUser, &res_vec),
+                    rbac_role.find_role_access_by_public(RbacRoleUserRange::Login, &res_vec),
+                    rbac_role.find_role_access_by_user(user_id, &res_vec),
+                )?
+            } else {
+                let (relation, all_user) = tokio::try_join!(
+                    rbac_role.find_role_detail_by_relation(relation_key_roles),
+                    rbac_role.find_role_access_by_public(RbacRoleUserRange::AllUser, &res_vec),
+                )?;
+                (relation, all_user, vec![], vec![])
+            };
+            for check_item in res_vec.iter() {
+                for res_op in check_item.ops.iter() {
+                    let mut source_roles = vec![
+                        system_data
+                            .rows()
+                            .iter()
+                            .filter(|e| e.res_op_id() == res_op.id)
+                            .filter_map(AccessExplainRole::from_check_row)
+                            .collect::<Vec<_>>(),
+                        self.role
+                            .relation_role_candidates(&relation, check_item, res_op)
+                            .into_iter()
+                            .map(|(role, positivity)| {
+                                AccessExplainRole::new(
+                                    AccessExplainSource::Relation,
+                                    &role,
+                                    positivity,
+                                    0,
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    ];
+                    for (source, data) in [
+                        (AccessExplainSource::AllUser, &all_user),
+                        (AccessExplainSource::Login, &login),
+                        (AccessExplainSource::User, &user),
+                    ] {
+                        source_roles.push(
+                            self.role
+                                .access_role_candidates(data, check_item, res_op)
+                                .into_iter()
+                                .map(|e| {
+                                    AccessExplainRole::new(
+                                        source,
+                                        &e.role,
+                                        e.op_positivity,
+                                        e.timeout,
+                                    )
+                                })
+                                .collect::<Vec<_>>(),
+                        );
+                    }
+                    ops.push(AccessExplainOp::merge(check_item, res_op, source_roles));
+                }
+            }
+        }
+        Ok(AccessExplain {
+            user_id,
+            allow: ops.iter().all(|e| e.allow),
+            ops,
+        })
+    }
+}
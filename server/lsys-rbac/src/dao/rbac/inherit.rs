@@ -0,0 +1,635 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use lsys_core::{fluent_message, now_time, RequestEnv};
+use sqlx::{Acquire, FromRow, MySql, Row, Transaction};
+use sqlx_model::{model_option_set, sql_format, Insert, ModelTableName, Select, SqlQuote, Update};
+
+use crate::model::{
+    RbacRoleModel, RbacRoleOpModel, RbacRoleOpStatus, RbacRoleParentModel, RbacRoleParentModelRef,
+    RbacRoleParentStatus, RbacRoleResOpRange, RbacRoleStatus, RbacRoleUserModel, RbacRoleUserRange,
+    RbacRoleUserStatus,
+};
+
+use super::{logger::LogRoleParent, RbacRole, RoleRelationKey, UserRbacError, UserRbacResult};
+
+//角色继承的最大层级
+pub const ROLE_INHERIT_DEPTH_MAX: u8 = 10;
+
+// 角色继承
+// 子角色继承上级角色的资源操作,rbac_role_parent 记录角色的所有上级角色及层级,层级为1的为直接父角色
+// 继承规则:
+// 1. 父角色及子角色需为同一用户的 AllowCustom 角色
+// 2. 子角色自身设置的操作优先于继承的操作
+// 3. 多个上级角色设置同一操作时,层级近的优先,层级相同时禁止优先
+// 4. 继承的操作使用子角色的优先级参与检测
+
+//根据直接父角色计算指定角色的所有上级角色及最小层级
+fn inherit_closure(direct: &BTreeMap<u64, Vec<u64>>, role_id: u64) -> BTreeMap<u64, u8> {
+    let mut out = BTreeMap::new();
+    let mut now = vec![role_id];
+    let mut depth = 0u8;
+    while !now.is_empty() {
+        depth = depth.saturating_add(1);
+        let mut next = vec![];
+        for id in now {
+            for pid in direct.get(&id).map(|e| e.as_slice()).unwrap_or_default() {
+                if !out.contains_key(pid) {
+                    out.insert(*pid, depth);
+                    next.push(*pid);
+                }
+            }
+        }
+        now = next;
+    }
+    out
+}
+
+//指定角色的所有下级角色
+fn inherit_children(direct: &BTreeMap<u64, Vec<u64>>, role_id: u64) -> Vec<u64> {
+    direct
+        .keys()
+        .filter(|e| **e != role_id && inherit_closure(direct, **e).contains_key(&role_id))
+        .copied()
+        .collect()
+}
+
+//合并角色自身及继承的操作,继承的操作按层级及禁止优先取一个,角色ID改为子角色ID
+fn inherit_merge_ops(
+    role_id: u64,
+    own: Vec<RbacRoleOpModel>,
+    mut inherit: Vec<(u8, RbacRoleOpModel)>,
+) -> Vec<RbacRoleOpModel> {
+    inherit.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.positivity.cmp(&b.1.positivity))
+    });
+    let mut out = own;
+    for (_, mut op) in inherit {
+        if out.iter().any(|e| e.res_op_id == op.res_op_id) {
+            continue;
+        }
+        op.role_id = role_id;
+        out.push(op);
+    }
+    out
+}
+
+impl RbacRole {
+    /// 指定资源操作的角色操作,包含继承的操作
+    /// 结果为派生表,字段为 role_id,res_op_id,positivity,depth,自身设置的操作 depth 为0
+    pub(crate) fn inherit_op_sql(&self, op_id: u64) -> String {
+        sql_format!(
+            r#"(select rop.role_id,rop.res_op_id,rop.positivity,0 as depth
+                from {rbac_role_op} as rop where rop.status={role_op_status} and rop.res_op_id={role_op_id}
+            union all
+            select rp.role_id,rop.res_op_id,rop.positivity,rp.depth
+                from {rbac_role_parent} as rp join {rbac_role_op} as rop
+                on rop.role_id=rp.parent_role_id and rop.status={role_op_status} and rop.res_op_id={role_op_id}
+                where rp.status={role_parent_status})"#,
+            rbac_role_op = RbacRoleOpModel::table_name(),
+            rbac_role_parent = RbacRoleParentModel::table_name(),
+            role_op_status = RbacRoleOpStatus::Enable,
+            role_parent_status = RbacRoleParentStatus::Enable,
+            role_op_id = op_id,
+        )
+    }
+    /// 指定角色继承的上级角色操作,返回 (角色ID,层级,上级角色的操作)
+    pub(crate) async fn inherit_parent_ops(
+        &self,
+        role_ids: &[u64],
+    ) -> UserRbacResult<Vec<(u64, u8, RbacRoleOpModel)>> {
+        if role_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = sql_format!(
+            r#"select rp.role_id as inherit_role_id,rp.depth as inherit_depth,rop.*
+            from {rbac_role_parent} as rp join {rbac_role_op} as rop
+            on rop.role_id=rp.parent_role_id and rop.status={role_op_status}
+            where rp.role_id in ({role_ids}) and rp.status={role_parent_status}"#,
+            rbac_role_op = RbacRoleOpModel::table_name(),
+            rbac_role_parent = RbacRoleParentModel::table_name(),
+            role_op_status = RbacRoleOpStatus::Enable,
+            role_parent_status = RbacRoleParentStatus::Enable,
+            role_ids = role_ids,
+        );
+        Ok(sqlx::query(&sql)
+            .try_map(|row: sqlx::mysql::MySqlRow| {
+                Ok((
+                    row.try_get::<u64, &str>("inherit_role_id")?,
+                    row.try_get::<u8, &str>("inherit_depth")?,
+                    RbacRoleOpModel::from_row(&row)?,
+                ))
+            })
+            .fetch_all(&self.db)
+            .await?)
+    }
+    /// 合并角色自身及继承的操作
+    /// * `role_ops` - 角色自身的操作
+    pub(crate) async fn inherit_role_ops(
+        &self,
+        role_ids: &[u64],
+        role_ops: Vec<RbacRoleOpModel>,
+    ) -> UserRbacResult<BTreeMap<u64, Vec<RbacRoleOpModel>>> {
+        let mut own = BTreeMap::<u64, Vec<RbacRoleOpModel>>::new();
+        for op in role_ops {
+            own.entry(op.role_id).or_default().push(op);
+        }
+        let mut inherit = BTreeMap::<u64, Vec<(u8, RbacRoleOpModel)>>::new();
+        for (role_id, depth, op) in self.inherit_parent_ops(role_ids).await? {
+            inherit.entry(role_id).or_default().push((depth, op));
+        }
+        let mut out = BTreeMap::new();
+        for role_id in role_ids {
+            out.insert(
+                *role_id,
+                inherit_merge_ops(
+                    *role_id,
+                    own.remove(role_id).unwrap_or_default(),
+                    inherit.remove(role_id).unwrap_or_default(),
+                ),
+            );
+        }
+        Ok(out)
+    }
+    /// 获取角色的直接父角色
+    pub async fn role_get_parents(
+        &self,
+        role_ids: &[u64],
+    ) -> UserRbacResult<BTreeMap<u64, Vec<RbacRoleModel>>> {
+        if role_ids.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+        let sql = sql_format!(
+            r#"select rp.role_id as inherit_role_id,ro.*
+            from {rbac_role_parent} as rp join {rbac_role} as ro
+            on ro.id=rp.parent_role_id and ro.status={role_status}
+            where rp.role_id in ({role_ids}) and rp.depth=1 and rp.status={role_parent_status}
+            order by ro.id asc"#,
+            rbac_role = RbacRoleModel::table_name(),
+            rbac_role_parent = RbacRoleParentModel::table_name(),
+            role_status = RbacRoleStatus::Enable,
+            role_parent_status = RbacRoleParentStatus::Enable,
+            role_ids = role_ids,
+        );
+        let data = sqlx::query(&sql)
+            .try_map(|row: sqlx::mysql::MySqlRow| {
+                Ok((
+                    row.try_get::<u64, &str>("inherit_role_id")?,
+                    RbacRoleModel::from_row(&row)?,
+                ))
+            })
+            .fetch_all(&self.db)
+            .await?;
+        let mut result = BTreeMap::<u64, Vec<RbacRoleModel>>::new();
+        for (role_id, role) in data {
+            result.entry(role_id).or_default().push(role);
+        }
+        Ok(result)
+    }
+    /// 获取角色的所有下级角色ID
+    pub async fn role_get_children(&self, role_id: u64) -> UserRbacResult<Vec<u64>> {
+        let sql = sql_format!(
+            "select distinct role_id from {} where parent_role_id={} and status={}",
+            RbacRoleParentModel::table_name(),
+            role_id,
+            RbacRoleParentStatus::Enable,
+        );
+        Ok(sqlx::query_scalar::<_, u64>(&sql)
+            .fetch_all(&self.db)
+            .await?)
+    }
+    //重新计算变更直接父角色后受影响角色的上级角色
+    //返回受影响的角色ID及变更前后的上级角色ID
+    async fn inherit_rebuild<'t>(
+        &self,
+        user_id: u64,
+        set: &[(u64, Vec<u64>)],
+        change_user_id: u64,
+        db: &mut Transaction<'t, MySql>,
+    ) -> UserRbacResult<(Vec<u64>, Vec<u64>)> {
+        let sql = sql_format!(
+            r#"select rp.* from {rbac_role_parent} as rp join {rbac_role} as ro
+            on ro.id=rp.role_id and ro.user_id={user_id}
+            where rp.status={role_parent_status}"#,
+            rbac_role = RbacRoleModel::table_name(),
+            rbac_role_parent = RbacRoleParentModel::table_name(),
+            user_id = user_id,
+            role_parent_status = RbacRoleParentStatus::Enable,
+        );
+        let rows = sqlx::query_as::<_, RbacRoleParentModel>(&sql)
+            .fetch_all(&mut *db)
+            .await?;
+        let mut old_direct = BTreeMap::<u64, Vec<u64>>::new();
+        for row in rows.iter().filter(|e| e.depth == 1) {
+            old_direct
+                .entry(row.role_id)
+                .or_default()
+                .push(row.parent_role_id);
+        }
+        let mut direct = old_direct.clone();
+        let mut affected = BTreeSet::new();
+        for (role_id, parents) in set {
+            if parents.is_empty() {
+                direct.remove(role_id);
+            } else {
+                direct.insert(*role_id, parents.to_owned());
+            }
+            affected.insert(*role_id);
+            affected.extend(inherit_children(&old_direct, *role_id));
+            affected.extend(inherit_children(&direct, *role_id));
+        }
+        let mut parent_ids = BTreeSet::new();
+        let mut del_ids = vec![];
+        let mut add_item = vec![];
+        let time = now_time().unwrap_or_default();
+        for role_id in affected.iter() {
+            let closure = inherit_closure(&direct, *role_id);
+            if closure.contains_key(role_id) {
+                return Err(UserRbacError::System(
+                    fluent_message!("rbac-role-parent-cycle",{
+                        "id":role_id
+                    }),
+                ));
+            }
+            if closure.values().any(|e| *e > ROLE_INHERIT_DEPTH_MAX) {
+                return Err(UserRbacError::System(
+                    fluent_message!("rbac-role-parent-depth",{
+                        "id":role_id,
+                        "max":ROLE_INHERIT_DEPTH_MAX
+                    }),
+                ));
+            }
+            let mut now = HashMap::new();
+            for row in rows.iter().filter(|e| e.role_id == *role_id) {
+                parent_ids.insert(row.parent_role_id);
+                match closure.get(&row.parent_role_id) {
+                    Some(depth)
+                        if *depth == row.depth && !now.contains_key(&row.parent_role_id) =>
+                    {
+                        now.insert(row.parent_role_id, row.id);
+                    }
+                    _ => del_ids.push(row.id),
+                }
+            }
+            for (parent_role_id, depth) in closure {
+                parent_ids.insert(parent_role_id);
+                if !now.contains_key(&parent_role_id) {
+                    add_item.push((*role_id, parent_role_id, depth));
+                }
+            }
+        }
+        if !del_ids.is_empty() {
+            let change = model_option_set!(RbacRoleParentModelRef,{
+                change_user_id:change_user_id,
+                change_time:time,
+                status:(RbacRoleParentStatus::Delete as i8)
+            });
+            Update::<sqlx::MySql, RbacRoleParentModel, _>::new(change)
+                .execute_by_where(
+                    &sqlx_model::WhereOption::Where(sql_format!("id in ({})", del_ids)),
+                    &mut *db,
+                )
+                .await?;
+        }
+        if !add_item.is_empty() {
+            let status = RbacRoleParentStatus::Enable as i8;
+            let mut idata = Vec::with_capacity(add_item.len());
+            for (role_id, parent_role_id, depth) in add_item.iter() {
+                idata.push(model_option_set!(RbacRoleParentModelRef,{
+                    role_id:*role_id,
+                    parent_role_id:*parent_role_id,
+                    depth:*depth,
+                    change_user_id:change_user_id,
+                    change_time:time,
+                    status:status,
+                }));
+            }
+            Insert::<sqlx::MySql, RbacRoleParentModel, _>::new_vec(idata)
+                .execute(&mut *db)
+                .await?;
+        }
+        Ok((
+            affected.into_iter().collect(),
+            parent_ids.into_iter().collect(),
+        ))
+    }
+    //指定角色的操作ID
+    async fn inherit_op_ids<'t>(
+        &self,
+        role_ids: &[u64],
+        db: &mut Transaction<'t, MySql>,
+    ) -> UserRbacResult<Vec<u64>> {
+        if role_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = sql_format!(
+            "select distinct res_op_id from {} where role_id in ({}) and status={}",
+            RbacRoleOpModel::table_name(),
+            role_ids,
+            RbacRoleOpStatus::Enable,
+        );
+        Ok(sqlx::query_scalar::<_, u64>(&sql)
+            .fetch_all(&mut *db)
+            .await?)
+    }
+    /// 移除角色的继承关系,角色删除或不再为 AllowCustom 时调用
+    /// 需在删除角色操作前调用,返回受影响的下级角色ID及需清理缓存的操作ID
+    pub(crate) async fn inherit_remove<'t>(
+        &self,
+        role: &RbacRoleModel,
+        change_user_id: u64,
+        db: &mut Transaction<'t, MySql>,
+    ) -> UserRbacResult<(Vec<u64>, Vec<u64>)> {
+        let sql = sql_format!(
+            "select * from {} where (role_id={} or parent_role_id={}) and status={}",
+            RbacRoleParentModel::table_name(),
+            role.id,
+            role.id,
+            RbacRoleParentStatus::Enable,
+        );
+        let rows = sqlx::query_as::<_, RbacRoleParentModel>(&sql)
+            .fetch_all(&mut *db)
+            .await?;
+        if rows.is_empty() {
+            return Ok((vec![], vec![]));
+        }
+        let mut set = vec![(role.id, vec![])];
+        for row in rows
+            .iter()
+            .filter(|e| e.parent_role_id == role.id && e.depth == 1)
+        {
+            let parents = self.role_get_parent_ids(row.role_id, db).await?;
+            set.push((
+                row.role_id,
+                parents.into_iter().filter(|e| *e != role.id).collect(),
+            ));
+        }
+        let (affected, mut parent_ids) = self
+            .inherit_rebuild(role.user_id, &set, change_user_id, db)
+            .await?;
+        parent_ids.push(role.id);
+        let op_ids = self.inherit_op_ids(&parent_ids, db).await?;
+        Ok((
+            affected.into_iter().filter(|e| *e != role.id).collect(),
+            op_ids,
+        ))
+    }
+    //角色的直接父角色ID
+    async fn role_get_parent_ids<'t>(
+        &self,
+        role_id: u64,
+        db: &mut Transaction<'t, MySql>,
+    ) -> UserRbacResult<Vec<u64>> {
+        let sql = sql_format!(
+            "select parent_role_id from {} where role_id={} and depth=1 and status={}",
+            RbacRoleParentModel::table_name(),
+            role_id,
+            RbacRoleParentStatus::Enable,
+        );
+        Ok(sqlx::query_scalar::<_, u64>(&sql)
+            .fetch_all(&mut *db)
+            .await?)
+    }
+    /// 清理角色继承的操作相关缓存
+    /// * `role_ids` - 继承的操作发生变化的角色
+    /// * `op_ids` - 变化的资源操作ID
+    pub(crate) async fn inherit_cache_clear(
+        &self,
+        role_ids: &[u64],
+        op_ids: &[u64],
+    ) -> UserRbacResult<()> {
+        if role_ids.is_empty() || op_ids.is_empty() {
+            return Ok(());
+        }
+        let roles = self.find_by_ids(role_ids).await?;
+        for role in roles.values() {
+            if !RbacRoleResOpRange::AllowCustom.eq(role.res_op_range) {
+                continue;
+            }
+            //public-res-{RbacRoleUserRange}-{yaf_rbac_role_op.op_id}
+            if RbacRoleUserRange::AllUser.eq(role.user_range)
+                || RbacRoleUserRange::Login.eq(role.user_range)
+            {
+                for op_id in op_ids {
+                    self.cache_access
+                        .clear(&self.find_role_cache_key_by_public_res(
+                            role.user_range,
+                            *op_id,
+                            role.user_id,
+                        ))
+                        .await;
+                }
+            }
+            //access-relation-{role.user_id}-{relation_key}
+            if RbacRoleUserRange::Relation.eq(role.user_range) {
+                self.cache_relation
+                    .clear(
+                        &RoleRelationKey {
+                            relation_key: role.relation_key.clone(),
+                            user_id: role.user_id,
+                        }
+                        .to_string(),
+                    )
+                    .await;
+            }
+            //user-res-{view.user_id}-{role_op.id}
+            if RbacRoleUserRange::User.eq(role.user_range) {
+                let role_users = Select::type_new::<RbacRoleUserModel>()
+                    .fetch_all_by_where::<RbacRoleUserModel, _>(
+                        &sqlx_model::WhereOption::Where(sql_format!(
+                            "role_id={} and status={}",
+                            role.id,
+                            RbacRoleUserStatus::Enable
+                        )),
+                        &self.db,
+                    )
+                    .await?;
+                for ru in role_users.iter() {
+                    for op_id in op_ids {
+                        self.cache_access
+                            .clear(&self.find_role_cache_key_by_user_res(
+                                ru.user_id,
+                                *op_id,
+                                role.user_id,
+                            ))
+                            .await;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    /// 设置角色的直接父角色,角色继承父角色及其上级角色的资源操作
+    /// 父角色需跟角色属于同一用户且均为 AllowCustom 角色
+    /// * `parent_ids` - 父角色ID,为空时取消继承
+    pub async fn role_set_parents<'t>(
+        &self,
+        role: &RbacRoleModel,
+        parent_ids: &[u64],
+        change_user_id: u64,
+        transaction: Option<&mut Transaction<'t, sqlx::MySql>>,
+        env_data: Option<&RequestEnv>,
+    ) -> UserRbacResult<()> {
+        if !RbacRoleResOpRange::AllowCustom.eq(role.res_op_range) {
+            return Err(UserRbacError::System(
+                fluent_message!("rbac-res-op-range-wrong",{
+                    "name":&role.name,
+                    "role_id":role.id,
+                    "range":role.res_op_range
+                }),
+            ));
+        }
+        let mut parent_ids = parent_ids.to_owned();
+        parent_ids.sort();
+        parent_ids.dedup();
+
+        let mut db = match transaction {
+            Some(pb) => pb.begin().await?,
+            None => self.db.begin().await?,
+        };
+        //在设置的事务中查询,可使用同一事务中新增的角色
+        let parents = if parent_ids.is_empty() {
+            vec![]
+        } else {
+            match Select::type_new::<RbacRoleModel>()
+                .fetch_all_by_where::<RbacRoleModel, _>(
+                    &sqlx_model::WhereOption::Where(sql_format!(
+                        "id in ({}) and status={}",
+                        parent_ids,
+                        RbacRoleStatus::Enable
+                    )),
+                    &mut db,
+                )
+                .await
+            {
+                Ok(tmp) => tmp,
+                Err(err) => {
+                    db.rollback().await?;
+                    return Err(err.into());
+                }
+            }
+        };
+        for pid in parent_ids.iter() {
+            let err = match parents.iter().find(|e| e.id == *pid) {
+                None => Some(fluent_message!("rbac-role-parent-miss",{
+                    "id":pid
+                })),
+                Some(parent)
+                    if parent.id == role.id
+                        || parent.user_id != role.user_id
+                        || !RbacRoleResOpRange::AllowCustom.eq(parent.res_op_range) =>
+                {
+                    Some(fluent_message!("rbac-role-parent-wrong",{
+                        "name":&parent.name,
+                        "id":parent.id
+                    }))
+                }
+                Some(_) => None,
+            };
+            if let Some(err) = err {
+                db.rollback().await?;
+                return Err(UserRbacError::System(err));
+            }
+        }
+        let (affected, op_ids) = match self
+            .inherit_rebuild(
+                role.user_id,
+                &[(role.id, parent_ids.clone())],
+                change_user_id,
+                &mut db,
+            )
+            .await
+        {
+            Ok((affected, parent_ids)) => match self.inherit_op_ids(&parent_ids, &mut db).await {
+                Ok(op_ids) => (affected, op_ids),
+                Err(err) => {
+                    db.rollback().await?;
+                    return Err(err);
+                }
+            },
+            Err(err) => {
+                db.rollback().await?;
+                return Err(err);
+            }
+        };
+        db.commit().await?;
+
+        //cache clean----------------------------
+        self.inherit_cache_clear(&affected, &op_ids).await?;
+        //cache clean----------------------------
+
+        self.logger
+            .add(
+                &LogRoleParent {
+                    name: role.name.to_owned(),
+                    parents: parents.iter().map(|e| (e.id, e.name.to_owned())).collect(),
+                },
+                &Some(role.id),
+                &Some(role.user_id),
+                &Some(change_user_id),
+                None,
+                env_data,
+            )
+            .await;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_inherit_closure() {
+    // 1 -> 2 -> 3, 1 -> 3, 4 -> 1
+    let mut direct = BTreeMap::new();
+    direct.insert(1, vec![2, 3]);
+    direct.insert(2, vec![3]);
+    direct.insert(4, vec![1]);
+    let closure = inherit_closure(&direct, 1);
+    assert_eq!(
+        closure.into_iter().collect::<Vec<_>>(),
+        vec![(2, 1), (3, 1)]
+    );
+    let closure = inherit_closure(&direct, 4);
+    assert_eq!(
+        closure.into_iter().collect::<Vec<_>>(),
+        vec![(1, 1), (2, 2), (3, 2)]
+    );
+    assert_eq!(inherit_children(&direct, 3), vec![1, 2, 4]);
+    assert!(inherit_children(&direct, 4).is_empty());
+    //存在环时包含自身
+    direct.insert(3, vec![4]);
+    assert!(inherit_closure(&direct, 1).contains_key(&1));
+}
+
+#[test]
+fn test_inherit_merge_ops() {
+    use crate::model::RbacRoleOpPositivity;
+    let op = |role_id: u64, res_op_id: u64, positivity: RbacRoleOpPositivity| RbacRoleOpModel {
+        id: 0,
+        res_op_id,
+        role_id,
+        positivity: positivity as i8,
+        status: RbacRoleOpStatus::Enable as i8,
+        change_user_id: 0,
+        change_time: 0,
+    };
+    let own = vec![op(1, 10, RbacRoleOpPositivity::Allow)];
+    let inherit = vec![
+        (1, op(2, 10, RbacRoleOpPositivity::Deny)),
+        (2, op(3, 11, RbacRoleOpPositivity::Deny)),
+        (1, op(4, 11, RbacRoleOpPositivity::Allow)),
+        (1, op(2, 12, RbacRoleOpPositivity::Allow)),
+        (1, op(4, 12, RbacRoleOpPositivity::Deny)),
+    ];
+    let ops = inherit_merge_ops(1, own, inherit)
+        .into_iter()
+        .map(|e| (e.role_id, e.res_op_id, e.positivity))
+        .collect::<Vec<_>>();
+    //自身操作优先,层级近的优先,层级相同时禁止优先
+    assert_eq!(
+        ops,
+        vec![
+            (1, 10, RbacRoleOpPositivity::Allow as i8),
+            (1, 12, RbacRoleOpPositivity::Deny as i8),
+            (1, 11, RbacRoleOpPositivity::Allow as i8),
+        ]
+    );
+}
@@ -5,7 +5,7 @@ use serde::Serialize;
 
 use crate::model::{RbacRoleResOpRange, RbacRoleUserRange, RbacTagsSource};
 
-use super::{ResOp, RoleAddUser, RoleSetOp};
+use super::{RbacTransferChange, ResOp, RoleAddUser, RoleSetOp};
 
 #[derive(Serialize)]
 pub(crate) struct LogTag {
@@ -93,6 +93,7 @@ impl ChangeLogData for LogRole {
 pub(crate) enum LogRoleUserAction {
     Add,
     Del,
+    Expire, //超时后自动删除
 }
 // impl Display for LogRoleUserAction {
 //     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -123,6 +124,7 @@ impl ChangeLogData for LogRoleUser {
         let action_str = match self.action {
             LogRoleUserAction::Add => "add",
             LogRoleUserAction::Del => "del",
+            LogRoleUserAction::Expire => "expire",
         };
         format!(
             "{} {} :{} ",
@@ -135,6 +137,9 @@ impl ChangeLogData for LogRoleUser {
                 LogRoleUserAction::Del => {
                     format!("del user:{:?}", self.del_user)
                 }
+                LogRoleUserAction::Expire => {
+                    format!("expire user:{:?}", self.del_user)
+                }
             }
         )
     }
@@ -160,3 +165,44 @@ impl ChangeLogData for LogRoleOp {
         serde_json::to_string(&self).unwrap_or_default()
     }
 }
+
+#[derive(Serialize)]
+pub(crate) struct LogRoleParent {
+    pub name: String,
+    pub parents: Vec<(u64, String)>,
+}
+
+impl ChangeLogData for LogRoleParent {
+    fn log_type<'t>() -> &'t str {
+        "rbac-role-parent"
+    }
+    fn message(&self) -> String {
+        format!("set role parent {} parent:{:?} ", self.name, self.parents)
+    }
+    fn encode(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct LogTransfer {
+    pub user_id: u64,
+    pub remove_miss: bool,
+    pub changes: Vec<RbacTransferChange>,
+}
+
+impl ChangeLogData for LogTransfer {
+    fn log_type<'t>() -> &'t str {
+        "rbac-transfer"
+    }
+    fn message(&self) -> String {
+        format!(
+            "import rbac data to user {} change:{}",
+            self.user_id,
+            self.changes.len()
+        )
+    }
+    fn encode(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+}
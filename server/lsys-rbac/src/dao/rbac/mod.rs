@@ -8,25 +8,37 @@ pub use access::*;
 pub use cache::*;
 pub use check::*;
 pub use data::*;
+pub use event::*;
+pub use explain::*;
+pub use expire::*;
+pub use inherit::*;
 use logger::*;
 use lsys_core::cache:: LocalCacheConfig;
 use lsys_core::RemoteNotify;
 use lsys_logger::dao::ChangeLogger;
 pub use res::*;
+pub use reverse::*;
 pub use role::*;
 use sqlx::{MySql, Pool};
 pub use tags::*;
+pub use transfer::*;
 
 mod access;
 mod cache;
 mod check;
 mod data;
+mod event;
+mod explain;
+mod expire;
+mod inherit;
 mod res;
 // mod res_tpl;
 mod logger;
 mod result;
+mod reverse;
 mod role;
 mod tags;
+mod transfer;
 pub use result::*;
 
 pub const PRIORITY_MAX: i8 = 100;
@@ -54,6 +66,7 @@ pub struct Rbac {
     pub role: Arc<RbacRole>,
     pub access: Arc<RbacAccess>,
     pub data: Arc<RbacData>,
+    pub transfer: Arc<RbacTransfer>,
     // pub(crate) role_relation_cache: Arc<LocalCache<String, Option<RoleDetailRow>>>,
     // pub(crate) role_access_cache: Arc<LocalCache<String, Option<RoleAccessRow>>>,
     // pub(crate) res_key_cache: Arc<LocalCache<ResKey, Option<RbacResData>>>,
@@ -78,13 +91,13 @@ impl Rbac {
             logger.clone(),
         ));
         let res = Arc::from(RbacRes::new(
-            db,
+            db.clone(),
             // fluent.clone(),
             tags.clone(),
             role.clone(),
             remote_notify.clone(),
             config.res_cache,
-            logger,
+            logger.clone(),
         ));
         let access = Arc::from(RbacAccess::new(
             // fluent,
@@ -92,12 +105,14 @@ impl Rbac {
             role.clone(),
             system_role,
         ));
+        let transfer = Arc::from(RbacTransfer::new(db, res.clone(), role.clone(), logger));
         let data = Arc::from(RbacData::new(res.clone(), role.clone(), tags));
         Rbac {
             res,
             role,
             access,
             data,
+            transfer,
             // res_key_cache,
             // role_relation_cache,
             // role_access_cache,
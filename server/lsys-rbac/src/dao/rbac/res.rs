@@ -15,6 +15,7 @@ use sqlx_model::{
     SqlQuote, Update,
 };
 use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::vec;
@@ -48,9 +49,9 @@ pub struct ResOp {
     pub key: String,  //操作key
 }
 
-impl ToString for ResKey {
-    fn to_string(&self) -> String {
-        format!("{}-{}", self.user_id, self.res_key)
+impl Display for ResKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.user_id, self.res_key)
     }
 }
 
@@ -2,6 +2,7 @@
 // use std::fmt::{Display, Formatter};
 
 use lsys_core::{fluent_message, FluentMessage, IntoFluentMessage};
+use lsys_logger::dao::LoggerError;
 
 #[derive(Debug)]
 pub enum UserRbacError {
@@ -43,3 +44,10 @@ impl From<sqlx::Error> for UserRbacError {
         UserRbacError::Sqlx(err)
     }
 }
+impl From<LoggerError> for UserRbacError {
+    fn from(err: LoggerError) -> Self {
+        match err {
+            LoggerError::Sqlx(err) => UserRbacError::Sqlx(err),
+        }
+    }
+}
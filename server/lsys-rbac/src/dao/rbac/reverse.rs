@@ -0,0 +1,245 @@
+use lsys_core::{fluent_message, PageParam};
+use serde::Serialize;
+
+use crate::model::{RbacRoleModel, RbacRoleOpPositivity, RbacRoleUserRange};
+
+use super::{AccessRes, RbacAccess, RbacResData, RoleAccessRow, UserRbacError, UserRbacResult};
+
+//资源操作的授权对象
+#[derive(Clone, Debug, Serialize)]
+pub struct OpAccessItem {
+    pub user_range: i8,        //授权对象类型,参见 RbacRoleUserRange
+    pub user_id: u64,          //指定用户时的用户ID
+    pub relation_key: String,  //关系角色时的关系KEY
+    pub relation_user_id: u64, //关系角色时的角色所属用户ID
+    pub allow: bool,           //合并优先级后为授权或禁止
+    pub role: RbacRoleModel,   //决定结果的角色
+    pub timeout: u64,          //决定结果的为指定用户角色时的授权超时时间,0为不超时
+}
+
+impl OpAccessItem {
+    fn new(user_range: RbacRoleUserRange, row: RoleAccessRow) -> Self {
+        Self {
+            user_range: user_range as i8,
+            user_id: 0,
+            relation_key: String::new(),
+            relation_user_id: 0,
+            allow: row.op_positivity == RbacRoleOpPositivity::Allow,
+            role: row.role,
+            timeout: row.timeout,
+        }
+    }
+}
+
+//按检测时的合并顺序传入各来源的角色,后合并的角色优先级更高时替换已有结果
+fn op_access_winner(rows: Vec<Option<RoleAccessRow>>) -> Option<RoleAccessRow> {
+    let mut winner: Option<RoleAccessRow> = None;
+    for row in rows.into_iter().flatten() {
+        if winner
+            .as_ref()
+            .map(|e| row.role.priority > e.role.priority)
+            .unwrap_or(true)
+        {
+            winner = Some(row);
+        }
+    }
+    winner
+}
+
+impl RbacAccess {
+    //查询资源操作,返回的资源数据中仅包含该操作
+    async fn find_op_res(
+        &self,
+        res: &str,
+        res_user_id: u64,
+        op: &str,
+    ) -> UserRbacResult<RbacResData> {
+        let res_data = self
+            .find_option_res(&[AccessRes::user(res_user_id, res, &[op], &[])])
+            .await?;
+        res_data
+            .into_iter()
+            .find_map(|e| {
+                let mut find_res = e.find_res?;
+                find_res.ops.retain(|t| t.op_key == op);
+                if find_res.ops.is_empty() {
+                    None
+                } else {
+                    Some(find_res)
+                }
+            })
+            .ok_or_else(|| {
+                UserRbacError::System(fluent_message!("rbac-access-check-res-empty",{
+                    "res":res,
+                    "op":op,
+                    "user_id":res_user_id,
+                    "view_user_id":0
+                }))
+            })
+    }
+    //同检测时的规则,取各来源中作用于该资源操作的角色
+    fn op_first_role(
+        &self,
+        rows: &[RoleAccessRow],
+        res_data: &RbacResData,
+    ) -> Option<RoleAccessRow> {
+        self.role
+            .access_role_candidates(rows, res_data, &res_data.ops[0])
+            .first()
+            .map(|e| (*e).to_owned())
+    }
+    //游客及登录用户作用于该资源操作的角色
+    async fn op_public_role(
+        &self,
+        res_data: &RbacResData,
+    ) -> UserRbacResult<(Option<RoleAccessRow>, Option<RoleAccessRow>)> {
+        let check_vec = std::slice::from_ref(res_data);
+        let rbac_role = self.role.cache();
+        let (all_user, login) = tokio::try_join!(
+            rbac_role.find_role_access_by_public(RbacRoleUserRange::AllUser, check_vec),
+            rbac_role.find_role_access_by_public(RbacRoleUserRange::Login, check_vec),
+        )?;
+        Ok((
+            self.op_first_role(&all_user, res_data),
+            self.op_first_role(&login, res_data),
+        ))
+    }
+    /// 查询游客,登录用户及各关系角色对指定资源操作的授权
+    /// 结果为合并优先级后的授权或禁止,未匹配任何角色的不返回
+    /// 关系角色按登录用户合并,系统内置角色依赖访问用户,不在结果中
+    /// * `res` - 资源KEY
+    /// * `res_user_id` - 资源所属用户ID
+    /// * `op` - 操作KEY
+    pub async fn op_access_range(
+        &self,
+        res: &str,
+        res_user_id: u64,
+        op: &str,
+    ) -> UserRbacResult<Vec<OpAccessItem>> {
+        let res_data = self.find_op_res(res, res_user_id, op).await?;
+        let res_op = &res_data.ops[0];
+        let (all_user, login) = self.op_public_role(&res_data).await?;
+        let mut out = vec![];
+        if let Some(row) = op_access_winner(vec![all_user.clone()]) {
+            out.push(OpAccessItem::new(RbacRoleUserRange::AllUser, row));
+        }
+        if let Some(row) = op_access_winner(vec![all_user.clone(), login.clone()]) {
+            out.push(OpAccessItem::new(RbacRoleUserRange::Login, row));
+        }
+        let rbac_role = self.role.cache();
+        for relation in self.role.op_relation_keys(res_user_id, res_op.id).await? {
+            let role_data = rbac_role
+                .find_role_detail_by_relation(std::slice::from_ref(&relation))
+                .await?;
+            let relation_row = self
+                .role
+                .relation_role_candidates(&role_data, &res_data, res_op)
+                .into_iter()
+                .next()
+                .map(|(role, op_positivity)| RoleAccessRow {
+                    role,
+                    res_op_id: res_op.id,
+                    op_positivity,
+                    timeout: 0,
+                });
+            if let Some(row) = op_access_winner(vec![relation_row, all_user.clone(), login.clone()])
+            {
+                let mut item = OpAccessItem::new(RbacRoleUserRange::Relation, row);
+                item.relation_key = relation.relation_key;
+                item.relation_user_id = relation.user_id;
+                out.push(item);
+            }
+        }
+        Ok(out)
+    }
+    /// 通过指定用户角色作用于指定资源操作的用户数量
+    /// * `res` - 资源KEY
+    /// * `res_user_id` - 资源所属用户ID
+    /// * `op` - 操作KEY
+    pub async fn op_access_user_count(
+        &self,
+        res: &str,
+        res_user_id: u64,
+        op: &str,
+    ) -> UserRbacResult<i64> {
+        let res_data = self.find_op_res(res, res_user_id, op).await?;
+        self.role
+            .op_role_user_count(res_user_id, res_data.ops[0].id)
+            .await
+    }
+    /// 查询通过指定用户角色作用于指定资源操作的用户,按用户ID升序分页
+    /// 结果为该用户合并游客及登录用户角色优先级后的授权或禁止
+    /// 分页按符合条件的用户ID进行,合并后无结果的用户不返回,
+    /// 因此返回数量可能少于分页数量,总数及是否有下一页以 op_access_user_count 为准
+    /// * `res` - 资源KEY
+    /// * `res_user_id` - 资源所属用户ID
+    /// * `op` - 操作KEY
+    pub async fn op_access_user(
+        &self,
+        res: &str,
+        res_user_id: u64,
+        op: &str,
+        page: &Option<PageParam>,
+    ) -> UserRbacResult<Vec<OpAccessItem>> {
+        let res_data = self.find_op_res(res, res_user_id, op).await?;
+        let user_ids = self
+            .role
+            .op_role_user_ids(res_user_id, res_data.ops[0].id, page)
+            .await?;
+        if user_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let (all_user, login) = self.op_public_role(&res_data).await?;
+        let rbac_role = self.role.cache();
+        let mut out = Vec::with_capacity(user_ids.len());
+        for user_id in user_ids {
+            let user_data = rbac_role
+                .find_role_access_by_user(user_id, std::slice::from_ref(&res_data))
+                .await?;
+            let user_row = self.op_first_role(&user_data, &res_data);
+            if let Some(row) = op_access_winner(vec![all_user.clone(), login.clone(), user_row]) {
+                let mut item = OpAccessItem::new(RbacRoleUserRange::User, row);
+                item.user_id = user_id;
+                out.push(item);
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[test]
+fn test_op_access_winner() {
+    let row = |id: u64, priority: i8, op_positivity: RbacRoleOpPositivity| {
+        Some(RoleAccessRow {
+            role: RbacRoleModel {
+                id,
+                user_id: 0,
+                name: String::new(),
+                relation_key: String::new(),
+                priority,
+                user_range: RbacRoleUserRange::User as i8,
+                res_op_range: crate::model::RbacRoleResOpRange::AllowCustom as i8,
+                status: 1,
+                change_user_id: 0,
+                change_time: 0,
+            },
+            res_op_id: 1,
+            op_positivity,
+            timeout: 0,
+        })
+    };
+    assert!(op_access_winner(vec![None, None]).is_none());
+    //优先级相同时保留先合并的角色
+    let tmp = op_access_winner(vec![
+        row(1, 10, RbacRoleOpPositivity::Allow),
+        None,
+        row(2, 10, RbacRoleOpPositivity::Deny),
+    ]);
+    assert_eq!(tmp.map(|e| e.role.id), Some(1));
+    let tmp = op_access_winner(vec![
+        row(1, 10, RbacRoleOpPositivity::Allow),
+        row(2, 50, RbacRoleOpPositivity::Deny),
+        row(3, 20, RbacRoleOpPositivity::Allow),
+    ]);
+    assert_eq!(tmp.map(|e| e.role.id), Some(2));
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::{BTreeMap, HashMap},
+    fmt::Display,
     sync::Arc,
     vec,
 };
@@ -26,7 +27,8 @@ use crate::model::{
 
 use super::{
     logger::{LogRole, LogRoleOp, LogRoleUser, LogRoleUserAction},
-    RbacResData, RbacTags, RoleCheckData, RoleCheckRow, UserRbacError, UserRbacResult,
+    access_cache_time, RbacResData, RbacRoleChangeEvent, RbacTags, RoleCheckData, RoleCheckRow, UserRbacError,
+    UserRbacResult,
 };
 
 pub const ROLE_PRIORITY_NONE: i8 = -1;
@@ -52,9 +54,9 @@ impl RoleRelationKey {
         }
     }
 }
-impl ToString for RoleRelationKey {
-    fn to_string(&self) -> String {
-        format!("access-relation-{}-{}", self.user_id, self.relation_key)
+impl Display for RoleRelationKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "access-relation-{}-{}", self.user_id, self.relation_key)
     }
 }
 
@@ -74,17 +76,18 @@ pub struct RoleAccessRow {
 
 //角色管理
 pub struct RbacRole {
-    db: Pool<MySql>,
+    pub(crate) db: Pool<MySql>,
     tags: Arc<RbacTags>,
     pub(crate) cache_relation: Arc<LocalCache<String, Option<RoleDetailRow>>>,
     pub(crate) cache_access: Arc<LocalCache<String, Option<RoleAccessRow>>>,
-    logger: Arc<ChangeLogger>,
+    pub(crate) logger: Arc<ChangeLogger>,
 }
 
 #[derive(Clone, Debug, Serialize)]
 pub struct RoleAddUser {
     pub user_id: u64,
-    pub timeout: u64, //换成时间不超过此值，查询时要有此值
+    pub start_time: u64, //生效时间,0为立即生效
+    pub timeout: u64,    //换成时间不超过此值，查询时要有此值
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -553,7 +556,7 @@ impl RbacRole {
 
     /// 编辑角色
     #[allow(clippy::too_many_arguments)]
-    async fn inner_edit_role<'t>(
+    pub(crate) async fn inner_edit_role<'t>(
         &self,
         role: &RbacRoleModel,
         name: Option<String>,
@@ -642,7 +645,7 @@ impl RbacRole {
                 .map(|e| e != RbacRoleResOpRange::AllowCustom)
                 .unwrap_or(false)
         {
-            Select::type_new::<RbacRoleOpModel>()
+            let mut tmp = Select::type_new::<RbacRoleOpModel>()
                 .fetch_all_by_where::<RbacRoleOpModel, _>(
                     &WhereOption::Where(sql_format!(
                         "role_id={} and status={}",
@@ -651,7 +654,15 @@ impl RbacRole {
                     )),
                     &self.db,
                 )
-                .await?
+                .await?;
+            //继承的操作
+            tmp.extend(
+                self.inherit_parent_ops(&[role.id])
+                    .await?
+                    .into_iter()
+                    .map(|(_, _, op)| op),
+            );
+            tmp
         } else {
             vec![]
         };
@@ -681,11 +692,20 @@ impl RbacRole {
                 return Err(e)?;
             }
         }
+        let mut inherit_change = (vec![], vec![]);
         if RbacRoleResOpRange::AllowCustom.eq(role.res_op_range)
             && res_op_range
                 .map(|e| e != RbacRoleResOpRange::AllowCustom)
                 .unwrap_or(false)
         {
+            //不再为自定义时移除继承关系
+            match self.inherit_remove(role, change_user_id, &mut db).await {
+                Ok(tmp) => inherit_change = tmp,
+                Err(e) => {
+                    db.rollback().await?;
+                    return Err(e);
+                }
+            }
             let change_role_op = sqlx_model::model_option_set!(RbacRoleOpModelRef,{
                 change_user_id:change_user_id,
                 change_time:time,
@@ -708,9 +728,31 @@ impl RbacRole {
             db.rollback().await?;
             return Err(e)?;
         }
+        let tmp = self
+            .logger
+            .add_event(
+                &RbacRoleChangeEvent {
+                    action: "edit".to_string(),
+                    role_id: role.id,
+                    user_id: role.user_id,
+                    name: name.clone().unwrap_or_else(|| role.name.clone()),
+                },
+                &Some(role.id),
+                &Some(role.user_id),
+                Some(&mut db),
+            )
+            .await;
+        if let Err(e) = tmp {
+            db.rollback().await?;
+            return Err(e)?;
+        }
         db.commit().await?;
+        self.logger.notify_event().await;
 
         //cache clean----------------------------
+        //继承该角色的下级角色
+        self.inherit_cache_clear(&inherit_change.0, &inherit_change.1)
+            .await?;
         //public-global-{RbacRoleUserRange}
         if res_op_range
             .map(|e| e == RbacRoleResOpRange::AllowAll || e == RbacRoleResOpRange::DenyAll)
@@ -985,7 +1027,7 @@ impl RbacRole {
         };
 
         let change_op = if RbacRoleResOpRange::AllowCustom.eq(role.res_op_range) {
-            Select::type_new::<RbacRoleOpModel>()
+            let mut tmp = Select::type_new::<RbacRoleOpModel>()
                 .fetch_all_by_where::<RbacRoleOpModel, _>(
                     &WhereOption::Where(sql_format!(
                         "role_id={} and status={}",
@@ -994,7 +1036,15 @@ impl RbacRole {
                     )),
                     &self.db,
                 )
-                .await?
+                .await?;
+            //继承的操作
+            tmp.extend(
+                self.inherit_parent_ops(&[role.id])
+                    .await?
+                    .into_iter()
+                    .map(|(_, _, op)| op),
+            );
+            tmp
         } else {
             vec![]
         };
@@ -1010,6 +1060,18 @@ impl RbacRole {
             db.rollback().await?;
             return Err(e)?;
         }
+        //移除继承关系
+        let inherit_change = if RbacRoleResOpRange::AllowCustom.eq(role.res_op_range) {
+            match self.inherit_remove(role, delete_user_id, &mut db).await {
+                Ok(tmp) => tmp,
+                Err(e) => {
+                    db.rollback().await?;
+                    return Err(e);
+                }
+            }
+        } else {
+            (vec![], vec![])
+        };
         let tmp = self
             .tags
             .del_tags(
@@ -1060,8 +1122,30 @@ impl RbacRole {
                 return Err(e)?;
             }
         }
+        let tmp = self
+            .logger
+            .add_event(
+                &RbacRoleChangeEvent {
+                    action: "del".to_string(),
+                    role_id: role.id,
+                    user_id: role.user_id,
+                    name: role.name.clone(),
+                },
+                &Some(role.id),
+                &Some(role.user_id),
+                Some(&mut db),
+            )
+            .await;
+        if let Err(e) = tmp {
+            db.rollback().await?;
+            return Err(e)?;
+        }
         db.commit().await?;
+        self.logger.notify_event().await;
         //cache clean----------------------------
+        //继承该角色的下级角色
+        self.inherit_cache_clear(&inherit_change.0, &inherit_change.1)
+            .await?;
         //public-global-{RbacRoleUserRange}
         if (RbacRoleUserRange::AllUser.eq(role.user_range)
             || RbacRoleUserRange::Login.eq(role.user_range))
@@ -1174,6 +1258,17 @@ impl RbacRole {
                 }), //"role({$name})[range:{$range}] can't set user [{$role_id}]",
             ));
         }
+        for tmp in user_vec.iter() {
+            if tmp.timeout > 0 && tmp.start_time >= tmp.timeout {
+                return Err(UserRbacError::System(
+                    fluent_message!("rbac-role-user-time-wrong",{
+                        "user_id":tmp.user_id,
+                        "start_time":tmp.start_time,
+                        "timeout":tmp.timeout
+                    }),
+                ));
+            }
+        }
         let db = &self.db;
 
         let user_id_vec = user_vec.iter().map(|e| e.user_id).collect::<Vec<_>>();
@@ -1194,11 +1289,15 @@ impl RbacRole {
 
         let mut add_item = vec![];
         let mut add_uids = vec![];
-        for RoleAddUser { user_id, timeout } in user_vec.iter() {
-            if res
-                .iter()
-                .any(|x| x.user_id == *user_id && x.timeout == *timeout)
-            {
+        for RoleAddUser {
+            user_id,
+            start_time,
+            timeout,
+        } in user_vec.iter()
+        {
+            if res.iter().any(|x| {
+                x.user_id == *user_id && x.start_time == *start_time && x.timeout == *timeout
+            }) {
                 continue;
             }
             let mut item = model_option_set!(RbacRoleUserModelRef,{
@@ -1208,6 +1307,7 @@ impl RbacRole {
                 status:(RbacRoleUserStatus::Enable as i8),
             });
             item.user_id = Some(user_id);
+            item.start_time = Some(start_time);
             item.timeout = Some(timeout);
             add_item.push(item);
             add_uids.push(*user_id);
@@ -1242,7 +1342,7 @@ impl RbacRole {
             && RbacRoleResOpRange::AllowCustom.eq(role.res_op_range)
             && !add_uids.is_empty()
         {
-            let change_op = Select::type_new::<RbacRoleOpModel>()
+            let mut change_op = Select::type_new::<RbacRoleOpModel>()
                 .fetch_all_by_where::<RbacRoleOpModel, _>(
                     &WhereOption::Where(sql_format!(
                         "role_id={} and status={}",
@@ -1252,6 +1352,13 @@ impl RbacRole {
                     &self.db,
                 )
                 .await?;
+            //继承的操作
+            change_op.extend(
+                self.inherit_parent_ops(&[role.id])
+                    .await?
+                    .into_iter()
+                    .map(|(_, _, op)| op),
+            );
             for uid in add_uids.iter() {
                 for tmp_op in change_op.iter() {
                     self.cache_access
@@ -1321,6 +1428,31 @@ impl RbacRole {
             db,
             db
         );
+        self.role_user_cache_clear(role, user_id_vec).await?;
+
+        self.logger
+            .add(
+                &LogRoleUser {
+                    action: LogRoleUserAction::Del,
+                    name: role.name.clone(),
+                    add_user: None,
+                    del_user: Some(user_id_vec.to_owned()),
+                },
+                &Some(role.id),
+                &Some(role.user_id),
+                &Some(del_user_id),
+                None,
+                env_data,
+            )
+            .await;
+        Ok(res.rows_affected())
+    }
+    //清理角色用户的授权缓存
+    pub(crate) async fn role_user_cache_clear(
+        &self,
+        role: &RbacRoleModel,
+        user_id_vec: &[u64],
+    ) -> UserRbacResult<()> {
         //cache clean----------------------------
         // user-global-{view.user_id}
         if RbacRoleUserRange::User.eq(role.user_range)
@@ -1339,7 +1471,7 @@ impl RbacRole {
             && RbacRoleResOpRange::AllowCustom.eq(role.res_op_range)
             && !user_id_vec.is_empty()
         {
-            let change_op = Select::type_new::<RbacRoleOpModel>()
+            let mut change_op = Select::type_new::<RbacRoleOpModel>()
                 .fetch_all_by_where::<RbacRoleOpModel, _>(
                     &WhereOption::Where(sql_format!(
                         "role_id={} and status={}",
@@ -1349,6 +1481,13 @@ impl RbacRole {
                     &self.db,
                 )
                 .await?;
+            //继承的操作
+            change_op.extend(
+                self.inherit_parent_ops(&[role.id])
+                    .await?
+                    .into_iter()
+                    .map(|(_, _, op)| op),
+            );
             for uid in user_id_vec.iter() {
                 for tmp_op in change_op.iter() {
                     self.cache_access
@@ -1362,23 +1501,7 @@ impl RbacRole {
             }
         }
         //cache clean----------------------------
-
-        self.logger
-            .add(
-                &LogRoleUser {
-                    action: LogRoleUserAction::Del,
-                    name: role.name.clone(),
-                    add_user: None,
-                    del_user: Some(user_id_vec.to_owned()),
-                },
-                &Some(role.id),
-                &Some(role.user_id),
-                &Some(del_user_id),
-                None,
-                env_data,
-            )
-            .await;
-        Ok(res.rows_affected())
+        Ok(())
     }
     //汇总指定关系角色的用户数量
     pub async fn role_group_users(
@@ -1392,9 +1515,11 @@ impl RbacRole {
         let ok_where = if all {
             SqlExpr("".to_string())
         } else {
+            let time = now_time().unwrap_or(0);
             SqlExpr(sql_format!(
-                " and (timeout=0 or timeout>{})",
-                now_time().unwrap_or(0)
+                " and (timeout=0 or timeout>{}) and start_time<={}",
+                time,
+                time
             ))
         };
         let sql = sql_format!(
@@ -1495,6 +1620,97 @@ impl RbacRole {
         let res = query.fetch_one(&self.db).await?;
         Ok(res)
     }
+    //作用于指定资源操作且未过期的指定用户角色关系条件
+    //同检测时的查询:仅系统角色及资源所属用户的角色
+    fn op_role_user_where(&self, res_user_id: u64, op_id: u64) -> String {
+        sql_format!(
+            r#"FROM {rbac_role} as ro
+            join {rbac_role_user} as ru on ro.id =ru.role_id and ru.status ={role_user_status} and (ru.timeout>{timeout} or ru.timeout=0) and ru.start_time<={timeout}
+            where ro.user_range={role_user_range} and ro.status ={role_status} and ro.user_id in ({role_user_id})
+                and (ro.res_op_range IN ({role_res_op_range})
+                    or (ro.res_op_range={role_res_op_custom} and ro.id in (select role_id from {rbac_role_op} as rop)))"#,
+            rbac_role = RbacRoleModel::table_name(),
+            rbac_role_user = RbacRoleUserModel::table_name(),
+            rbac_role_op = SqlExpr(self.inherit_op_sql(op_id)),
+            role_user_status = RbacRoleUserStatus::Enable,
+            timeout = now_time().unwrap_or(0),
+            role_user_range = RbacRoleUserRange::User,
+            role_status = RbacRoleStatus::Enable,
+            role_user_id = vec![0, res_user_id],
+            role_res_op_range = [
+                RbacRoleResOpRange::AllowAll.sql_quote(),
+                RbacRoleResOpRange::DenyAll.sql_quote()
+            ],
+            role_res_op_custom = RbacRoleResOpRange::AllowCustom,
+        )
+    }
+    /// 通过指定用户角色作用于指定资源操作的用户ID,按用户ID升序
+    pub async fn op_role_user_ids(
+        &self,
+        res_user_id: u64,
+        op_id: u64,
+        page: &Option<PageParam>,
+    ) -> UserRbacResult<Vec<u64>> {
+        let mut sql = format!(
+            "select distinct ru.user_id {} order by ru.user_id asc",
+            self.op_role_user_where(res_user_id, op_id)
+        );
+        if let Some(pdat) = page {
+            sql += format!(" limit {} offset {}", pdat.limit, pdat.offset).as_str();
+        }
+        let query = sqlx::query_scalar::<_, u64>(&sql);
+        let res = query.fetch_all(&self.db).await?;
+        Ok(res)
+    }
+    /// 通过指定用户角色作用于指定资源操作的用户数量
+    pub async fn op_role_user_count(&self, res_user_id: u64, op_id: u64) -> UserRbacResult<i64> {
+        let sql = format!(
+            "select count(distinct ru.user_id) as total {}",
+            self.op_role_user_where(res_user_id, op_id)
+        );
+        let query = sqlx::query_scalar::<_, i64>(&sql);
+        let res = query.fetch_one(&self.db).await?;
+        Ok(res)
+    }
+    /// 作用于指定资源操作的关系角色KEY
+    /// 同检测时的查询:仅系统角色及资源所属用户的角色,
+    /// 包含全部授权或禁止的关系角色及通过指定操作或继承的操作授权的关系角色
+    /// * `res_user_id` - 资源所属用户ID
+    /// * `op_id` - 资源操作ID
+    pub async fn op_relation_keys(
+        &self,
+        res_user_id: u64,
+        op_id: u64,
+    ) -> UserRbacResult<Vec<RoleRelationKey>> {
+        let sql = sql_format!(
+            r#"select distinct ro.relation_key,ro.user_id
+            FROM {rbac_role} as ro
+            where ro.user_range={role_user_range} and ro.status ={role_status} and ro.user_id in ({role_user_id})
+                and (ro.res_op_range IN ({role_res_op_range})
+                    or (ro.res_op_range={role_res_op_custom} and ro.id in (select role_id from {rbac_role_op} as rop)))
+            order by ro.user_id asc,ro.relation_key asc"#,
+            rbac_role = RbacRoleModel::table_name(),
+            rbac_role_op = SqlExpr(self.inherit_op_sql(op_id)),
+            role_user_range = RbacRoleUserRange::Relation,
+            role_status = RbacRoleStatus::Enable,
+            role_user_id = vec![0, res_user_id],
+            role_res_op_range = [
+                RbacRoleResOpRange::AllowAll.sql_quote(),
+                RbacRoleResOpRange::DenyAll.sql_quote()
+            ],
+            role_res_op_custom = RbacRoleResOpRange::AllowCustom,
+        );
+        let res = sqlx::query_as::<_, (String, u64)>(&sql)
+            .fetch_all(&self.db)
+            .await?
+            .into_iter()
+            .map(|(relation_key, user_id)| RoleRelationKey {
+                relation_key,
+                user_id,
+            })
+            .collect::<Vec<_>>();
+        Ok(res)
+    }
     /// 角色获取用户数量
     pub async fn role_get_user_count(
         &self,
@@ -1518,7 +1734,7 @@ impl RbacRole {
         Ok(res)
     }
     /// 角色获取操作
-    pub async fn role_get_ops<'t>(
+    pub async fn role_get_ops(
         &self,
         role_ids: &[u64],
     ) -> UserRbacResult<BTreeMap<u64, Vec<RbacRoleOpModel>>> {
@@ -1668,28 +1884,14 @@ impl RbacRole {
 
         Ok(())
     }
-    /// 角色设置资源的操作
-    pub async fn role_set_ops<'t>(
+    //确认资源及操作存在且可被该角色使用,返回需删除及需添加的操作
+    //在设置操作的事务中查询,可使用同一事务中新增的资源
+    async fn role_set_ops_diff<'t>(
         &self,
         role: &RbacRoleModel,
         role_op_vec: &[RoleSetOp],
-        set_user_id: u64,
-        transaction: Option<&mut Transaction<'t, sqlx::MySql>>,
-        env_data: Option<&RequestEnv>,
-    ) -> UserRbacResult<()> {
-        if !RbacRoleResOpRange::AllowCustom.eq(role.res_op_range) {
-            return Err(UserRbacError::System(
-                fluent_message!("rbac-res-op-range-wrong",{
-                    "name":&role.name,
-                    "role_id":role.id,
-                    "range":role.res_op_range
-                }), //"role({$name})[range:{$range}] can't set ops [{$role_id}]",[
-            ));
-        }
-
-        let time = now_time().unwrap_or_default();
-        let db = &self.db;
-
+        db: &mut Transaction<'t, sqlx::MySql>,
+    ) -> UserRbacResult<(Vec<(u64, u64)>, Vec<(u64, i8)>)> {
         //确认资源都存在且属于当前角色用户
         let res_id = role_op_vec.iter().map(|e| e.res.id).collect::<Vec<_>>();
         let fres = if res_id.is_empty() {
@@ -1702,7 +1904,7 @@ impl RbacRole {
                         res_id,
                         RbacResStatus::Enable
                     )),
-                    db,
+                    &mut *db,
                 )
                 .await?
         };
@@ -1744,7 +1946,7 @@ impl RbacRole {
                         res_op_id,
                         RbacRoleOpStatus::Enable
                     )),
-                    db,
+                    &mut *db,
                 )
                 .await?
         };
@@ -1782,7 +1984,7 @@ impl RbacRole {
                     role.id,
                     RbacRoleOpStatus::Enable,
                 )),
-                db,
+                &mut *db,
             )
             .await?;
 
@@ -1812,10 +2014,39 @@ impl RbacRole {
                 add_item.push((res_opt.id, res_op_positivity.to_owned() as i8));
             }
         }
+        Ok((del_op, add_item))
+    }
+    /// 角色设置资源的操作
+    pub async fn role_set_ops<'t>(
+        &self,
+        role: &RbacRoleModel,
+        role_op_vec: &[RoleSetOp],
+        set_user_id: u64,
+        transaction: Option<&mut Transaction<'t, sqlx::MySql>>,
+        env_data: Option<&RequestEnv>,
+    ) -> UserRbacResult<()> {
+        if !RbacRoleResOpRange::AllowCustom.eq(role.res_op_range) {
+            return Err(UserRbacError::System(
+                fluent_message!("rbac-res-op-range-wrong",{
+                    "name":&role.name,
+                    "role_id":role.id,
+                    "range":role.res_op_range
+                }), //"role({$name})[range:{$range}] can't set ops [{$role_id}]",[
+            ));
+        }
+
+        let time = now_time().unwrap_or_default();
         let mut db = match transaction {
             Some(pb) => pb.begin().await?,
             None => self.db.begin().await?,
         };
+        let (del_op, add_item) = match self.role_set_ops_diff(role, role_op_vec, &mut db).await {
+            Ok(tmp) => tmp,
+            Err(e) => {
+                db.rollback().await?;
+                return Err(e);
+            }
+        };
 
         if !add_item.is_empty() {
             let mut add_op = Vec::with_capacity(add_item.len());
@@ -1924,6 +2155,16 @@ impl RbacRole {
                 }
             }
         }
+        //继承该角色的下级角色
+        let children = self.role_get_children(role.id).await?;
+        if !children.is_empty() {
+            let op_ids = del_op
+                .iter()
+                .map(|e| e.1)
+                .chain(add_item.iter().map(|e| e.0))
+                .collect::<Vec<_>>();
+            self.inherit_cache_clear(&children, &op_ids).await?;
+        }
         //cache clean----------------------------
 
         self.logger
@@ -1971,12 +2212,12 @@ impl RbacRole {
             .fetch_all(&self.db)
             .await?;
         //当指定关系角色的类型为`自定义`,根据关系表查询出对应配置数据
+        let res_id = roles
+            .iter()
+            .filter(|e| RbacRoleResOpRange::AllowCustom.eq(e.res_op_range))
+            .map(|res| res.id)
+            .collect::<Vec<_>>();
         let role_op = if !roles.is_empty() {
-            let res_id = roles
-                .iter()
-                .filter(|e| RbacRoleResOpRange::AllowCustom.eq(e.res_op_range))
-                .map(|res| res.id)
-                .collect::<Vec<_>>();
             if res_id.is_empty() {
                 vec![]
             } else {
@@ -1994,15 +2235,11 @@ impl RbacRole {
         } else {
             vec![]
         };
+        //合并继承的上级角色操作
+        let mut role_op = self.inherit_role_ops(&res_id, role_op).await?;
         let mut out = Vec::with_capacity(roles.len());
         for role_ in roles.into_iter() {
-            let mut role_ops = vec![];
-            for orole_ in role_op.iter() {
-                if orole_.role_id != role_.id {
-                    continue;
-                }
-                role_ops.push(orole_.to_owned());
-            }
+            let role_ops = role_op.remove(&role_.id).unwrap_or_default();
             out.push(RoleDetailRow {
                 role: role_,
                 role_ops,
@@ -2019,34 +2256,67 @@ impl RbacRole {
             Vec::with_capacity(check_vec.iter().fold(0, |acc, res| acc + res.ops.len()));
         for check_item in check_vec.iter() {
             for res_op in check_item.ops.iter() {
-                let mut tmp = vec![];
-                for RoleDetailRow { role, role_ops } in role_data.iter() {
-                    for role_res_op in role_ops.iter() {
-                        //为每个资源操作分配角色
-                        if (role.user_id == 0//系统角色的全局授权或禁止
-                        && (RbacRoleResOpRange::AllowAll.eq(role.res_op_range)
-                        || RbacRoleResOpRange::DenyAll.eq(role.res_op_range)))
-                            || (role.user_id == check_item.res.user_id //用户角色 授权或禁止
-                           && RbacRoleResOpRange::AllowAll.eq(role.res_op_range)
-                                || RbacRoleResOpRange::DenyAll.eq(role.res_op_range))
-                            || (RbacRoleResOpRange::AllowCustom.eq(role.res_op_range)  //系统或用户指定操作授权
-                           && res_op.id == role_res_op.res_op_id)
-                        {
-                            let positivity = RbacRoleOpPositivity::try_from(role_res_op.positivity)
-                                .unwrap_or(RbacRoleOpPositivity::Allow);
-                            tmp.push((role.clone(), positivity))
-                        }
-                    }
-                }
-                tmp.sort_by(|a, b| a.0.priority.cmp(&b.0.priority));
+                let tmp = self.relation_role_candidates(&role_data, check_item, res_op);
                 relation_data.push(RoleCheckRow::ModelRole {
-                    role: tmp.first().map(|e| e.to_owned()),
+                    role: tmp.into_iter().next(),
                     res_op_id: res_op.id,
                 });
             }
         }
         RoleCheckData::new(relation_data)
     }
+    //角色是否作用于指定资源操作
+    fn role_match_res_op(
+        role: &RbacRoleModel,
+        role_res_op_id: u64,
+        check_item: &RbacResData,
+        res_op: &RbacResOpModel,
+    ) -> bool {
+        (role.user_id == 0//系统角色的全局授权或禁止
+            && (RbacRoleResOpRange::AllowAll.eq(role.res_op_range)
+            || RbacRoleResOpRange::DenyAll.eq(role.res_op_range)))
+            || (role.user_id == check_item.res.user_id //用户角色 授权或禁止
+                && RbacRoleResOpRange::AllowAll.eq(role.res_op_range)
+                || RbacRoleResOpRange::DenyAll.eq(role.res_op_range))
+            || (RbacRoleResOpRange::AllowCustom.eq(role.res_op_range)  //系统或用户指定操作授权
+                && res_op.id == role_res_op_id)
+    }
+    /// 关系角色中作用于指定资源操作的候选角色
+    /// 按优先级升序排列,检测时使用第一个
+    pub(crate) fn relation_role_candidates(
+        &self,
+        role_data: &[RoleDetailRow],
+        check_item: &RbacResData,
+        res_op: &RbacResOpModel,
+    ) -> Vec<(RbacRoleModel, RbacRoleOpPositivity)> {
+        let mut tmp = vec![];
+        for RoleDetailRow { role, role_ops } in role_data.iter() {
+            for role_res_op in role_ops.iter() {
+                if Self::role_match_res_op(role, role_res_op.res_op_id, check_item, res_op) {
+                    let positivity = RbacRoleOpPositivity::try_from(role_res_op.positivity)
+                        .unwrap_or(RbacRoleOpPositivity::Allow);
+                    tmp.push((role.clone(), positivity))
+                }
+            }
+        }
+        tmp.sort_by_key(|a| a.0.priority);
+        tmp
+    }
+    /// 已查询角色中作用于指定资源操作的候选角色
+    /// 按优先级升序排列,检测时使用第一个
+    pub(crate) fn access_role_candidates<'a>(
+        &self,
+        find_role_data: &'a [RoleAccessRow],
+        check_item: &RbacResData,
+        res_op: &RbacResOpModel,
+    ) -> Vec<&'a RoleAccessRow> {
+        let mut tmp = find_role_data
+            .iter()
+            .filter(|e| Self::role_match_res_op(&e.role, e.res_op_id, check_item, res_op))
+            .collect::<Vec<_>>();
+        tmp.sort_by_key(|a| a.role.priority);
+        tmp
+    }
     //根据关系key获取待检测角色数据
     // pub(crate) async fn find_role_by_relation(
     //     &self,
@@ -2056,7 +2326,7 @@ impl RbacRole {
     //     let relaction_res = self.find_role_detail_by_relation_key(relation_role).await?;
     //     Ok(self.filter_relation_role(relaction_res, check_vec).await)
     // }
-    fn find_role_cache_key_by_public_res(
+    pub(crate) fn find_role_cache_key_by_public_res(
         &self,
         user_range: i8,
         op_id: u64,
@@ -2065,21 +2335,20 @@ impl RbacRole {
         format!("public-res-{}-{}-{}", user_range, op_id, res_user_id)
     }
     //指定用户类型[游客或登录用户]的系统层面 的 指定授权
+    //rop 包含继承的操作,同一角色优先使用自身及层级近的操作,层级相同时禁止优先
     fn find_role_sql_by_public_res(&self, user_range: i8, op_id: u64, role_user_id: u64) -> String {
         sql_format!(
             r#"SELECT CONVERT(rop.res_op_id,UNSIGNED) as res_op_id,rop.positivity as positivity,ro.*
                 FROM {rbac_role} as ro 
                 join {rbac_role_op} as rop 
                 on  ro.user_range={role_user_range} and ro.status ={role_status} and  ro.res_op_range={role_res_op_range} 
-                    and rop.status ={role_op_status} and rop.res_op_id = {role_op_id} and ro.user_id={user_id} and ro.id =rop.role_id
-                order by ro.priority desc,ro.id desc  limit 1"#,
+                    and ro.user_id={user_id} and ro.id =rop.role_id
+                order by ro.priority desc,ro.id desc,rop.depth asc,rop.positivity asc  limit 1"#,
             rbac_role = RbacRoleModel::table_name(),
-            rbac_role_op = RbacRoleOpModel::table_name(),
+            rbac_role_op = SqlExpr(self.inherit_op_sql(op_id)),
             role_user_range = user_range,
             role_status = RbacRoleStatus::Enable,
             role_res_op_range = RbacRoleResOpRange::AllowCustom,
-            role_op_status = RbacRoleOpStatus::Enable,
-            role_op_id = op_id,
             user_id = role_user_id
         )
     }
@@ -2208,30 +2477,11 @@ impl RbacRole {
 
         for check_item in check_vec {
             for res_op in &check_item.ops {
-                let mut tmp = vec![];
-                for RoleAccessRow {
-                    role,
-                    res_op_id,
-                    op_positivity,
-                    timeout: _,
-                } in find_role_data.iter()
-                {
-                    //为每个资源操作分配角色
-                    if (role.user_id == 0//系统角色的全局授权或禁止
-                         && (RbacRoleResOpRange::AllowAll.eq(role.res_op_range)
-                         || RbacRoleResOpRange::DenyAll.eq(role.res_op_range)))
-                        || (role.user_id == check_item.res.user_id //用户角色 授权或禁止
-                            && RbacRoleResOpRange::AllowAll.eq(role.res_op_range)
-                            || RbacRoleResOpRange::DenyAll.eq(role.res_op_range))
-                        || (RbacRoleResOpRange::AllowCustom.eq(role.res_op_range)  //系统或用户指定操作授权
-                            && res_op.id == *res_op_id)
-                    {
-                        tmp.push((role.to_owned(), op_positivity.to_owned()))
-                    }
-                }
-                tmp.sort_by(|a, b| a.0.priority.cmp(&b.0.priority));
+                let tmp = self.access_role_candidates(&find_role_data, check_item, res_op);
                 out.push(RoleCheckRow::ModelRole {
-                    role: tmp.first().map(|e| e.to_owned()),
+                    role: tmp
+                        .first()
+                        .map(|e| (e.role.to_owned(), e.op_positivity.to_owned())),
                     res_op_id: res_op.id,
                 });
             }
@@ -2239,7 +2489,7 @@ impl RbacRole {
         Ok(RoleCheckData::new(out))
     }
 
-    fn find_role_cache_key_by_user_res(
+    pub(crate) fn find_role_cache_key_by_user_res(
         &self,
         user_id: u64,
         op_id: u64,
@@ -2254,17 +2504,15 @@ impl RbacRole {
             SELECT CONVERT(rop.res_op_id,UNSIGNED) as res_op_id,rop.positivity as positivity,ro.*,ru.timeout
             FROM {rbac_role}  as ro 
             join {rbac_role_user} as ru on ro.user_range={role_user_range} and ro.status ={role_status} and ro.res_op_range={role_res_op_range} and ro.user_id={role_user_id}
-                and ru.status ={role_user_status} and ru.user_id = {role_user_user_id}  and (ru.timeout>{timeout} or ru.timeout=0) and ro.id =ru.role_id
-            join {rbac_role_op} as rop on rop.status ={role_op_status} and rop.res_op_id ={role_op_id}  and ro.id =rop.role_id
-            order by ro.priority desc,ro.id desc  limit 1 "#,
+                and ru.status ={role_user_status} and ru.user_id = {role_user_user_id}  and (ru.timeout>{timeout} or ru.timeout=0) and ru.start_time<={timeout} and ro.id =ru.role_id
+            join {rbac_role_op} as rop on ro.id =rop.role_id
+            order by ro.priority desc,ro.id desc,rop.depth asc,rop.positivity asc  limit 1 "#,
             rbac_role = RbacRoleModel::table_name(),
             rbac_role_user = RbacRoleUserModel::table_name(),
-            rbac_role_op = RbacRoleOpModel::table_name(),
+            rbac_role_op = SqlExpr(self.inherit_op_sql(op_id)),
             role_user_range = RbacRoleUserRange::User,
             role_status = RbacRoleStatus::Enable,
             role_res_op_range = RbacRoleResOpRange::AllowCustom,
-            role_op_status = RbacRoleOpStatus::Enable,
-            role_op_id = op_id,
             role_user_status = RbacRoleUserStatus::Enable,
             role_user_user_id = user_id,
             timeout = time,
@@ -2282,7 +2530,7 @@ impl RbacRole {
             SELECT CONVERT(0,UNSIGNED) as res_op_id,0 as positivity,ro.*,ru.timeout
             FROM {rbac_role}  as ro 
                 join {rbac_role_user} as ru on ro.user_id={role_user_id} and ro.user_range={role_user_range} and ro.status ={role_status} and ro.res_op_range IN ({role_res_op_range}) 
-                and ru.status ={role_user_status} and ru.user_id =  {role_user_user_id} and (ru.timeout>{timeout} or ru.timeout=0) and ro.id =ru.role_id 
+                and ru.status ={role_user_status} and ru.user_id =  {role_user_user_id} and (ru.timeout>{timeout} or ru.timeout=0) and ru.start_time<={timeout} and ro.id =ru.role_id 
             order by ro.priority desc,ro.id desc  limit 1  
              "#,
             rbac_role = RbacRoleModel::table_name(),
@@ -2300,8 +2548,8 @@ impl RbacRole {
         )
     }
 
-    /// 获取指定用户的待检测角色数据
-    /// 并根据资源的优先级排序结果
+    // 获取指定用户的待检测角色数据
+    // 并根据资源的优先级排序结果
     // 公开角色：资源key->资源id反查->角色且公开【纬度由访问用户决定】 得到资源数据库角色（建这样公开角色时自动建资源）
     // 用户-角色关系配置角色：访问用户通过角色配置得到数据库角色
     // pub(crate) async fn find_role_by_user(
@@ -2341,6 +2589,14 @@ impl<'t> RbacRoleCache<'t> {
         relation_role: &[RoleRelationKey],
         check_vec: &[RbacResData],
     ) -> UserRbacResult<RoleCheckData> {
+        let role_data = self.find_role_detail_by_relation(relation_role).await?;
+        Ok(self.role.filter_relation_role(role_data, check_vec).await)
+    }
+    /// 获取关系KEY对应的角色详细数据
+    pub(crate) async fn find_role_detail_by_relation(
+        &self,
+        relation_role: &[RoleRelationKey],
+    ) -> UserRbacResult<Vec<RoleDetailRow>> {
         let mut get = vec![];
         let mut hash = std::collections::HashMap::with_capacity(relation_role.len());
         for id in relation_role {
@@ -2378,21 +2634,24 @@ impl<'t> RbacRoleCache<'t> {
                 }
             }
         }
-        Ok(self
-            .role
-            .filter_relation_role(
-                hash.into_values().flatten().collect::<Vec<RoleDetailRow>>(),
-                check_vec,
-            )
-            .await)
+        Ok(hash.into_values().flatten().collect::<Vec<RoleDetailRow>>())
     }
     async fn find_role_by_public(
         &self,
         user_range: RbacRoleUserRange,
         check_vec: &[RbacResData],
     ) -> UserRbacResult<RoleCheckData> {
+        let access_data = self.find_role_access_by_public(user_range, check_vec).await?;
+        self.role.filter_find_role(access_data, check_vec)
+    }
+    /// 获取指定用户类型[游客或登录用户]作用于待检测资源的角色
+    pub(crate) async fn find_role_access_by_public(
+        &self,
+        user_range: RbacRoleUserRange,
+        check_vec: &[RbacResData],
+    ) -> UserRbacResult<Vec<RoleAccessRow>> {
         if check_vec.is_empty() {
-            return Ok(RoleCheckData::new(vec![]));
+            return Ok(vec![]);
         }
 
         let mut access_data = vec![];
@@ -2526,7 +2785,7 @@ impl<'t> RbacRoleCache<'t> {
             }
             access_data.extend(data);
         }
-        self.role.filter_find_role(access_data, check_vec)
+        Ok(access_data)
     }
     pub(crate) async fn find_role_by_all_user(
         &self,
@@ -2547,8 +2806,17 @@ impl<'t> RbacRoleCache<'t> {
         user_id: u64,
         check_vec: &[RbacResData],
     ) -> UserRbacResult<RoleCheckData> {
+        let access_data = self.find_role_access_by_user(user_id, check_vec).await?;
+        self.role.filter_find_role(access_data, check_vec)
+    }
+    /// 获取指定用户通过角色用户关系作用于待检测资源的角色
+    pub(crate) async fn find_role_access_by_user(
+        &self,
+        user_id: u64,
+        check_vec: &[RbacResData],
+    ) -> UserRbacResult<Vec<RoleAccessRow>> {
         if check_vec.is_empty() {
-            return Ok(RoleCheckData::new(vec![]));
+            return Ok(vec![]);
         }
 
         let mut access_data = vec![];
@@ -2631,6 +2899,8 @@ impl<'t> RbacRoleCache<'t> {
         let nowtime = now_time().unwrap_or(0);
         if !sqls.is_empty() {
             let data = self.role.find_role_by_sqls(sqls, true).await?;
+            //存在未生效的角色用户时,缓存在其生效时失效
+            let next_start = self.role.role_user_next_start(user_id).await?;
             if global_keys.is_none() {
                 let mut set_time = 0;
                 let tmp = data
@@ -2640,28 +2910,15 @@ impl<'t> RbacRoleCache<'t> {
                             && (RbacRoleResOpRange::AllowAll.eq(e.role.res_op_range)
                                 || RbacRoleResOpRange::DenyAll.eq(e.role.res_op_range))
                         {
-                            if e.timeout > 0 && e.timeout < set_time {
-                                set_time = e.timeout;
-                            }
+                            set_time = e.timeout;
                             true
                         } else {
                             false
                         }
                     })
                     .map(|e| e.to_owned());
-                if set_time == 0 || set_time > nowtime {
-                    self.role
-                        .cache_access
-                        .set(
-                            global_key,
-                            tmp,
-                            if set_time > nowtime {
-                                set_time - nowtime
-                            } else {
-                                0
-                            },
-                        )
-                        .await;
+                if let Some(cache_time) = access_cache_time(nowtime, set_time, next_start) {
+                    self.role.cache_access.set(global_key, tmp, cache_time).await;
                 }
             }
             for tkey in global_user_keys {
@@ -2674,28 +2931,15 @@ impl<'t> RbacRoleCache<'t> {
                                 || RbacRoleResOpRange::DenyAll.eq(e.role.res_op_range))
                             && e.role.user_id == tkey.1
                         {
-                            if e.timeout > 0 && e.timeout < set_time {
-                                set_time = e.timeout;
-                            }
+                            set_time = e.timeout;
                             true
                         } else {
                             false
                         }
                     })
                     .map(|e| e.to_owned());
-                if set_time == 0 || set_time > nowtime {
-                    self.role
-                        .cache_access
-                        .set(
-                            tkey.0,
-                            tmp,
-                            if set_time > nowtime {
-                                set_time - nowtime
-                            } else {
-                                0
-                            },
-                        )
-                        .await;
+                if let Some(cache_time) = access_cache_time(nowtime, set_time, next_start) {
+                    self.role.cache_access.set(tkey.0, tmp, cache_time).await;
                 }
             }
 
@@ -2708,28 +2952,15 @@ impl<'t> RbacRoleCache<'t> {
                             && RbacRoleResOpRange::AllowCustom.eq(e.role.res_op_range)
                             && e.res_op_id == tkey.1
                         {
-                            if e.timeout > 0 && e.timeout < set_time {
-                                set_time = e.timeout;
-                            }
+                            set_time = e.timeout;
                             true
                         } else {
                             false
                         }
                     })
                     .map(|e| e.to_owned());
-                if set_time == 0 || set_time > nowtime {
-                    self.role
-                        .cache_access
-                        .set(
-                            tkey.0,
-                            tmp,
-                            if set_time > nowtime {
-                                set_time - nowtime
-                            } else {
-                                0
-                            },
-                        )
-                        .await;
+                if let Some(cache_time) = access_cache_time(nowtime, set_time, next_start) {
+                    self.role.cache_access.set(tkey.0, tmp, cache_time).await;
                 }
             }
 
@@ -2742,32 +2973,19 @@ impl<'t> RbacRoleCache<'t> {
                             && RbacRoleResOpRange::AllowCustom.eq(e.role.res_op_range)
                             && e.res_op_id == tkey.1
                         {
-                            if e.timeout > 0 && e.timeout < set_time {
-                                set_time = e.timeout;
-                            }
+                            set_time = e.timeout;
                             true
                         } else {
                             false
                         }
                     })
                     .map(|e| e.to_owned());
-                if set_time == 0 || set_time > nowtime {
-                    self.role
-                        .cache_access
-                        .set(
-                            tkey.0,
-                            tmp,
-                            if set_time > nowtime {
-                                set_time - nowtime
-                            } else {
-                                0
-                            },
-                        )
-                        .await;
+                if let Some(cache_time) = access_cache_time(nowtime, set_time, next_start) {
+                    self.role.cache_access.set(tkey.0, tmp, cache_time).await;
                 }
             }
             access_data.extend(data);
         }
-        self.role.filter_find_role(access_data, check_vec)
+        Ok(access_data)
     }
 }
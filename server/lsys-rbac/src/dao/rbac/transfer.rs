@@ -0,0 +1,991 @@
+use std::{collections::HashMap, sync::Arc};
+
+use lsys_core::{fluent_message, now_time, RequestEnv};
+use lsys_logger::dao::ChangeLogger;
+use serde::{Deserialize, Serialize};
+use sqlx::{MySql, Pool, Transaction};
+use sqlx_model::{sql_format, Select, SqlQuote, WhereOption};
+
+use crate::model::{
+    RbacResModel, RbacResOpModel, RbacResOpStatus, RbacResStatus, RbacRoleModel,
+    RbacRoleOpPositivity, RbacRoleResOpRange, RbacRoleStatus, RbacRoleUserRange,
+};
+
+use super::{
+    logger::LogTransfer, RbacRes, RbacRole, ResOp, RoleAddUser, RoleSetOp, UserRbacError,
+    UserRbacResult,
+};
+
+//导入导出数据格式版本,格式不兼容变更时递增
+pub const RBAC_TRANSFER_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RbacTransferOp {
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RbacTransferRes {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub ops: Vec<RbacTransferOp>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RbacTransferRoleOp {
+    pub res_user_id: u64, //资源所属用户ID,等于导出用户ID时导入到目标用户的资源
+    pub res: String,      //资源KEY
+    pub op: String,       //操作KEY
+    pub allow: bool,      //加权或减权
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RbacTransferRoleUser {
+    pub user_id: u64,
+    #[serde(default)]
+    pub start_time: u64,
+    #[serde(default)]
+    pub timeout: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RbacTransferRole {
+    pub name: String, //角色名,导入时按角色名对比
+    #[serde(default)]
+    pub relation_key: String, //关系角色的关系KEY
+    pub user_range: i8,
+    pub res_op_range: i8,
+    pub priority: i8,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub ops: Vec<RbacTransferRoleOp>, //仅 AllowCustom 角色
+    #[serde(default)]
+    pub users: Vec<RbacTransferRoleUser>, //仅指定用户角色
+    #[serde(default)]
+    pub parents: Vec<String>, //继承的父角色名,仅 AllowCustom 角色
+}
+
+//导入导出数据,资源按KEY,角色按角色名对比
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RbacTransferData {
+    pub version: u32,
+    pub user_id: u64, //导出用户ID
+    #[serde(default)]
+    pub res: Vec<RbacTransferRes>,
+    #[serde(default)]
+    pub role: Vec<RbacTransferRole>,
+}
+
+impl RbacTransferData {
+    //排序去重并将导出用户的资源映射为目标用户,便于对比
+    fn normalize(&self, user_id: u64) -> Self {
+        let mut out = self.clone();
+        out.user_id = user_id;
+        for res in out.res.iter_mut() {
+            res.ops.sort_by(|a, b| a.key.cmp(&b.key));
+            res.ops.dedup_by(|a, b| a.key == b.key);
+            res.tags.sort();
+            res.tags.dedup();
+        }
+        out.res.sort_by(|a, b| a.key.cmp(&b.key));
+        for role in out.role.iter_mut() {
+            if !RbacRoleUserRange::Relation.eq(role.user_range) {
+                role.relation_key = String::new();
+            }
+            if !RbacRoleResOpRange::AllowCustom.eq(role.res_op_range) {
+                role.ops.clear();
+                role.parents.clear();
+            }
+            if !RbacRoleUserRange::User.eq(role.user_range) {
+                role.users.clear();
+            }
+            for op in role.ops.iter_mut() {
+                if op.res_user_id == self.user_id {
+                    op.res_user_id = user_id;
+                }
+            }
+            role.ops.sort();
+            role.ops.dedup();
+            role.tags.sort();
+            role.tags.dedup();
+            role.users.sort();
+            role.users.dedup_by(|a, b| a.user_id == b.user_id);
+            role.parents.sort();
+            role.parents.dedup();
+        }
+        out.role.sort_by(|a, b| a.name.cmp(&b.name));
+        out
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RbacTransferTarget {
+    Res,
+    Role,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RbacTransferAction {
+    Add,
+    Edit,
+    Delete,
+}
+
+//导入时的变更项
+#[derive(Clone, Debug, Serialize)]
+pub struct RbacTransferChange {
+    pub target: RbacTransferTarget,
+    pub key: String, //资源KEY或角色名
+    pub action: RbacTransferAction,
+    pub fields: Vec<&'static str>, //需设置的字段,新增时为新增后需设置的关联数据
+}
+
+impl RbacTransferChange {
+    fn has(&self, field: &str) -> bool {
+        self.fields.contains(&field)
+    }
+}
+
+//当前数据
+struct RbacTransferState {
+    data: RbacTransferData,
+    res: HashMap<String, RbacResModel>,
+    role: HashMap<String, RbacRoleModel>,
+}
+
+//对比角色用户,返回需添加的用户及需删除的用户ID
+//生效时间或超时时间变更的用户先删除再添加
+fn role_user_change(
+    now: &[RbacTransferRoleUser],
+    set: &[RbacTransferRoleUser],
+    remove_miss: bool,
+) -> (Vec<RoleAddUser>, Vec<u64>) {
+    let add = set
+        .iter()
+        .filter(|e| !now.contains(e))
+        .map(|e| RoleAddUser {
+            user_id: e.user_id,
+            start_time: e.start_time,
+            timeout: e.timeout,
+        })
+        .collect::<Vec<_>>();
+    let mut del = now
+        .iter()
+        .filter(|e| !set.contains(e) && (remove_miss || set.iter().any(|t| t.user_id == e.user_id)))
+        .map(|e| e.user_id)
+        .collect::<Vec<_>>();
+    del.sort();
+    del.dedup();
+    (add, del)
+}
+
+//对比当前数据与导入数据,数据需已 normalize
+//资源操作,标签及角色操作按导入数据完整替换,未在导入数据中的资源,角色及角色用户仅在 remove_miss 时删除
+fn transfer_diff(
+    now: &RbacTransferData,
+    set: &RbacTransferData,
+    remove_miss: bool,
+) -> Vec<RbacTransferChange> {
+    let mut out = vec![];
+    if remove_miss {
+        for res in now.res.iter() {
+            if !set.res.iter().any(|e| e.key == res.key) {
+                out.push(RbacTransferChange {
+                    target: RbacTransferTarget::Res,
+                    key: res.key.clone(),
+                    action: RbacTransferAction::Delete,
+                    fields: vec![],
+                });
+            }
+        }
+    }
+    for res in set.res.iter() {
+        let (action, fields) = match now.res.iter().find(|e| e.key == res.key) {
+            Some(now_res) => {
+                let mut fields = vec![];
+                if now_res.name != res.name {
+                    fields.push("name");
+                }
+                if now_res.ops != res.ops {
+                    fields.push("ops");
+                }
+                if now_res.tags != res.tags {
+                    fields.push("tags");
+                }
+                if fields.is_empty() {
+                    continue;
+                }
+                (RbacTransferAction::Edit, fields)
+            }
+            None => {
+                let mut fields = vec![];
+                if !res.ops.is_empty() {
+                    fields.push("ops");
+                }
+                if !res.tags.is_empty() {
+                    fields.push("tags");
+                }
+                (RbacTransferAction::Add, fields)
+            }
+        };
+        out.push(RbacTransferChange {
+            target: RbacTransferTarget::Res,
+            key: res.key.clone(),
+            action,
+            fields,
+        });
+    }
+    if remove_miss {
+        for role in now.role.iter() {
+            if !set.role.iter().any(|e| e.name == role.name) {
+                out.push(RbacTransferChange {
+                    target: RbacTransferTarget::Role,
+                    key: role.name.clone(),
+                    action: RbacTransferAction::Delete,
+                    fields: vec![],
+                });
+            }
+        }
+    }
+    for role in set.role.iter() {
+        let now_role = now.role.iter().find(|e| e.name == role.name);
+        let now_users = now_role.map(|e| e.users.as_slice()).unwrap_or_default();
+        let (add_user, del_user) = role_user_change(now_users, &role.users, remove_miss);
+        let mut fields = vec![];
+        let action = match now_role {
+            Some(now_role) => {
+                if now_role.user_range != role.user_range {
+                    fields.push("user_range");
+                }
+                if now_role.relation_key != role.relation_key {
+                    fields.push("relation_key");
+                }
+                if now_role.res_op_range != role.res_op_range {
+                    fields.push("res_op_range");
+                }
+                if now_role.priority != role.priority {
+                    fields.push("priority");
+                }
+                if now_role.tags != role.tags {
+                    fields.push("tags");
+                }
+                if now_role.ops != role.ops {
+                    fields.push("ops");
+                }
+                if now_role.parents != role.parents {
+                    fields.push("parents");
+                }
+                RbacTransferAction::Edit
+            }
+            None => {
+                if !role.tags.is_empty() {
+                    fields.push("tags");
+                }
+                if !role.ops.is_empty() {
+                    fields.push("ops");
+                }
+                if !role.parents.is_empty() {
+                    fields.push("parents");
+                }
+                RbacTransferAction::Add
+            }
+        };
+        if !add_user.is_empty() || !del_user.is_empty() {
+            fields.push("users");
+        }
+        if action == RbacTransferAction::Edit && fields.is_empty() {
+            continue;
+        }
+        out.push(RbacTransferChange {
+            target: RbacTransferTarget::Role,
+            key: role.name.clone(),
+            action,
+            fields,
+        });
+    }
+    out
+}
+
+// 资源及角色的导入导出
+// 导入时对比当前数据,在同一事务中完成变更
+pub struct RbacTransfer {
+    db: Pool<MySql>,
+    res: Arc<RbacRes>,
+    role: Arc<RbacRole>,
+    logger: Arc<ChangeLogger>,
+}
+
+impl RbacTransfer {
+    pub fn new(
+        db: Pool<MySql>,
+        res: Arc<RbacRes>,
+        role: Arc<RbacRole>,
+        logger: Arc<ChangeLogger>,
+    ) -> Self {
+        Self {
+            db,
+            res,
+            role,
+            logger,
+        }
+    }
+    //读取指定用户的当前数据
+    async fn load(&self, user_id: u64) -> UserRbacResult<RbacTransferState> {
+        let res_list = self.res.get_res(user_id, &None, &None, &None).await?;
+        let res_ids = res_list.iter().map(|e| e.id).collect::<Vec<_>>();
+        let mut res_ops = self.res.res_get_ops(&res_ids).await?;
+        let mut res_tags = self.res.res_get_tags(&res_ids).await?;
+        let mut res_data = Vec::with_capacity(res_list.len());
+        for res in res_list.iter() {
+            res_data.push(RbacTransferRes {
+                key: res.res_key.clone(),
+                name: res.name.clone(),
+                ops: res_ops
+                    .remove(&res.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| RbacTransferOp {
+                        key: e.op_key,
+                        name: e.name,
+                    })
+                    .collect(),
+                tags: res_tags
+                    .remove(&res.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| e.name)
+                    .collect(),
+            });
+        }
+
+        let role_list = self
+            .role
+            .get_role(user_id, &None, &None, &None, &None, &None, &None)
+            .await?;
+        let role_ids = role_list.iter().map(|e| e.id).collect::<Vec<_>>();
+        let mut role_tags = self.role.role_get_tags(&role_ids).await?;
+        let mut role_users = self.role.role_get_users(&role_ids, &None, &None).await?;
+        let role_ops = self.role.role_get_ops(&role_ids).await?;
+        let mut role_parents = self.role.role_get_parents(&role_ids).await?;
+        let op_ids = role_ops
+            .values()
+            .flatten()
+            .map(|e| e.res_op_id)
+            .collect::<Vec<_>>();
+        let op_res = self.res.find_by_op_ids(&op_ids).await?;
+        let mut role_data = Vec::with_capacity(role_list.len());
+        for role in role_list.iter() {
+            let ops = role_ops
+                .get(&role.id)
+                .map(|ops| {
+                    ops.iter()
+                        .filter_map(|op| {
+                            op_res
+                                .iter()
+                                .find(|(_, res_op)| res_op.id == op.res_op_id)
+                                .map(|(res, res_op)| RbacTransferRoleOp {
+                                    res_user_id: res.user_id,
+                                    res: res.res_key.clone(),
+                                    op: res_op.op_key.clone(),
+                                    allow: RbacRoleOpPositivity::Allow.eq(op.positivity),
+                                })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            role_data.push(RbacTransferRole {
+                name: role.name.clone(),
+                relation_key: role.relation_key.clone(),
+                user_range: role.user_range,
+                res_op_range: role.res_op_range,
+                priority: role.priority,
+                tags: role_tags
+                    .remove(&role.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| e.name)
+                    .collect(),
+                ops,
+                users: role_users
+                    .remove(&role.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| RbacTransferRoleUser {
+                        user_id: e.user_id,
+                        start_time: e.start_time,
+                        timeout: e.timeout,
+                    })
+                    .collect(),
+                parents: role_parents
+                    .remove(&role.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| e.name)
+                    .collect(),
+            });
+        }
+        let data = RbacTransferData {
+            version: RBAC_TRANSFER_VERSION,
+            user_id,
+            res: res_data,
+            role: role_data,
+        };
+        Ok(RbacTransferState {
+            data: data.normalize(user_id),
+            res: res_list
+                .into_iter()
+                .map(|e| (e.res_key.clone(), e))
+                .collect(),
+            role: role_list.into_iter().map(|e| (e.name.clone(), e)).collect(),
+        })
+    }
+    /// 导出指定用户的资源及角色
+    /// * `user_id` - 资源及角色所属用户ID,0为系统
+    pub async fn export(&self, user_id: u64) -> UserRbacResult<RbacTransferData> {
+        Ok(self.load(user_id).await?.data)
+    }
+    //确认导入数据可被导入
+    fn check_data(&self, data: &RbacTransferData) -> UserRbacResult<()> {
+        if data.version != RBAC_TRANSFER_VERSION {
+            return Err(UserRbacError::System(
+                fluent_message!("rbac-transfer-version",{
+                    "version":data.version,
+                    "support":RBAC_TRANSFER_VERSION
+                }),
+            ));
+        }
+        for role in data.role.iter() {
+            RbacRoleUserRange::try_from(role.user_range)?;
+            RbacRoleResOpRange::try_from(role.res_op_range)?;
+            if RbacRoleUserRange::Relation.eq(role.user_range) && role.relation_key.is_empty() {
+                return Err(UserRbacError::System(fluent_message!(
+                    "rbac-miss-relation-key"
+                )));
+            }
+        }
+        Ok(())
+    }
+    /// 导入资源及角色,返回变更项
+    /// 资源操作,资源标签,角色标签及角色操作按导入数据完整替换
+    /// * `user_id` - 导入到的用户ID,导入数据中属于导出用户的资源视为该用户的资源
+    /// * `data` - 导出的数据
+    /// * `remove_miss` - 是否删除导入数据中不存在的资源,角色及角色用户
+    /// * `dry_run` - 仅返回变更项,不修改数据
+    pub async fn import(
+        &self,
+        user_id: u64,
+        data: &RbacTransferData,
+        remove_miss: bool,
+        dry_run: bool,
+        change_user_id: u64,
+        env_data: Option<&RequestEnv>,
+    ) -> UserRbacResult<Vec<RbacTransferChange>> {
+        self.check_data(data)?;
+        let data = data.normalize(user_id);
+        let state = self.load(user_id).await?;
+        let changes = transfer_diff(&state.data, &data, remove_miss);
+        if dry_run || changes.is_empty() {
+            return Ok(changes);
+        }
+        let mut db = self.db.begin().await?;
+        if let Err(err) = self
+            .apply(
+                &state,
+                &data,
+                &changes,
+                remove_miss,
+                change_user_id,
+                &mut db,
+                env_data,
+            )
+            .await
+        {
+            db.rollback().await?;
+            return Err(err);
+        }
+        self.logger
+            .add(
+                &LogTransfer {
+                    user_id,
+                    remove_miss,
+                    changes: changes.clone(),
+                },
+                &None,
+                &Some(user_id),
+                &Some(change_user_id),
+                Some(&mut db),
+                env_data,
+            )
+            .await;
+        db.commit().await?;
+        Ok(changes)
+    }
+    //在事务中查询角色操作对应的资源,包含同一事务中新增的资源
+    async fn find_role_set_ops<'t>(
+        &self,
+        ops: &[RbacTransferRoleOp],
+        db: &mut Transaction<'t, MySql>,
+    ) -> UserRbacResult<Vec<RoleSetOp>> {
+        let mut out: Vec<RoleSetOp> = vec![];
+        for op in ops.iter() {
+            let index = match out
+                .iter()
+                .position(|e| e.res.user_id == op.res_user_id && e.res.res_key == op.res)
+            {
+                Some(index) => index,
+                None => {
+                    let res = Select::type_new::<RbacResModel>()
+                        .fetch_one_by_where::<RbacResModel, _>(
+                            &WhereOption::Where(sql_format!(
+                                "user_id={} and res_key={} and status={}",
+                                op.res_user_id,
+                                op.res,
+                                RbacResStatus::Enable
+                            )),
+                            &mut *db,
+                        )
+                        .await;
+                    let res = match res {
+                        Ok(res) => res,
+                        Err(sqlx::Error::RowNotFound) => {
+                            return Err(UserRbacError::System(
+                                fluent_message!("rbac-transfer-miss-res-op",{
+                                    "res":&op.res,
+                                    "op":&op.op,
+                                    "user_id":op.res_user_id
+                                }),
+                            ))
+                        }
+                        Err(err) => return Err(err.into()),
+                    };
+                    out.push(RoleSetOp {
+                        res,
+                        res_op: vec![],
+                    });
+                    out.len() - 1
+                }
+            };
+            let res = &mut out[index];
+            let res_op = Select::type_new::<RbacResOpModel>()
+                .fetch_one_by_where::<RbacResOpModel, _>(
+                    &WhereOption::Where(sql_format!(
+                        "res_id={} and op_key={} and status={}",
+                        res.res.id,
+                        op.op,
+                        RbacResOpStatus::Enable
+                    )),
+                    &mut *db,
+                )
+                .await;
+            let res_op = match res_op {
+                Ok(res_op) => res_op,
+                Err(sqlx::Error::RowNotFound) => {
+                    return Err(UserRbacError::System(
+                        fluent_message!("rbac-transfer-miss-res-op",{
+                            "res":&op.res,
+                            "op":&op.op,
+                            "user_id":op.res_user_id
+                        }),
+                    ))
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let positivity = if op.allow {
+                RbacRoleOpPositivity::Allow
+            } else {
+                RbacRoleOpPositivity::Deny
+            };
+            res.res_op.push((res_op, positivity));
+        }
+        Ok(out)
+    }
+    //在事务中按角色名查询父角色ID,包含同一事务中新增的角色
+    async fn find_role_parent_ids<'t>(
+        &self,
+        user_id: u64,
+        role: &RbacTransferRole,
+        db: &mut Transaction<'t, MySql>,
+    ) -> UserRbacResult<Vec<u64>> {
+        if role.parents.is_empty() {
+            return Ok(vec![]);
+        }
+        let parents = Select::type_new::<RbacRoleModel>()
+            .fetch_all_by_where::<RbacRoleModel, _>(
+                &WhereOption::Where(sql_format!(
+                    "user_id={} and name in ({}) and status={}",
+                    user_id,
+                    role.parents,
+                    RbacRoleStatus::Enable
+                )),
+                &mut *db,
+            )
+            .await?;
+        let mut out = Vec::with_capacity(role.parents.len());
+        for name in role.parents.iter() {
+            match parents.iter().find(|e| e.name == *name) {
+                Some(parent) => out.push(parent.id),
+                None => {
+                    return Err(UserRbacError::System(
+                        fluent_message!("rbac-transfer-miss-parent",{
+                            "name":name,
+                            "role":&role.name
+                        }),
+                    ))
+                }
+            }
+        }
+        Ok(out)
+    }
+    //按变更项修改数据,资源先于角色处理,角色操作可使用本次新增的资源
+    //父角色在角色处理完后设置,可继承本次新增的角色
+    #[allow(clippy::too_many_arguments)]
+    async fn apply<'t>(
+        &self,
+        state: &RbacTransferState,
+        data: &RbacTransferData,
+        changes: &[RbacTransferChange],
+        remove_miss: bool,
+        change_user_id: u64,
+        db: &mut Transaction<'t, MySql>,
+        env_data: Option<&RequestEnv>,
+    ) -> UserRbacResult<()> {
+        let user_id = data.user_id;
+        let time = now_time().unwrap_or_default();
+        for change in changes
+            .iter()
+            .filter(|e| e.target == RbacTransferTarget::Res)
+        {
+            if change.action == RbacTransferAction::Delete {
+                if let Some(res) = state.res.get(&change.key) {
+                    self.res
+                        .del_res(res, change_user_id, Some(&mut *db), env_data)
+                        .await?;
+                }
+                continue;
+            }
+            let item = match data.res.iter().find(|e| e.key == change.key) {
+                Some(item) => item,
+                None => continue,
+            };
+            let res = match state.res.get(&change.key) {
+                Some(res) => {
+                    if change.has("name") {
+                        self.res
+                            .edit_res(
+                                res,
+                                Some(item.name.clone()),
+                                change_user_id,
+                                Some(&mut *db),
+                                env_data,
+                            )
+                            .await?;
+                    }
+                    res.to_owned()
+                }
+                None => {
+                    let id = self
+                        .res
+                        .add_res(
+                            user_id,
+                            item.name.clone(),
+                            item.key.clone(),
+                            change_user_id,
+                            Some(&mut *db),
+                            env_data,
+                        )
+                        .await?;
+                    RbacResModel {
+                        id,
+                        user_id,
+                        name: item.name.clone(),
+                        res_key: item.key.clone(),
+                        status: RbacResStatus::Enable as i8,
+                        change_user_id,
+                        change_time: time,
+                    }
+                }
+            };
+            if change.has("ops") {
+                let ops = item
+                    .ops
+                    .iter()
+                    .map(|e| ResOp {
+                        name: e.name.clone(),
+                        key: e.key.clone(),
+                    })
+                    .collect();
+                self.res
+                    .res_set_ops(&res, ops, change_user_id, Some(&mut *db), env_data)
+                    .await?;
+            }
+            if change.has("tags") {
+                self.res
+                    .res_set_tags(&res, &item.tags, change_user_id, Some(&mut *db), env_data)
+                    .await?;
+            }
+        }
+        let mut set_parents = vec![];
+        for change in changes
+            .iter()
+            .filter(|e| e.target == RbacTransferTarget::Role)
+        {
+            if change.action == RbacTransferAction::Delete {
+                if let Some(role) = state.role.get(&change.key) {
+                    self.role
+                        .del_role(role, change_user_id, Some(&mut *db), env_data)
+                        .await?;
+                }
+                continue;
+            }
+            let item = match data.role.iter().find(|e| e.name == change.key) {
+                Some(item) => item,
+                None => continue,
+            };
+            let user_range = RbacRoleUserRange::try_from(item.user_range)?;
+            let res_op_range = RbacRoleResOpRange::try_from(item.res_op_range)?;
+            let role = match state.role.get(&change.key) {
+                Some(role) => {
+                    let relation_key = if user_range == RbacRoleUserRange::Relation
+                        && (change.has("user_range") || change.has("relation_key"))
+                    {
+                        match self
+                            .role
+                            .find_enable_role_by_relation_key(user_id, item.relation_key.clone())
+                            .await
+                        {
+                            Ok(row) if row.id != role.id => {
+                                return Err(UserRbacError::System(
+                                    fluent_message!("rbac-relation-key-exist",{
+                                        "name":row.name,
+                                        "id":row.id,
+                                        "relation_key":&item.relation_key
+                                    }),
+                                ));
+                            }
+                            Ok(_) | Err(sqlx::Error::RowNotFound) => {}
+                            Err(err) => return Err(err.into()),
+                        }
+                        Some(item.relation_key.clone())
+                    } else {
+                        None
+                    };
+                    if relation_key.is_some()
+                        || change.has("user_range")
+                        || change.has("res_op_range")
+                        || change.has("priority")
+                    {
+                        self.role
+                            .inner_edit_role(
+                                role,
+                                None,
+                                relation_key,
+                                Some(item.priority),
+                                Some(user_range),
+                                Some(res_op_range),
+                                change_user_id,
+                                Some(&mut *db),
+                                env_data,
+                            )
+                            .await?;
+                    }
+                    RbacRoleModel {
+                        relation_key: item.relation_key.clone(),
+                        priority: item.priority,
+                        user_range: item.user_range,
+                        res_op_range: item.res_op_range,
+                        ..role.to_owned()
+                    }
+                }
+                None => {
+                    let id = if user_range == RbacRoleUserRange::Relation {
+                        self.role
+                            .add_relation_role(
+                                user_id,
+                                item.relation_key.clone(),
+                                item.name.clone(),
+                                res_op_range,
+                                item.priority,
+                                change_user_id,
+                                Some(&mut *db),
+                                env_data,
+                            )
+                            .await?
+                    } else {
+                        self.role
+                            .add_role(
+                                user_id,
+                                item.name.clone(),
+                                user_range,
+                                res_op_range,
+                                item.priority,
+                                change_user_id,
+                                Some(&mut *db),
+                                env_data,
+                            )
+                            .await?
+                    };
+                    RbacRoleModel {
+                        id,
+                        user_id,
+                        name: item.name.clone(),
+                        relation_key: item.relation_key.clone(),
+                        priority: item.priority,
+                        user_range: item.user_range,
+                        res_op_range: item.res_op_range,
+                        status: RbacRoleStatus::Enable as i8,
+                        change_user_id,
+                        change_time: time,
+                    }
+                }
+            };
+            if change.has("tags") {
+                self.role
+                    .role_set_tags(&role, &item.tags, change_user_id, Some(&mut *db), env_data)
+                    .await?;
+            }
+            if change.has("ops") && res_op_range == RbacRoleResOpRange::AllowCustom {
+                let role_op_vec = self.find_role_set_ops(&item.ops, db).await?;
+                self.role
+                    .role_set_ops(
+                        &role,
+                        &role_op_vec,
+                        change_user_id,
+                        Some(&mut *db),
+                        env_data,
+                    )
+                    .await?;
+            }
+            if change.has("users") && user_range == RbacRoleUserRange::User {
+                let now_users = state
+                    .data
+                    .role
+                    .iter()
+                    .find(|e| e.name == item.name)
+                    .map(|e| e.users.as_slice())
+                    .unwrap_or_default();
+                let (add_user, del_user) = role_user_change(now_users, &item.users, remove_miss);
+                if !del_user.is_empty() {
+                    self.role
+                        .role_del_user(&role, &del_user, change_user_id, Some(&mut *db), env_data)
+                        .await?;
+                }
+                if !add_user.is_empty() {
+                    self.role
+                        .role_add_user(&role, &add_user, change_user_id, Some(&mut *db), env_data)
+                        .await?;
+                }
+            }
+            if change.has("parents") && res_op_range == RbacRoleResOpRange::AllowCustom {
+                set_parents.push((role, item));
+            }
+        }
+        for (role, item) in set_parents {
+            let parent_ids = self.find_role_parent_ids(user_id, item, db).await?;
+            self.role
+                .role_set_parents(&role, &parent_ids, change_user_id, Some(&mut *db), env_data)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_role_user_change() {
+    let user = |user_id: u64, timeout: u64| RbacTransferRoleUser {
+        user_id,
+        start_time: 0,
+        timeout,
+    };
+    let now = vec![user(1, 0), user(2, 100), user(3, 0)];
+    let set = vec![user(1, 0), user(2, 200), user(4, 0)];
+    let (add, del) = role_user_change(&now, &set, false);
+    assert_eq!(
+        add.iter()
+            .map(|e| (e.user_id, e.timeout))
+            .collect::<Vec<_>>(),
+        vec![(2, 200), (4, 0)]
+    );
+    assert_eq!(del, vec![2]);
+    let (_, del) = role_user_change(&now, &set, true);
+    assert_eq!(del, vec![2, 3]);
+}
+
+#[test]
+fn test_transfer_diff() {
+    let role = |name: &str, users: Vec<u64>| RbacTransferRole {
+        name: name.to_string(),
+        relation_key: String::new(),
+        user_range: RbacRoleUserRange::User as i8,
+        res_op_range: RbacRoleResOpRange::AllowAll as i8,
+        priority: 10,
+        tags: vec![],
+        ops: vec![],
+        users: users
+            .into_iter()
+            .map(|user_id| RbacTransferRoleUser {
+                user_id,
+                start_time: 0,
+                timeout: 0,
+            })
+            .collect(),
+        parents: vec![],
+    };
+    let data = |user_id: u64, res: Vec<RbacTransferRes>, role: Vec<RbacTransferRole>| {
+        RbacTransferData {
+            version: RBAC_TRANSFER_VERSION,
+            user_id,
+            res,
+            role,
+        }
+        .normalize(1)
+    };
+    let res = RbacTransferRes {
+        key: "doc".to_string(),
+        name: "doc".to_string(),
+        ops: vec![
+            RbacTransferOp {
+                key: "view".to_string(),
+                name: "view".to_string(),
+            },
+            RbacTransferOp {
+                key: "edit".to_string(),
+                name: "edit".to_string(),
+            },
+        ],
+        tags: vec!["b".to_string(), "a".to_string()],
+    };
+    let now = data(1, vec![res.clone()], vec![role("admin", vec![5])]);
+    //导出用户不同且顺序不同时无变更
+    let mut same_res = res.clone();
+    same_res.ops.reverse();
+    same_res.tags.reverse();
+    let set = data(9, vec![same_res], vec![role("admin", vec![5])]);
+    assert!(transfer_diff(&now, &set, true).is_empty());
+
+    let mut edit_res = res;
+    edit_res.name = "document".to_string();
+    let set = data(
+        1,
+        vec![edit_res],
+        vec![role("admin", vec![]), role("guest", vec![6])],
+    );
+    let changes = transfer_diff(&now, &set, false);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].action, RbacTransferAction::Edit);
+    assert_eq!(changes[0].fields, vec!["name"]);
+    assert_eq!(changes[1].key, "guest");
+    assert_eq!(changes[1].action, RbacTransferAction::Add);
+    assert_eq!(changes[1].fields, vec!["users"]);
+    //删除缺失的角色用户
+    let changes = transfer_diff(&now, &set, true);
+    assert_eq!(changes.len(), 3);
+    assert_eq!(changes[1].key, "admin");
+    assert_eq!(changes[1].fields, vec!["users"]);
+}
@@ -1,2 +1,26 @@
 pub mod dao;
 pub mod model;
+
+/// 数据库迁移,按版本顺序执行
+pub fn migrations() -> Vec<lsys_core::MigrateItem> {
+    vec![
+        lsys_core::MigrateItem::new(
+            env!("CARGO_PKG_NAME"),
+            1,
+            "init",
+            include_str!("../tables.sql"),
+        ),
+        lsys_core::MigrateItem::new(
+            env!("CARGO_PKG_NAME"),
+            2,
+            "role_parent",
+            include_str!("../migrations/0002_role_parent.sql"),
+        ),
+        lsys_core::MigrateItem::new(
+            env!("CARGO_PKG_NAME"),
+            3,
+            "role_user_time",
+            include_str!("../migrations/0003_role_user_time.sql"),
+        ),
+    ]
+}
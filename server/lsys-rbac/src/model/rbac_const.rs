@@ -61,6 +61,13 @@ pub enum RbacRoleOpStatus {
     Delete = -1,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[sqlx_model_status(field_type = "i8")]
+pub enum RbacRoleParentStatus {
+    Enable = 1,
+    Delete = -1,
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[sqlx_model_status(field_type = "i8")]
 pub enum RbacTagsSource {
@@ -125,6 +125,10 @@ pub struct RbacRoleUserModel {
     #[sqlx(default)]
     pub user_id: u64,
 
+    /// 生效时间 0 为立即生效
+    #[sqlx(default)]
+    pub start_time: u64,
+
     /// 超时时间
     #[sqlx(default)]
     pub timeout: u64,
@@ -177,6 +181,38 @@ pub struct RbacRoleOpModel {
     pub change_time: u64,
 }
 
+/// 角色继承 角色【RbacRoleModel】的所有上级角色,继承上级角色的资源操作【RbacRoleOpModel】
+#[derive(FromRow, Clone, Debug, Serialize, Deserialize)]
+#[sqlx_model(db_type = "MySql", table_name = "rbac_role_parent")]
+pub struct RbacRoleParentModel {
+    #[sqlx(default)]
+    pub id: u64,
+
+    /// 角色ID
+    #[sqlx(default)]
+    pub role_id: u64,
+
+    /// 上级角色ID
+    #[sqlx(default)]
+    pub parent_role_id: u64,
+
+    /// 层级 1 为直接父角色
+    #[sqlx(default)]
+    pub depth: u8,
+
+    /// 状态 1 启用 -1 删除
+    #[sqlx(default)]
+    pub status: i8,
+
+    /// 添加用户
+    #[sqlx(default)]
+    pub change_user_id: u64,
+
+    /// 绑定时间
+    #[sqlx(default)]
+    pub change_time: u64,
+}
+
 /// 给角色 资源分组用的tag
 #[derive(FromRow, Clone, Debug, Serialize, Deserialize)]
 #[sqlx_model(db_type = "MySql", table_name = "rbac_tags")]
@@ -1,8 +1,10 @@
 mod setting;
+mod setting_fluent;
 mod setting_multiple;
 mod setting_single;
 
 pub use setting::*;
+pub use setting_fluent::*;
 pub use setting_multiple::*;
 pub use setting_single::*;
 
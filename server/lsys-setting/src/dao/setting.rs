@@ -11,7 +11,7 @@ use crate::model::{SettingModel, SettingStatus, SettingType};
 use sqlx::{MySql, Pool};
 use std::sync::Arc;
 
-use super::{MultipleSetting, SettingError, SettingResult, SingleSetting};
+use super::{FluentSetting, MultipleSetting, SettingError, SettingResult, SingleSetting};
 
 use lsys_core::{cache:: LocalCacheConfig, AppCoreError, RemoteNotify};
 use lsys_logger::dao::ChangeLogger;
@@ -37,6 +37,7 @@ pub struct Setting {
     db: Pool<MySql>,
     pub single: Arc<SingleSetting>,
     pub multiple: Arc<MultipleSetting>,
+    pub fluent: Arc<FluentSetting>,
 }
 
 
@@ -48,21 +49,23 @@ impl Setting {
         config:SettingConfig,
         logger: Arc<ChangeLogger>,
     ) -> Result<Self, AppCoreError> {
+        let multiple = Arc::new(MultipleSetting::new(
+            db.clone(),
+            // fluents_message,
+            remote_notify.clone(),
+            config.multiple_cache,
+            logger.clone(),
+        ));
         Ok(Self {
             single: Arc::from(SingleSetting::new(
                 db.clone(),
                 // fluents_message.clone(),
                 remote_notify.clone(), 
                 config.single_cache,
-                logger.clone(),
-            )),
-            multiple: Arc::from(MultipleSetting::new(
-                db.clone(),
-                // fluents_message,
-                remote_notify.clone(), 
-                config.multiple_cache,
                 logger,
             )),
+            fluent: Arc::from(FluentSetting::new(multiple.clone())),
+            multiple,
             db,
         })
     }
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use lsys_core::{FluentOverrideItem, FluentOverrideLoader, IntoFluentMessage, RequestEnv};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    MultipleSetting, SettingData, SettingDecode, SettingEncode, SettingJson, SettingKey,
+    SettingResult,
+};
+
+//后台编辑的翻译消息,覆盖翻译文件中的消息
+#[derive(Deserialize, Serialize, Clone, Default)]
+pub struct FluentOverrideConfig {
+    pub lang: String,
+    pub crate_name: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl SettingKey for FluentOverrideConfig {
+    fn key<'t>() -> &'t str {
+        "fluent-override"
+    }
+}
+impl SettingDecode for FluentOverrideConfig {
+    fn decode(data: &str) -> SettingResult<Self> {
+        SettingJson::decode(data)
+    }
+}
+impl SettingEncode for FluentOverrideConfig {
+    fn encode(&self) -> String {
+        SettingJson::encode(self)
+    }
+}
+impl SettingJson<'_> for FluentOverrideConfig {}
+
+impl From<FluentOverrideConfig> for FluentOverrideItem {
+    fn from(value: FluentOverrideConfig) -> Self {
+        FluentOverrideItem {
+            lang: value.lang,
+            crate_name: value.crate_name,
+            key: value.key,
+            value: value.value,
+        }
+    }
+}
+
+/// 翻译消息覆盖存储,修改后需重新加载翻译才生效
+pub struct FluentSetting {
+    setting: Arc<MultipleSetting>,
+}
+
+impl FluentSetting {
+    pub fn new(setting: Arc<MultipleSetting>) -> Self {
+        Self { setting }
+    }
+    pub async fn list_data(&self) -> SettingResult<Vec<SettingData<FluentOverrideConfig>>> {
+        self.setting
+            .list_data::<FluentOverrideConfig>(&None, &None, &None)
+            .await
+    }
+    async fn find_data(
+        &self,
+        lang: &str,
+        crate_name: &str,
+        key: &str,
+    ) -> SettingResult<Option<SettingData<FluentOverrideConfig>>> {
+        Ok(self
+            .list_data()
+            .await?
+            .into_iter()
+            .find(|e| e.lang == lang && e.crate_name == crate_name && e.key == key))
+    }
+    /// 添加或修改覆盖的翻译消息
+    pub async fn set(
+        &self,
+        data: &FluentOverrideConfig,
+        change_user_id: &u64,
+        env_data: Option<&RequestEnv>,
+    ) -> SettingResult<u64> {
+        let name = format!("{}:{}:{}", data.lang, data.crate_name, data.key);
+        match self
+            .find_data(&data.lang, &data.crate_name, &data.key)
+            .await?
+        {
+            Some(item) => {
+                self.setting
+                    .edit(
+                        &None,
+                        &item.model().id,
+                        &name,
+                        data,
+                        change_user_id,
+                        None,
+                        env_data,
+                    )
+                    .await?;
+                Ok(item.model().id)
+            }
+            None => {
+                self.setting
+                    .add(&None, &name, data, change_user_id, None, env_data)
+                    .await
+            }
+        }
+    }
+    /// 删除覆盖的翻译消息,恢复为翻译文件中的消息
+    pub async fn del(
+        &self,
+        lang: &str,
+        crate_name: &str,
+        key: &str,
+        change_user_id: &u64,
+        env_data: Option<&RequestEnv>,
+    ) -> SettingResult<u64> {
+        match self.find_data(lang, crate_name, key).await? {
+            Some(item) => {
+                self.setting
+                    .del::<FluentOverrideConfig>(
+                        &None,
+                        &item.model().id,
+                        change_user_id,
+                        None,
+                        env_data,
+                    )
+                    .await
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+#[async_trait]
+impl FluentOverrideLoader for FluentSetting {
+    async fn load(&self) -> Result<Vec<FluentOverrideItem>, String> {
+        Ok(self
+            .list_data()
+            .await
+            .map_err(|e| e.to_fluent_message().default_format())?
+            .into_iter()
+            .map(|e| (*e).clone().into())
+            .collect())
+    }
+}
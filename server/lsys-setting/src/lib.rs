@@ -1,2 +1,12 @@
 pub mod dao;
 pub mod model;
+
+/// 数据库迁移,按版本顺序执行
+pub fn migrations() -> Vec<lsys_core::MigrateItem> {
+    vec![lsys_core::MigrateItem::new(
+        env!("CARGO_PKG_NAME"),
+        1,
+        "init",
+        include_str!("../tables.sql"),
+    )]
+}
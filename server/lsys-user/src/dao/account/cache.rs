@@ -1,7 +1,7 @@
 use std::{str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
-use lsys_core::cache::{LocalCache, LocalCacheClearItem};
+use lsys_core::cache::{LocalCache, LocalCacheClearItem, LocalCacheStats};
 
 use crate::model::{
     UserAddressModel, UserEmailModel, UserExternalModel, UserInfoModel, UserMobileModel, UserModel,
@@ -60,4 +60,60 @@ impl LocalCacheClearItem for UserAccountLocalCacheClear {
         };
         Ok(())
     }
+    async fn clear_all_from_message(&self) {
+        match self {
+            Self::User(cache) => cache.del_all().await,
+            Self::Address(cache) => cache.del_all().await,
+            Self::Email(cache) => cache.del_all().await,
+            Self::External(cache) => cache.del_all().await,
+            Self::Info(cache) => cache.del_all().await,
+            Self::Mobile(cache) => cache.del_all().await,
+            Self::Name(cache) => cache.del_all().await,
+        }
+    }
+    async fn clear(&self, key: Option<&str>) -> Result<(), String> {
+        let key = match key {
+            Some(key) => Some(u64::from_str(key).map_err(|e| e.to_string())?),
+            None => None,
+        };
+        match (self, key) {
+            (Self::User(cache), Some(key)) => cache.clear(&key).await,
+            (Self::Address(cache), Some(key)) => cache.clear(&key).await,
+            (Self::Email(cache), Some(key)) => cache.clear(&key).await,
+            (Self::External(cache), Some(key)) => cache.clear(&key).await,
+            (Self::Info(cache), Some(key)) => cache.clear(&key).await,
+            (Self::Mobile(cache), Some(key)) => cache.clear(&key).await,
+            (Self::Name(cache), Some(key)) => cache.clear(&key).await,
+            (Self::User(cache), None) => cache.clear_all().await,
+            (Self::Address(cache), None) => cache.clear_all().await,
+            (Self::Email(cache), None) => cache.clear_all().await,
+            (Self::External(cache), None) => cache.clear_all().await,
+            (Self::Info(cache), None) => cache.clear_all().await,
+            (Self::Mobile(cache), None) => cache.clear_all().await,
+            (Self::Name(cache), None) => cache.clear_all().await,
+        };
+        Ok(())
+    }
+    async fn stats(&self) -> LocalCacheStats {
+        match self {
+            Self::User(cache) => cache.stats().await,
+            Self::Address(cache) => cache.stats().await,
+            Self::Email(cache) => cache.stats().await,
+            Self::External(cache) => cache.stats().await,
+            Self::Info(cache) => cache.stats().await,
+            Self::Mobile(cache) => cache.stats().await,
+            Self::Name(cache) => cache.stats().await,
+        }
+    }
+    async fn set_enable(&self, enable: bool) {
+        match self {
+            Self::User(cache) => cache.set_enable(enable).await,
+            Self::Address(cache) => cache.set_enable(enable).await,
+            Self::Email(cache) => cache.set_enable(enable).await,
+            Self::External(cache) => cache.set_enable(enable).await,
+            Self::Info(cache) => cache.set_enable(enable).await,
+            Self::Mobile(cache) => cache.set_enable(enable).await,
+            Self::Name(cache) => cache.set_enable(enable).await,
+        }
+    }
 }
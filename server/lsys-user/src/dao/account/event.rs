@@ -0,0 +1,19 @@
+use lsys_logger::dao::EventData;
+use serde::{Deserialize, Serialize};
+
+//用户注册完成
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UserRegisterEvent {
+    pub user_id: u64,
+    pub nickname: String,
+    pub status: i8,
+}
+
+impl EventData for UserRegisterEvent {
+    fn event_type<'t>() -> &'t str {
+        "user-register"
+    }
+    fn encode(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+}
@@ -7,6 +7,7 @@ macro_rules! impl_account_valid_code_method {
         /// 验证码生成
         pub fn valid_code(&$me) -> lsys_core::ValidCode {
             lsys_core::ValidCode::new($me.redis.clone(), $valid_type.to_string(),true)
+                .with_check_limit($me.valid_code_limit)
         }
         /// 获取验证码
         pub async fn valid_code_set<T: lsys_core::ValidCodeData>(
@@ -62,6 +63,7 @@ fn valid_code_test() {
     #[allow(dead_code)]
     struct Test1 {
         redis: deadpool_redis::Pool,
+        valid_code_limit: lsys_core::ValidCodeCheckLimit,
     }
     #[allow(dead_code)]
     impl Test1 {
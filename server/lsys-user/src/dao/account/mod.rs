@@ -9,7 +9,7 @@ use self::user_login::UserLogin;
 use super::auth::UserPasswordHash;
 
 
-use lsys_core::{cache:: LocalCacheConfig, RemoteNotify};
+use lsys_core::{cache:: LocalCacheConfig, RemoteNotify, ValidCodeCheckLimit};
 
 use lsys_logger::dao::ChangeLogger;
 
@@ -27,10 +27,12 @@ use user_password::UserPassword;
 #[macro_use]
 mod macros;
 
+mod event;
 mod logger;
 mod result;
 mod user_index;
 mod utils;
+pub use event::*;
 pub use result::*;
 pub use utils::*;
 pub mod cache;
@@ -54,6 +56,7 @@ pub struct UserAccountConfig{
     pub info_cache:LocalCacheConfig,
     pub address_cache:LocalCacheConfig,
     pub external_cache:LocalCacheConfig,
+    pub valid_code_limit:ValidCodeCheckLimit,
 }
 
 impl UserAccountConfig {
@@ -66,8 +69,27 @@ impl UserAccountConfig {
             info_cache:LocalCacheConfig::new("user-info",if use_cache{None}else{Some(0)},None),
             address_cache: LocalCacheConfig::new("user-address",if use_cache{None}else{Some(0)},None),
             external_cache: LocalCacheConfig::new("user-external",if use_cache{None}else{Some(0)},None),
+            valid_code_limit:ValidCodeCheckLimit::default(),
         }
     }
+    /// 启用REDIS二级缓存
+    /// * `redis_time` - REDIS缓存时间,单位秒,为0时不使用
+    pub fn use_redis(mut self, redis_time: u64) -> Self {
+        self.user_cache = self.user_cache.use_redis(redis_time);
+        self.email_cache = self.email_cache.use_redis(redis_time);
+        self.mobile_cache = self.mobile_cache.use_redis(redis_time);
+        self.name_cache = self.name_cache.use_redis(redis_time);
+        self.info_cache = self.info_cache.use_redis(redis_time);
+        self.address_cache = self.address_cache.use_redis(redis_time);
+        self.external_cache = self.external_cache.use_redis(redis_time);
+        self
+    }
+    /// 设置验证码校验失败限制
+    /// * `valid_code_limit` - 最大失败次数及锁定时间
+    pub fn valid_code_limit(mut self, valid_code_limit: ValidCodeCheckLimit) -> Self {
+        self.valid_code_limit = valid_code_limit;
+        self
+    }
 }
 
 
@@ -82,6 +104,7 @@ pub struct UserAccount {
     pub user_password: Arc<UserPassword>,
     pub user_login: Arc<UserLogin>,
     pub user_passwrod_hash: Arc<UserPasswordHash>,
+    pub valid_code_limit: ValidCodeCheckLimit,
 }
 
 impl UserAccount {
@@ -98,6 +121,7 @@ impl UserAccount {
         UserAccount {
             user: Arc::from(User::new(
                 db.clone(),
+                redis.clone(),
                 // fluent.clone(),
          
                 user_index.clone(),
@@ -113,10 +137,12 @@ impl UserAccount {
                 user_index.clone(),
                 remote_notify.clone(),
                 config.email_cache,
+                config.valid_code_limit,
                 logger.clone(),
             )),
             user_external: Arc::from(UserExternal::new(
                 db.clone(),
+                redis.clone(),
       
                 user_index.clone(),
                 remote_notify.clone(),
@@ -129,10 +155,12 @@ impl UserAccount {
                 user_index.clone(),
                 remote_notify.clone(),
                 config.mobile_cache,
+                config.valid_code_limit,
                 logger.clone(),
             )),
             user_name: Arc::from(UserName::new(
                 db.clone(),
+                redis.clone(),
                 user_index.clone(),
                 remote_notify.clone(),
                 config.name_cache,
@@ -140,6 +168,7 @@ impl UserAccount {
             )),
             user_info: Arc::from(UserInfo::new(
                 db.clone(),
+                redis.clone(),
                 user_index.clone(),
                 remote_notify.clone(),
                 config.info_cache,
@@ -147,6 +176,7 @@ impl UserAccount {
             )),
             user_address: Arc::from(UserAddress::new(
                 db.clone(),
+                redis.clone(),
                 user_index,
                 remote_notify.clone(),
                 config.address_cache,
@@ -158,8 +188,10 @@ impl UserAccount {
                 // fluent,
                 redis,
                 password_hash.clone(),
+                config.valid_code_limit,
             )),
             user_passwrod_hash: password_hash,
+            valid_code_limit: config.valid_code_limit,
             user_login: Arc::from(UserLogin::new(db)),
         }
     }
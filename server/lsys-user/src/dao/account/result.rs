@@ -6,6 +6,7 @@ use std::time::SystemTimeError;
 use deadpool_redis::PoolError;
 use lsys_core::{fluent_message, FluentMessage, IntoFluentMessage, ValidCodeError};
 
+use lsys_logger::dao::LoggerError;
 use lsys_setting::dao::SettingError;
 use redis::RedisError;
 #[derive(Debug)]
@@ -55,6 +56,13 @@ impl From<sqlx::Error> for UserAccountError {
         UserAccountError::Sqlx(err)
     }
 }
+impl From<LoggerError> for UserAccountError {
+    fn from(err: LoggerError) -> Self {
+        match err {
+            LoggerError::Sqlx(err) => UserAccountError::Sqlx(err),
+        }
+    }
+}
 impl From<RedisError> for UserAccountError {
     fn from(err: RedisError) -> Self {
         UserAccountError::Redis(err)
@@ -9,6 +9,7 @@ use lsys_core::{fluent_message, now_time, LimitParam, RemoteNotify, RequestEnv};
 use lsys_logger::dao::ChangeLogger;
 
 use super::logger::LogUser;
+use super::UserRegisterEvent;
 use super::user_index::{UserIndex, UserItem};
 use super::UserAccountError;
 use sqlx::{Acquire, MySql, Pool, Transaction};
@@ -28,6 +29,7 @@ pub struct User {
 impl User {
     pub fn new(
         db: Pool<MySql>,
+        redis: deadpool_redis::Pool,
 
        
         index: Arc<UserIndex>,
@@ -36,7 +38,7 @@ impl User {
         logger: Arc<ChangeLogger>,
     ) -> Self {
         Self {
-            cache:Arc::new(LocalCache::new(remote_notify, config)),
+            cache:Arc::new(LocalCache::new(remote_notify, config).with_redis(redis)),
             db,
             // fluent,
             index,
@@ -109,7 +111,25 @@ impl User {
             db.rollback().await?;
             return Err(ie);
         }
+        if let Err(ie) = self
+            .logger
+            .add_event(
+                &UserRegisterEvent {
+                    user_id: user.id,
+                    nickname: nickname.clone(),
+                    status: u_status,
+                },
+                &Some(user.id),
+                &Some(user.id),
+                Some(&mut db),
+            )
+            .await
+        {
+            db.rollback().await?;
+            return Err(ie.into());
+        }
         db.commit().await?;
+        self.logger.notify_event().await;
 
         self.logger
             .add(
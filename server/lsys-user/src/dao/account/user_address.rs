@@ -26,6 +26,7 @@ pub struct UserAddress {
 impl UserAddress {
     pub fn new(
         db: Pool<MySql>,
+        redis: deadpool_redis::Pool,
         // fluent: Arc<FluentBuild>,
        
         index: Arc<UserIndex>,
@@ -34,7 +35,7 @@ impl UserAddress {
         logger: Arc<ChangeLogger>,
     ) -> Self {
         Self {
-            cache:Arc::new(LocalCache::new(remote_notify, config)),
+            cache:Arc::new(LocalCache::new(remote_notify, config).with_redis(redis)),
             db,
             //  fluent,
             index,
@@ -105,7 +106,7 @@ impl UserAddress {
                     .del(
                         crate::model::UserIndexCat::Address,
                         user.id,
-                        &[address.address_info.clone()],
+                        std::slice::from_ref(&address.address_info),
                         Some(&mut db),
                     )
                     .await
@@ -118,7 +119,7 @@ impl UserAddress {
                     .add(
                         crate::model::UserIndexCat::Address,
                         user.id,
-                        &[address_info.clone()],
+                        std::slice::from_ref(&address_info),
                         Some(&mut db),
                     )
                     .await
@@ -246,7 +247,7 @@ impl UserAddress {
                             .add(
                                 crate::model::UserIndexCat::Address,
                                 user.id,
-                                &[address_info.clone()],
+                                std::slice::from_ref(&address_info),
                                 Some(&mut db),
                             )
                             .await
@@ -285,7 +286,7 @@ impl UserAddress {
         }
     }
     /// 删除用户地址
-    pub async fn del_address<'t, 't2>(
+    pub async fn del_address<'t>(
         &self,
         address: &UserAddressModel,
         transaction: Option<&mut Transaction<'t, sqlx::MySql>>,
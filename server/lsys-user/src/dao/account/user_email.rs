@@ -23,6 +23,7 @@ pub struct UserEmail {
     redis: deadpool_redis::Pool,
     // fluent: Arc<FluentBuild>,
     index: Arc<UserIndex>,
+    valid_code_limit: lsys_core::ValidCodeCheckLimit,
     pub(crate) cache: Arc<LocalCache<u64, Vec<UserEmailModel>>>,
     logger: Arc<ChangeLogger>,
 }
@@ -35,10 +36,12 @@ impl UserEmail {
         index: Arc<UserIndex>,
         remote_notify: Arc<RemoteNotify>,
         config:LocalCacheConfig,
+        valid_code_limit: lsys_core::ValidCodeCheckLimit,
         logger: Arc<ChangeLogger>,
     ) -> Self {
         Self {
-            cache:Arc::new(LocalCache::new(remote_notify, config)),
+            valid_code_limit,
+            cache:Arc::new(LocalCache::new(remote_notify, config).with_redis(redis.clone())),
             db,
             redis,
             // fluent,
@@ -143,7 +146,7 @@ impl UserEmail {
                                 .add(
                                     crate::model::UserIndexCat::Email,
                                     user.id,
-                                    &[email.clone()],
+                                    std::slice::from_ref(&email),
                                     Some(&mut db),
                                 )
                                 .await
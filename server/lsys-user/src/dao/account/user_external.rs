@@ -27,6 +27,7 @@ pub struct UserExternal {
 impl UserExternal {
     pub fn new(
         db: Pool<MySql>,
+        redis: deadpool_redis::Pool,
      
         index: Arc<UserIndex>,
         remote_notify: Arc<RemoteNotify>,
@@ -34,7 +35,7 @@ impl UserExternal {
         logger: Arc<ChangeLogger>,
     ) -> Self {
         Self {
-            cache:Arc::new(LocalCache::new(remote_notify, config)),
+            cache:Arc::new(LocalCache::new(remote_notify, config).with_redis(redis)),
             db,
             // fluent,
             index,
@@ -182,7 +183,7 @@ impl UserExternal {
                                     .add(
                                         crate::model::UserIndexCat::ExternalType,
                                         user.id,
-                                        &[external_type.clone()],
+                                        std::slice::from_ref(&external_type),
                                         Some(&mut db),
                                     )
                                     .await
@@ -326,7 +327,7 @@ impl UserExternal {
                             .del(
                                 crate::model::UserIndexCat::ExternalType,
                                 user_ext.user_id,
-                                &[user_ext.external_type.clone()],
+                                std::slice::from_ref(&user_ext.external_type),
                                 Some(&mut db),
                             )
                             .await
@@ -25,6 +25,7 @@ pub struct UserInfo {
 impl UserInfo {
     pub fn new(
         db: Pool<MySql>,
+        redis: deadpool_redis::Pool,
       
         index: Arc<UserIndex>,
         remote_notify: Arc<RemoteNotify>,
@@ -32,7 +33,7 @@ impl UserInfo {
         logger: Arc<ChangeLogger>,
     ) -> Self {
         Self {
-            cache:Arc::new(LocalCache::new(remote_notify, config)),
+            cache:Arc::new(LocalCache::new(remote_notify, config).with_redis(redis)),
             db,
             logger,
             index,
@@ -26,6 +26,7 @@ pub struct UserMobile {
     redis: deadpool_redis::Pool,
     // fluent: Arc<FluentBuild>,
     index: Arc<UserIndex>,
+    valid_code_limit: lsys_core::ValidCodeCheckLimit,
     pub(crate) cache: Arc<LocalCache<u64, Vec<UserMobileModel>>>,
     logger: Arc<ChangeLogger>,
 }
@@ -38,10 +39,12 @@ impl UserMobile {
         index: Arc<UserIndex>,
         remote_notify: Arc<RemoteNotify>,
         config:LocalCacheConfig,
+        valid_code_limit: lsys_core::ValidCodeCheckLimit,
         logger: Arc<ChangeLogger>,
     ) -> Self {
         Self {
-            cache:Arc::new(LocalCache::new(remote_notify, config)),
+            valid_code_limit,
+            cache:Arc::new(LocalCache::new(remote_notify, config).with_redis(redis.clone())),
             db,
             redis,
             //  fluent,
@@ -159,7 +162,7 @@ impl UserMobile {
                                 .add(
                                     crate::model::UserIndexCat::Mobile,
                                     user.id,
-                                    &[mobile.clone()],
+                                    std::slice::from_ref(&mobile),
                                     Some(&mut db),
                                 )
                                 .await
@@ -39,6 +39,7 @@ fn del_rand_name() -> Result<String, FromUtf8Error> {
 impl UserName {
     pub fn new(
         db: Pool<MySql>,
+        redis: deadpool_redis::Pool,
       
         index: Arc<UserIndex>,
         remote_notify: Arc<RemoteNotify>,
@@ -46,7 +47,7 @@ impl UserName {
         logger: Arc<ChangeLogger>,
     ) -> Self {
         Self {
-            cache:Arc::new(LocalCache::new(remote_notify, config)),
+            cache:Arc::new(LocalCache::new(remote_notify, config).with_redis(redis)),
             db,
             //    fluent,
             index,
@@ -47,6 +47,7 @@ pub struct UserPassword {
     redis: deadpool_redis::Pool,
     user_passwrd_hash: Arc<UserPasswordHash>,
     setting: Arc<SingleSetting>,
+    valid_code_limit: lsys_core::ValidCodeCheckLimit,
 }
 
 impl UserPassword {
@@ -56,6 +57,7 @@ impl UserPassword {
         //fluent: Arc<FluentBuild>,
         redis: deadpool_redis::Pool,
         user_passwrd_hash: Arc<UserPasswordHash>,
+        valid_code_limit: lsys_core::ValidCodeCheckLimit,
     ) -> Self {
         Self {
             db,
@@ -63,6 +65,7 @@ impl UserPassword {
             redis,
             user_passwrd_hash,
             setting,
+            valid_code_limit,
         }
     }
     impl_account_valid_code_method!("passwrod",{
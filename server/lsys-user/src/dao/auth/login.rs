@@ -10,6 +10,7 @@ use lsys_core::{now_time, PageParam};
 use serde::Deserialize;
 use serde::Serialize;
 use sqlx::{MySql, Pool};
+use std::fmt::Display;
 use std::net::{IpAddr, Ipv4Addr};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -176,11 +177,10 @@ pub struct UserAuthTokenData {
     pub user_id: u64,
     pub time_out: u64,
 }
-impl ToString for UserAuthTokenData {
-    fn to_string(&self) -> String {
+impl Display for UserAuthTokenData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = format!("{}-{}-{}", self.user_id, self.token, self.time_out);
-        let base64 = CUSTOM_ENGINE.encode(str.as_bytes());
-        base64
+        write!(f, "{}", CUSTOM_ENGINE.encode(str.as_bytes()))
     }
 }
 impl FromStr for UserAuthTokenData {
@@ -232,12 +232,12 @@ impl FromStr for SessionToken<UserAuthTokenData> {
         Ok(Self::from_data(Some(UserAuthTokenData::from_str(s)?)))
     }
 }
-impl ToString for SessionToken<UserAuthTokenData> {
-    fn to_string(&self) -> String {
-        self.data()
-            .as_ref()
-            .map(|e| e.to_string())
-            .unwrap_or_default()
+impl Display for SessionToken<UserAuthTokenData> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(data) = self.data() {
+            write!(f, "{}", data)?;
+        }
+        Ok(())
     }
 }
 
@@ -390,7 +390,7 @@ impl<T: UserAuthStore + Send + Sync> UserAuth<T> {
             .to_string();
         let mut city = String::from("");
         if let Some(ref lock_db) = self.login_config.ip_db {
-            let mut db = lock_db.lock().await;
+            let db = lock_db.lock().await;
             if let Some(ref ip) = login_env.login_ip {
                 let bip = *ip;
                 if let Ok(rec) = db.ip_lookup(bip) {
@@ -620,7 +620,10 @@ impl<T: UserAuthStore + Send + Sync> UserSession<UserAuthTokenData, UserAuthData
         self.user_token = token
     }
     async fn get_session_data(&self) -> UserAuthResult<UserAuthData> {
-        self.auth.get_session_data(&self.user_token).await
+        let data = self.auth.get_session_data(&self.user_token).await?;
+        //写入请求日志的span字段
+        tracing::Span::current().record("user_id", data.user_data().user_id);
+        Ok(data)
     }
     async fn refresh_session(&mut self, reset_token: bool) -> UserAuthResult<UserAuthTokenData> {
         let token = self.auth.reload_auth(&self.user_token, reset_token).await?;
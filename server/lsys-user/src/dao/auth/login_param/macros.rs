@@ -21,6 +21,7 @@ macro_rules! impl_auth_valid_code_method {
         /// 验证码生成
         pub fn valid_code(redis: deadpool_redis::Pool) -> lsys_core::ValidCode {
             lsys_core::ValidCode::new(redis, $valid_type.to_string(),true)
+                .with_check_limit(lsys_core::ValidCodeCheckLimit::default())
         }
         /// 获取验证码
         pub async fn valid_code_set<T: lsys_core::ValidCodeData>(
@@ -50,11 +51,15 @@ macro_rules! impl_auth_valid_code_method {
         /// 检测验证码
         pub async fn valid_code_check(
             redis:deadpool_redis::Pool,
+            check_limit: lsys_core::ValidCodeCheckLimit,
             code: &String,
             $($name:$name_type),+
         ) -> UserAuthResult<()> {
             let key = $key_block;
-            Self::valid_code(redis).check_code(&key, code).await?;
+            Self::valid_code(redis)
+                .with_check_limit(check_limit)
+                .check_code(&key, code)
+                .await?;
             Ok(())
         }
         pub async fn valid_code_clear(
@@ -66,7 +66,7 @@ impl LoginParam for EmailCodeLogin {
             .map_err(auth_user_not_found_map!(self.show_name(), "email code"))?;
         email.is_enable()?;
 
-        Self::valid_code_check(redis.to_owned(), &self.code, &self.email).await?;
+        Self::valid_code_check(redis.to_owned(), account.valid_code_limit, &self.code, &self.email).await?;
 
         let user =
             account
@@ -67,7 +67,7 @@ impl LoginParam for MobileCodeLogin {
             .map_err(auth_user_not_found_map!(self.show_name(), "mobile code"))?;
         mobile.is_enable()?;
 
-        Self::valid_code_check(redis.clone(), &self.code, &self.area_code, &self.mobile).await?;
+        Self::valid_code_check(redis.clone(), account.valid_code_limit, &self.code, &self.area_code, &self.mobile).await?;
 
         let user = account
             .user
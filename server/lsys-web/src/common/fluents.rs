@@ -1,7 +1,8 @@
 use lsys_app_notify::dao::NotifyError;
 use lsys_app_sender::dao::SenderError;
 use lsys_core::{
-    fluent_message, ConfigError, FluentBundle, FluentMessage, IntoFluentMessage, ValidCodeError,
+    fluent_message, ConfigError, FluentBundle, FluentMessage, IntoFluentMessage, TaskError,
+    ValidCodeError,
 };
 
 use lsys_logger::dao::LoggerError;
@@ -33,6 +34,7 @@ self_error_fluent_string!(SettingError);
 self_error_fluent_string!(SenderError);
 self_error_fluent_string!(AppsError);
 self_error_fluent_string!(ConfigError);
+self_error_fluent_string!(TaskError);
 self_error_fluent_string!(NotifyError);
 self_error_fluent_string!(LoggerError);
 
@@ -1,6 +1,6 @@
 use lsys_app_notify::dao::NotifyError;
 use lsys_app_sender::dao::SenderError;
-use lsys_core::{ConfigError, FluentBundle, FluentMessage, ValidCodeError};
+use lsys_core::{ConfigError, FluentBundle, FluentMessage, TaskError, ValidCodeError};
 
 use lsys_logger::dao::LoggerError;
 use lsys_rbac::dao::rbac::UserRbacError;
@@ -137,8 +137,14 @@ impl FluentJsonData for ValidCodeError {
             ValidCodeError::NotMatch(err) => json_data.set_sub_code("valid_code").set_data(json!({
                 "type":err.prefix
             })),
+            ValidCodeError::Locked(err) => {
+                json_data.set_sub_code("valid_code_locked").set_data(json!({
+                    "type":err.prefix
+                }))
+            }
             ValidCodeError::Utf8Err(_) => json_data.set_sub_code("valid_code_err"),
             ValidCodeError::Tag(_) => json_data.set_sub_code("valid_code_err"),
+            ValidCodeError::Totp(_) => json_data.set_sub_code("valid_code_err"),
             // ValidCodeError::Create(err) => json_data.set_message(fluent.format_message(&err.into())),
             ValidCodeError::Redis(err) => err.set_data(json_data, fluent),
             ValidCodeError::RedisPool(err) => err.set_data(json_data, fluent),
@@ -212,11 +218,24 @@ impl FluentJsonData for SenderError {
             SenderError::RedisPool(err) => err.set_data(json_data, fluent),
             SenderError::Tera(err) => err.set_data(json_data, fluent),
             SenderError::Setting(err) => err.set_data(json_data, fluent),
+            SenderError::Task(err) => err.set_data(json_data, fluent),
             SenderError::System(_) => json_data,
         }
     }
 }
 
+impl FluentJsonData for TaskError {
+    fn set_data(&self, json_data: JsonData, fluent: &FluentBundle) -> JsonData {
+        let json_data = json_data.set_code(500).set_sub_code("task");
+        match self {
+            TaskError::System(_) => json_data,
+            TaskError::Redis(err) => err.set_data(json_data, fluent),
+            TaskError::RedisPool(err) => err.set_data(json_data, fluent),
+            TaskError::RemoteNotify(_) => json_data,
+        }
+    }
+}
+
 impl FluentJsonData for AppsError {
     fn set_data(&self, json_data: JsonData, fluent: &FluentBundle) -> JsonData {
         let json_data = json_data.set_code(500).set_sub_code("apps");
@@ -238,6 +257,7 @@ impl FluentJsonData for ConfigError {
         match self {
             ConfigError::Io(err) => err.set_data(json_data, fluent),
             ConfigError::Config(err) => err.set_data(json_data, fluent),
+            ConfigError::Validate(_) => json_data,
         }
     }
 }
@@ -63,7 +63,7 @@ impl WebAppCaptcha {
     pub fn valid_code_builder(&self) -> ValidCodeDataCaptcha {
         ValidCodeDataCaptcha::default()
     }
-    pub async fn clear_code(&self, valid_code: &ValidCode, tag: &String) {
+    pub async fn clear_code(&self, valid_code: &ValidCode, tag: &str) {
         let _ = valid_code
             .clear_code(tag, &mut self.valid_code_builder())
             .await;
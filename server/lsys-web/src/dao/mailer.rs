@@ -1,7 +1,7 @@
 use lsys_app::model::AppsModel;
 use lsys_app_sender::{
     dao::{MailSender, SenderError, SenderSmtpConfig, SmtpSenderTask},
-    model::{SenderMailBodyModel, SenderMailMessageModel},
+    model::{SenderMailBodyModel, SenderMailMessageModel, SenderPriority},
 };
 use lsys_core::{fluent_message, AppCore, IntoFluentMessage, RequestEnv};
 use lsys_logger::dao::ChangeLogger;
@@ -66,6 +66,7 @@ impl WebAppMailer {
             to,
             &context.into_json().to_string(),
             &Some(0),
+            SenderPriority::High,
             env_data,
         )
         .await
@@ -78,6 +79,7 @@ impl WebAppMailer {
         to: &str,
         body: &str,
         max_try_num: &Option<u8>,
+        priority: SenderPriority,
         env_data: Option<&RequestEnv>,
     ) -> Result<u64, SenderError> {
         check_email(to).map_err(|e| {
@@ -94,6 +96,7 @@ impl WebAppMailer {
                 &None,
                 &None,
                 max_try_num,
+                priority,
                 env_data,
             )
             .await?;
@@ -171,6 +174,7 @@ impl WebAppMailer {
                 &Some(app.user_id),
                 reply,
                 max_try_num,
+                SenderPriority::Normal,
                 env_data,
             )
             .await
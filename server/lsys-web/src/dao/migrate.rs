@@ -0,0 +1,54 @@
+use lsys_core::{AppCore, AppCoreError, Migrate, MigrateItem};
+use sqlx::{MySql, Pool};
+use tracing::info;
+
+/// 各模块的数据库迁移,按依赖顺序排列
+pub fn web_migrations() -> Vec<MigrateItem> {
+    let mut items = vec![];
+    items.extend(lsys_logger::migrations());
+    items.extend(lsys_setting::migrations());
+    items.extend(lsys_user::migrations());
+    items.extend(lsys_rbac::migrations());
+    items.extend(lsys_app::migrations());
+    items.extend(lsys_app_notify::migrations());
+    items.extend(lsys_app_sender::migrations());
+    #[cfg(feature = "docs")]
+    items.extend(lsys_docs::migrations());
+    #[cfg(feature = "barcode")]
+    items.extend(lsys_app_barcode::migrations());
+    items
+}
+
+/// 创建数据库迁移
+pub fn web_migrate(app_core: &AppCore, db: Pool<MySql>) -> Migrate {
+    let table_prefix = app_core
+        .config
+        .find(None)
+        .get_string("database_table_prefix")
+        .unwrap_or_default();
+    Migrate::new(db, &table_prefix, web_migrations())
+}
+
+//启动时按 database_migrate 配置执行迁移
+// apply 执行未执行的迁移 verify 存在未执行或已修改的迁移时启动失败
+pub(crate) async fn web_migrate_on_start(
+    app_core: &AppCore,
+    db: Pool<MySql>,
+) -> Result<(), AppCoreError> {
+    let migrate_type = app_core
+        .config
+        .find(None)
+        .get_string("database_migrate")
+        .unwrap_or_default();
+    match migrate_type.as_str() {
+        "apply" => {
+            let apply = web_migrate(app_core, db).apply().await?;
+            info!("database migrate apply {} item", apply.len());
+        }
+        "verify" => {
+            web_migrate(app_core, db).verify().await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
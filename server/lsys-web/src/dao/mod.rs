@@ -1,17 +1,21 @@
 use ip2location::LocationDB;
 use lsys_app::dao::{AppConfig, AppDao};
 use lsys_app_barcode::dao::{BarCodeCacheClear, BarCodeConfig, BarCodeDao};
-use lsys_app_notify::dao::Notify;
-use lsys_app_sender::dao::MessageTpls;
+use lsys_app_notify::dao::{Notify, NotifyEventSubscriber};
+use lsys_app_sender::dao::{MessageLogsClearCron, MessageTpls};
 use lsys_core::cache::{LocalCacheClear, LocalCacheClearItem};
-use lsys_core::{AppCore, AppCoreError, FluentMgr, IntoFluentMessage, RemoteNotify};
+use lsys_core::{
+    AppCore, AppCoreError, ConfigRemoteReload, CronMissedPolicy, CronTask, FluentMgr,
+    FluentRemoteReload, IntoFluentMessage, RemoteNotify, TaskBackendConfig, TaskMonitor,
+    ValidCodeCheckLimit,
+};
 #[cfg(feature = "docs")]
 use lsys_docs::dao::{DocsDao, GitRemoteTask};
 #[cfg(feature = "area")]
 use lsys_lib_area::AreaDao;
-use lsys_logger::dao::ChangeLogger;
+use lsys_logger::dao::{ChangeLogger, EventBus, EventOutboxClearCron};
 use lsys_rbac::dao::rbac::RbacLocalCacheClear;
-use lsys_rbac::dao::{RbacConfig, RbacDao, SystemRole};
+use lsys_rbac::dao::{RbacConfig, RbacDao, RbacRoleUserExpireCron, SystemRole};
 use lsys_setting::dao::{Setting, SettingConfig};
 use lsys_user::dao::account::cache::UserAccountLocalCacheClear;
 use lsys_user::dao::account::UserAccountConfig;
@@ -20,6 +24,7 @@ use lsys_user::dao::{UserConfig, UserDao};
 
 use sqlx::{MySql, Pool};
 use std::sync::Arc;
+use std::time::Duration;
 use std::vec;
 use tera::Tera;
 use tokio::sync::Mutex;
@@ -28,6 +33,7 @@ use tracing::{error, info, warn};
 pub mod app;
 mod captcha;
 mod mailer;
+mod migrate;
 mod request;
 pub mod site_config;
 mod smser;
@@ -37,6 +43,7 @@ use self::captcha::WebAppCaptcha;
 use self::mailer::WebAppMailer;
 
 pub use self::captcha::CaptchaKey;
+pub use self::migrate::*;
 pub use self::request::*;
 pub use self::site_config::*;
 use self::smser::WebAppSmser;
@@ -57,30 +64,80 @@ pub struct WebDao {
     pub tera: Arc<Tera>,
     pub setting: Arc<Setting>,
     pub logger: Arc<ChangeLogger>,
+    pub event_bus: Arc<EventBus>,
     #[cfg(feature = "area")]
     pub area: Option<Arc<AreaDao>>,
     pub notify: Arc<Notify>,
-    pub fluent: FluentMgr,
+    pub fluent: Arc<FluentMgr>,
     pub barcode: Arc<BarCodeDao>,
+    pub cron: Arc<CronTask>,
+    pub task_monitor: Arc<TaskMonitor>,
+    pub remote_notify: Arc<RemoteNotify>,
+    pub local_cache: LocalCacheClear,
 }
 
 impl WebDao {
     pub async fn new(app_core: Arc<AppCore>) -> Result<WebDao, AppCoreError> {
-        let path = app_core.config_path(app_core.config.find(None), "fluent_dir")?;
+        let path = app_core.config_path(&app_core.config.find(None), "fluent_dir")?;
         let fluent = FluentMgr::new(path, "app", None).await?;
 
         let db = app_core.create_db().await?;
+        web_migrate_on_start(&app_core, db.clone()).await?;
         let tera = Arc::new(app_core.create_tera(None)?);
         let redis = app_core.create_redis().await?;
-        let remote_notify = Arc::new(RemoteNotify::new(
-            "lsys-remote-notify",
-            app_core.clone(),
-            redis.clone(),
-        )?);
+        app_core.init_snowflake_worker(redis.clone()).await?;
+        let remote_notify = Arc::new(
+            match app_core
+                .config
+                .find(None)
+                .get_string("remote_notify_transport")
+                .unwrap_or_default()
+                .as_str()
+            {
+                "memory" => RemoteNotify::new_memory(&app_core),
+                _ => RemoteNotify::new("lsys-remote-notify", app_core.clone(), redis.clone())?,
+            },
+        );
 
         let use_cache=app_core.config.find(None).get_bool("use_cache").unwrap_or(false);
+        let cache_redis_time = app_core
+            .config
+            .find(None)
+            .get_int("cache_redis_time")
+            .map(|e| if e > 0 { e as u64 } else { 0 })
+            .unwrap_or(0);
+        //验证码校验失败次数限制
+        let valid_code_default = ValidCodeCheckLimit::default();
+        let valid_code_limit = ValidCodeCheckLimit {
+            max_fail: app_core
+                .config
+                .find(None)
+                .get_int("valid_code_max_fail")
+                .map(|e| if e > 0 { e as usize } else { 0 })
+                .unwrap_or(valid_code_default.max_fail),
+            lock_time: app_core
+                .config
+                .find(None)
+                .get_int("valid_code_lock_time")
+                .ok()
+                .filter(|e| *e > 0)
+                .map(|e| e as usize)
+                .unwrap_or(valid_code_default.lock_time),
+        };
 
-        let change_logger = Arc::new(ChangeLogger::new(db.clone()));
+        //领域事件,添加订阅后通过 event_bus.start 启动投递
+        let event_bus = Arc::new(EventBus::new(
+            redis.clone(),
+            db.clone(),
+            app_core.clone(),
+            None,
+            None,
+            None,
+            true,
+        ));
+        let change_logger = Arc::new(
+            ChangeLogger::new(db.clone()).with_event_bus(event_bus.clone()),
+        );
         let setting = Arc::new(
             Setting::new(
                 //app_core.clone(),
@@ -91,6 +148,14 @@ impl WebDao {
             )
             .await?,
         );
+        //加载后台编辑的翻译
+        let fluent = Arc::new(fluent.with_override_loader(setting.fluent.clone()));
+        if let Err(err) = fluent.reload().await {
+            warn!(
+                "fluent override load fail:{}",
+                err.to_fluent_message().default_format()
+            );
+        }
 
         let root_user_id = app_core
             .config
@@ -113,7 +178,7 @@ impl WebDao {
         let login_store = UserAuthRedisStore::new(redis.clone());
         let mut login_config = UserAuthConfig::new(use_cache);
 
-        match app_core.config_path(app_core.config.find(None), "ip_city_db") {
+        match app_core.config_path(&app_core.config.find(None), "ip_city_db") {
             Ok(ip_db_path) => match LocationDB::from_file(&ip_db_path) {
                 Ok(city_db) => {
                     login_config.ip_db = Some(Mutex::new(ip2location::DB::LocationDb(city_db)));
@@ -160,7 +225,9 @@ impl WebDao {
                 remote_notify.clone(),
                 login_store,
                 UserConfig{
-                    account:UserAccountConfig::new(use_cache),
+                    account:UserAccountConfig::new(use_cache)
+                        .use_redis(cache_redis_time)
+                        .valid_code_limit(valid_code_limit),
                     oauth:login_config,
                 }
             )
@@ -225,6 +292,22 @@ impl WebDao {
             }
         });
 
+        //转发领域事件给外部应用
+        let event_notify_types = app_core
+            .config
+            .find(None)
+            .get_array("event_notify_types")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|e| e.to_owned().into_string().ok())
+            .collect::<Vec<String>>();
+        if !event_notify_types.is_empty() {
+            event_bus.subscribe(Arc::new(NotifyEventSubscriber::new(
+                notify.clone(),
+                event_notify_types,
+            )));
+        }
+
         let web_smser = Arc::new(WebAppSmser::new(
             app_core.clone(),
             redis.clone(),
@@ -292,7 +375,40 @@ impl WebDao {
             cache_item.push(Box::new(item))
         }
         let local_cache_clear = LocalCacheClear::new(cache_item);
-        remote_notify.push_run(Box::new(local_cache_clear)).await;
+        remote_notify.push_run(Box::new(local_cache_clear.clone())).await;
+        app_core.config.subscribe(Arc::new(local_cache_clear.clone()));
+
+        //后台任务运行状态及强制释放
+        let mut task_list = web_smser.smser.task_monitor();
+        task_list.push(mailer.mailer.task_monitor());
+        task_list.push(notify.task_monitor());
+        task_list.push(event_bus.task_monitor());
+        let task_monitor = Arc::new(TaskMonitor::new(remote_notify.clone(), task_list));
+        remote_notify
+            .push_run(Box::new(task_monitor.remote_task()))
+            .await;
+
+        //配置重新加载
+        remote_notify
+            .push_run(Box::new(ConfigRemoteReload::new(app_core.clone())))
+            .await;
+        remote_notify
+            .push_run(Box::new(FluentRemoteReload::new(fluent.clone())))
+            .await;
+        let config_watch_time = app_core
+            .config
+            .find(None)
+            .get_int("config_watch_time")
+            .unwrap_or(0);
+        if config_watch_time > 0 {
+            let watch_core = app_core.clone();
+            tokio::spawn(async move {
+                watch_core
+                    .config
+                    .watch(Duration::from_secs(config_watch_time as u64))
+                    .await
+            });
+        }
 
         //git文档 远程同步任务
         #[cfg(feature = "docs")]
@@ -300,20 +416,89 @@ impl WebDao {
             .push_run(Box::new(GitRemoteTask::new(docs.task.clone())))
             .await;
 
+        //集群节点信息,通过心跳发送给其他节点
+        let mut node_features = vec![];
+        if cfg!(feature = "docs") {
+            node_features.push("docs".to_string());
+        }
+        if cfg!(feature = "barcode") {
+            node_features.push("barcode".to_string());
+        }
+        if cfg!(feature = "area") {
+            node_features.push("area".to_string());
+        }
+        remote_notify.set_node_info(env!("CARGO_PKG_VERSION"), node_features);
+
         //远程任务后台任务
+        let listen_notify = remote_notify.clone();
         tokio::spawn(async move {
             //listen redis notify
-            remote_notify.listen().await;
+            listen_notify.listen().await;
+        });
+
+        //定时任务
+        let cron = Arc::new(CronTask::new(
+            TaskBackendConfig::new(app_core.clone(), redis.clone())
+                .create_cron_store("lsys-cron".to_string()),
+            30,
+        ));
+        let sender_log_keep_day = app_core
+            .config
+            .find(None)
+            .get_int("sender_log_keep_day")
+            .unwrap_or(0);
+        if sender_log_keep_day > 0 {
+            if let Err(err) = cron.add(
+                "0 30 3 * * *",
+                CronMissedPolicy::RunOnce,
+                MessageLogsClearCron::new(db.clone(), sender_log_keep_day as u64 * 86400),
+            ) {
+                error!(
+                    "add sender log clear cron fail:{}",
+                    err.to_fluent_message().default_format()
+                );
+            }
+        }
+        let event_outbox_keep_day = app_core
+            .config
+            .find(None)
+            .get_int("event_outbox_keep_day")
+            .unwrap_or(7);
+        if event_outbox_keep_day > 0 {
+            if let Err(err) = cron.add(
+                "0 40 3 * * *",
+                CronMissedPolicy::RunOnce,
+                EventOutboxClearCron::new(db.clone(), event_outbox_keep_day as u64 * 86400),
+            ) {
+                error!(
+                    "add event outbox clear cron fail:{}",
+                    err.to_fluent_message().default_format()
+                );
+            }
+        }
+        if let Err(err) = cron.add(
+            "0 */5 * * * *",
+            CronMissedPolicy::RunOnce,
+            RbacRoleUserExpireCron::new(rbac_dao.rbac.role.clone()),
+        ) {
+            error!(
+                "add rbac role user expire cron fail:{}",
+                err.to_fluent_message().default_format()
+            );
+        }
+        let cron_task = cron.clone();
+        tokio::spawn(async move {
+            cron_task.dispatch().await;
         });
 
         //行政区域地址库数据初始化
         #[cfg(feature = "area")]
-        let area = match app_core.config_path(app_core.config.find(None), "area_code_db") {
+        let area = match app_core.config_path(&app_core.config.find(None), "area_code_db") {
             Ok(code_path) => {
                 match lsys_lib_area::CsvAreaCodeData::from_inner_path(code_path.clone(), true) {
                     Ok(tmp) => {
 
-                        let  geo_data =  match app_core.config_path(app_core.config.find(None), "area_geo_db") {
+                        let  geo_data =  match app_core.config_path(&app_core.config.find(None), "area_geo_db") {
                             Ok(geo_path) => {
                                 match lsys_lib_area::CsvAreaGeoData::from_inner_path(geo_path.clone(), true){
                                     Ok(geo_obj) => {
@@ -332,7 +517,7 @@ impl WebDao {
                         };
                         let data = lsys_lib_area::CsvAreaData::new(tmp, geo_data);
                         let area_index_dir = app_core
-                            .config_path(app_core.config.find(None), "area_index_dir")
+                            .config_path(&app_core.config.find(None), "area_index_dir")
                             .unwrap_or_else(|_| {
                                 let mut index_dir = std::env::temp_dir();
                                 index_dir.push("lsys_area_cache");
@@ -342,7 +527,7 @@ impl WebDao {
                             .config
                             .find(None)
                             .get_int("area_index_size")
-                            .map(|e| e.abs() as usize)
+                            .map(|e| e.unsigned_abs() as usize)
                             .ok();
                         let area_store =
                             lsys_lib_area::AreaStoreDisk::new(area_index_dir, area_index_size)
@@ -393,13 +578,33 @@ impl WebDao {
             tera,
             setting,
             logger: change_logger,
+            event_bus,
             #[cfg(feature = "area")]
             area,
             notify,
             #[cfg(feature = "barcode")]
             barcode,
+            cron,
+            task_monitor,
+            remote_notify,
+            local_cache: local_cache_clear,
         })
     }
+    //停止后台任务,等待执行中任务完成,服务退出前调用
+    pub async fn shutdown(&self) {
+        let timeout = self
+            .app_core
+            .config
+            .find(None)
+            .get_int("task_shutdown_timeout")
+            .map(|e| if e > 0 { e as u64 } else { 0 })
+            .unwrap_or(30);
+        info!("shutdown background task,wait {} seconds", timeout);
+        self.task_monitor
+            .shutdown(Duration::from_secs(timeout))
+            .await;
+        self.app_core.snowflake_worker().release().await;
+    }
     pub fn bind_addr(&self) -> String {
         let host = self
             .app_core
@@ -10,7 +10,7 @@ use lsys_app_sender::{
         SenderNetEaseConfig, SenderResult, SenderTenYunConfig, SmsSender, TenYunSendStatus,
         TenyunSenderTask,
     },
-    model::{SenderSmsBodyModel, SenderSmsMessageModel},
+    model::{SenderPriority, SenderSmsBodyModel, SenderSmsMessageModel},
 };
 use lsys_core::{fluent_message, AppCore, IntoFluentMessage, RequestEnv};
 use lsys_logger::dao::ChangeLogger;
@@ -140,6 +140,7 @@ impl WebAppSmser {
                 send_time,
                 &Some(app.user_id),
                 max_try_num,
+                SenderPriority::Normal,
                 env_data,
             )
             .await?;
@@ -169,6 +170,7 @@ impl WebAppSmser {
             .await
     }
     // 短信发送接口
+    #[allow(clippy::too_many_arguments)]
     async fn send(
         &self,
         tpl_type: &str,
@@ -176,6 +178,7 @@ impl WebAppSmser {
         mobile: &str,
         body: &str,
         max_try_num: &Option<u8>,
+        priority: SenderPriority,
         env_data: Option<&RequestEnv>,
     ) -> Result<u64, SenderError> {
         check_mobile(area, mobile).map_err(|e| {
@@ -194,6 +197,7 @@ impl WebAppSmser {
                 &None,
                 &None,
                 max_try_num,
+                priority,
                 env_data,
             )
             .await?;
@@ -228,6 +232,7 @@ impl WebAppSmser {
             mobile,
             &json!(context).to_string(),
             &Some(0),
+            SenderPriority::High,
             env_data,
         )
         .await
@@ -13,7 +13,7 @@ use crate::CaptchaParam;
 use super::{ShowUserAuthData, WebUser};
 
 impl WebUser {
-    pub async fn user_login<'t, TO: LoginParam, T: UserAuthStore + Send + Sync>(
+    pub async fn user_login<TO: LoginParam, T: UserAuthStore + Send + Sync>(
         &self,
         user_session: &RwLock<UserAuthSession<T>>,
         req_env: &RequestEnv,
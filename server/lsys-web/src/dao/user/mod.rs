@@ -90,11 +90,7 @@ impl From<UserAuthData> for ShowUserAuthData {
             LoginData::External(val) => ("external", json!(val)),
         };
         let stime = login_type.time_out as u64;
-        let login_time = if time_out > stime {
-            time_out - stime
-        } else {
-            0
-        };
+        let login_time = time_out.saturating_sub(stime);
         ShowUserAuthData {
             login_type: show_login_type,
             login_data: show_login_data,
@@ -48,7 +48,6 @@ impl WebUser {
     }
     //得到外部数据转为内部UserExternalModel
     pub async fn user_external_login<
-        't,
         T: OauthLogin<L, P, D>,
         L: OauthLoginParam + Send + Sync,
         P: OauthCallbackParam + Send + Sync,
@@ -155,7 +154,6 @@ impl WebUser {
     }
     /// 已登陆后绑定外部账号
     pub async fn user_external_bind<
-        't,
         T: OauthLogin<L, P, D>,
         L: OauthLoginParam + Send + Sync,
         P: OauthCallbackParam + Send + Sync,
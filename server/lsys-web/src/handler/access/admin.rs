@@ -175,6 +175,181 @@ impl RbacResTpl for AccessAdminChangeLogsView {
     }
 }
 
+pub struct AccessAdminTask {
+    pub user_id: u64,
+}
+#[async_trait::async_trait]
+impl RbacCheck for AccessAdminTask {
+    async fn check<'t>(
+        &self,
+        access: &'t RbacAccess,
+        relation: &'t [RoleRelationKey],
+    ) -> UserRbacResult<()> {
+        access
+            .check(
+                self.user_id,
+                relation,
+                &[AccessRes::system("global-system", &["task"], &[])],
+            )
+            .await
+    }
+    fn depends(&self) -> Vec<Box<RbacCheckDepend>> {
+        vec![Box::new(AccessAdminManage {
+            user_id: self.user_id,
+        })]
+    }
+}
+impl RbacResTpl for AccessAdminTask {
+    fn tpl_data() -> Vec<ResTpl> {
+        vec![ResTpl {
+            tags: vec!["system", "task"],
+            user: false,
+            key: "global-system",
+            ops: vec!["task"],
+        }]
+    }
+}
+
+pub struct AccessAdminNode {
+    pub user_id: u64,
+}
+#[async_trait::async_trait]
+impl RbacCheck for AccessAdminNode {
+    async fn check<'t>(
+        &self,
+        access: &'t RbacAccess,
+        relation: &'t [RoleRelationKey],
+    ) -> UserRbacResult<()> {
+        access
+            .check(
+                self.user_id,
+                relation,
+                &[AccessRes::system("global-system", &["node"], &[])],
+            )
+            .await
+    }
+    fn depends(&self) -> Vec<Box<RbacCheckDepend>> {
+        vec![Box::new(AccessAdminManage {
+            user_id: self.user_id,
+        })]
+    }
+}
+impl RbacResTpl for AccessAdminNode {
+    fn tpl_data() -> Vec<ResTpl> {
+        vec![ResTpl {
+            tags: vec!["system", "node"],
+            user: false,
+            key: "global-system",
+            ops: vec!["node"],
+        }]
+    }
+}
+
+pub struct AccessAdminCache {
+    pub user_id: u64,
+}
+#[async_trait::async_trait]
+impl RbacCheck for AccessAdminCache {
+    async fn check<'t>(
+        &self,
+        access: &'t RbacAccess,
+        relation: &'t [RoleRelationKey],
+    ) -> UserRbacResult<()> {
+        access
+            .check(
+                self.user_id,
+                relation,
+                &[AccessRes::system("global-system", &["cache"], &[])],
+            )
+            .await
+    }
+    fn depends(&self) -> Vec<Box<RbacCheckDepend>> {
+        vec![Box::new(AccessAdminManage {
+            user_id: self.user_id,
+        })]
+    }
+}
+impl RbacResTpl for AccessAdminCache {
+    fn tpl_data() -> Vec<ResTpl> {
+        vec![ResTpl {
+            tags: vec!["system", "cache"],
+            user: false,
+            key: "global-system",
+            ops: vec!["cache"],
+        }]
+    }
+}
+
+pub struct AccessAdminConfigReload {
+    pub user_id: u64,
+}
+#[async_trait::async_trait]
+impl RbacCheck for AccessAdminConfigReload {
+    async fn check<'t>(
+        &self,
+        access: &'t RbacAccess,
+        relation: &'t [RoleRelationKey],
+    ) -> UserRbacResult<()> {
+        access
+            .check(
+                self.user_id,
+                relation,
+                &[AccessRes::system("global-system", &["config-reload"], &[])],
+            )
+            .await
+    }
+    fn depends(&self) -> Vec<Box<RbacCheckDepend>> {
+        vec![Box::new(AccessAdminManage {
+            user_id: self.user_id,
+        })]
+    }
+}
+impl RbacResTpl for AccessAdminConfigReload {
+    fn tpl_data() -> Vec<ResTpl> {
+        vec![ResTpl {
+            tags: vec!["system", "config"],
+            user: false,
+            key: "global-system",
+            ops: vec!["config-reload"],
+        }]
+    }
+}
+
+pub struct AccessAdminFluent {
+    pub user_id: u64,
+}
+#[async_trait::async_trait]
+impl RbacCheck for AccessAdminFluent {
+    async fn check<'t>(
+        &self,
+        access: &'t RbacAccess,
+        relation: &'t [RoleRelationKey],
+    ) -> UserRbacResult<()> {
+        access
+            .check(
+                self.user_id,
+                relation,
+                &[AccessRes::system("global-system", &["fluent"], &[])],
+            )
+            .await
+    }
+    fn depends(&self) -> Vec<Box<RbacCheckDepend>> {
+        vec![Box::new(AccessAdminManage {
+            user_id: self.user_id,
+        })]
+    }
+}
+impl RbacResTpl for AccessAdminFluent {
+    fn tpl_data() -> Vec<ResTpl> {
+        vec![ResTpl {
+            tags: vec!["system", "fluent"],
+            user: false,
+            key: "global-system",
+            ops: vec!["fluent"],
+        }]
+    }
+}
+
 pub struct AccessAdminDocsEdit {
     pub user_id: u64,
 }
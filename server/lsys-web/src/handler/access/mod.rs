@@ -39,6 +39,11 @@ pub fn res_tpls() -> Vec<ResTpl> {
         AccessAdminChangeLogsView,
         AccessAdminDocsEdit,
         AccessAdminSetting,
+        AccessAdminTask,
+        AccessAdminNode,
+        AccessAdminCache,
+        AccessAdminConfigReload,
+        AccessAdminFluent,
         AccessAdminUserFull,
         AccessAdminUserBase,
         AccessAppSenderDoSms,
@@ -9,4 +9,5 @@ pub use super::common::docs;
 pub use super::common::rbac;
 pub use super::common::sender;
 pub use super::common::setting;
+pub use super::common::system;
 pub use super::common::user;
@@ -1,6 +1,8 @@
 use lsys_app::model::AppsModel;
+use lsys_core::fluent_message;
 use lsys_rbac::dao::{AccessRes, RoleRelationKey};
 use serde::Deserialize;
+use serde_json::json;
 
 use crate::dao::RequestDao;
 
@@ -80,6 +82,67 @@ pub async fn app_rbac_check(
     Ok(JsonData::default())
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExplainParam {
+    pub user_id: u64,
+    pub relation_key: Vec<RelationParam>,
+    pub check_res: Vec<CheckResParam>,
+}
+
+pub async fn app_rbac_explain(
+    req_dao: &RequestDao,
+    app: &AppsModel,
+    param: ExplainParam,
+) -> JsonResult<JsonData> {
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessSubAppRbacCheck {
+                user_id: app.user_id,
+                app_id: app.id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    //应用只能查看其所属用户资源的授权详情
+    if let Some(res) = param.check_res.iter().find(|e| e.user_id != app.user_id) {
+        return Ok(req_dao
+            .fluent_json_data(fluent_message!("rbac-explain-bad-res-user",{
+                "res":res.res.clone()
+            }))
+            .set_code(403)
+            .set_sub_code("bad_res_user"));
+    }
+    let dao = &req_dao.web_dao.user.rbac_dao.rbac.access;
+    let rkey = param
+        .relation_key
+        .into_iter()
+        .map(|e| RoleRelationKey {
+            relation_key: e.role_key,
+            user_id: e.user_id,
+        })
+        .collect::<Vec<RoleRelationKey>>();
+    let check_res = param
+        .check_res
+        .into_iter()
+        .map(|p| AccessRes {
+            res: p.res,
+            user_id: p.user_id,
+            ops: p.ops,
+            option_ops: p.option_ops.unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    let explain = dao
+        .check_explain(param.user_id, &rkey, &check_res)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::data(json!({ "data": explain })))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct MenuParam {
     pub user_id: u64,
@@ -8,6 +8,7 @@ pub mod app;
 pub mod rbac;
 pub mod sender;
 pub mod setting;
+pub mod system;
 pub mod user;
 
 #[cfg(feature = "docs")]
@@ -1,10 +1,10 @@
-use lsys_rbac::dao::RbacDao;
+use lsys_rbac::dao::{AccessRes, RbacDao, RoleRelationKey};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::{dao::RequestDao, JsonData, JsonResult};
+use crate::{dao::RequestDao, handler::access::AccessRoleView, JsonData, JsonResult, PageParam};
 
-use super::{access_check, RbacAccessParam};
+use super::{access_check, RbacAccessParam, RelationParam};
 
 pub async fn rbac_access_check(
     user_id: u64,
@@ -46,3 +46,120 @@ pub async fn rbac_menu_check(
     }
     Ok(JsonData::data(json!({ "data": out })))
 }
+
+#[derive(Debug, Deserialize)]
+pub struct RbacExplainResParam {
+    pub res: String,                     //资源KEY
+    pub user_id: u64,                    //资源用户ID
+    pub ops: Vec<String>,                //授权列表
+    pub option_ops: Option<Vec<String>>, //可选授权列表
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RbacExplainParam {
+    pub user_id: u64, //被检测的访问用户ID,0为游客
+    pub relation: Option<Vec<RelationParam>>,
+    pub check_res: Vec<RbacExplainResParam>,
+}
+
+//查看指定用户访问资源时的授权检测过程
+pub async fn rbac_access_explain(
+    param: RbacExplainParam,
+    rbac_dao: &RbacDao,
+    user_id: u64,
+    req_dao: &RequestDao,
+) -> JsonResult<JsonData> {
+    //检测过程会列出各用户的相关角色,需有查看系统角色权限
+    rbac_dao
+        .rbac
+        .check(
+            &AccessRoleView {
+                user_id,
+                res_user_id: 0,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let relation = param
+        .relation
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| RoleRelationKey {
+            relation_key: e.role_key,
+            user_id: e.user_id,
+        })
+        .collect::<Vec<RoleRelationKey>>();
+    let check_res = param
+        .check_res
+        .into_iter()
+        .map(|e| AccessRes {
+            res: e.res,
+            user_id: e.user_id,
+            ops: e.ops,
+            option_ops: e.option_ops.unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    let explain = rbac_dao
+        .rbac
+        .access
+        .check_explain(param.user_id, &relation, &check_res)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::data(json!({ "data": explain })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RbacOpAccessParam {
+    pub res: String,      //资源KEY
+    pub res_user_id: u64, //资源用户ID
+    pub op: String,       //操作KEY
+    pub count_num: Option<bool>,
+    pub page: Option<PageParam>, //指定用户分页
+}
+
+//查询可对指定资源操作的用户及用户类型
+pub async fn rbac_op_access(
+    param: RbacOpAccessParam,
+    rbac_dao: &RbacDao,
+    user_id: u64,
+    req_dao: &RequestDao,
+) -> JsonResult<JsonData> {
+    rbac_dao
+        .rbac
+        .check(
+            &AccessRoleView {
+                user_id,
+                res_user_id: 0,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let dao = &rbac_dao.rbac.access;
+    let range = dao
+        .op_access_range(&param.res, param.res_user_id, &param.op)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let data = dao
+        .op_access_user(
+            &param.res,
+            param.res_user_id,
+            &param.op,
+            &Some(param.page.unwrap_or_default().into()),
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let total = if param.count_num.unwrap_or(false) {
+        Some(
+            dao.op_access_user_count(&param.res, param.res_user_id, &param.op)
+                .await
+                .map_err(|e| req_dao.fluent_json_data(e))?,
+        )
+    } else {
+        None
+    };
+    Ok(JsonData::data(
+        json!({ "range": range, "data": data, "total": total }),
+    ))
+}
@@ -1,6 +1,7 @@
 mod access;
 mod res;
 mod role;
+mod transfer;
 
 use crate::handler::access::{
     AccessAdminChangeLogsView, AccessAdminDocsEdit, AccessAdminMailConfig, AccessAdminManage,
@@ -12,6 +13,7 @@ use lsys_core::fluent_message;
 use lsys_rbac::dao::{RbacDao, RoleRelationKey, UserRbacError, UserRbacResult};
 pub use res::*;
 pub use role::*;
+pub use transfer::*;
 use serde::Deserialize;
 use serde_json::Value;
 
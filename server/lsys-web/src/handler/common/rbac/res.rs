@@ -401,7 +401,7 @@ pub async fn rbac_all_res_list(
         })
         .map(|e| {
             let name = req_dao.fluent_string(FluentMessage {
-                id: format!("res-{}", e.key.replace('{', "").replace('}', "")),
+                id: format!("res-{}", e.key.replace(['{', '}'], "")),
                 crate_name: env!("CARGO_PKG_NAME").to_string(),
                 data: vec![],
             });
@@ -412,7 +412,7 @@ pub async fn rbac_all_res_list(
                     json!({
                         "key":e,
                         "name": req_dao.fluent_string(FluentMessage {
-                            id: format!("res-op-{}", e.replace('{', "").replace('}', "")),
+                            id: format!("res-op-{}", e.replace(['{', '}'], "")),
                             crate_name: env!("CARGO_PKG_NAME").to_string(),
                             data: vec![],
                         })
@@ -27,6 +27,8 @@ use serde_json::Value;
 #[derive(Debug, Deserialize)]
 pub struct RoleUserParam {
     user_id: u64,
+    #[serde(default)]
+    start_time: u64,
     timeout: u64,
 }
 
@@ -34,6 +36,7 @@ impl From<RoleUserParam> for RoleAddUser {
     fn from(p: RoleUserParam) -> Self {
         RoleAddUser {
             user_id: p.user_id,
+            start_time: p.start_time,
             timeout: p.timeout,
         }
     }
@@ -480,6 +483,55 @@ pub async fn rbac_role_list_user(
 
     Ok(JsonData::data(json!({ "data": data,"total":total })))
 }
+
+#[derive(Debug, Deserialize)]
+pub struct RoleExpiringUserParam {
+    pub count_num: Option<bool>,
+    pub role_id: u64,
+    pub within: u64, //从当前起多少秒内超时
+    pub page: Option<PageParam>,
+}
+pub async fn rbac_role_expiring_user(
+    param: RoleExpiringUserParam,
+    rbac_dao: &RbacDao,
+    user_id: u64,
+    req_dao: &RequestDao,
+) -> JsonResult<JsonData> {
+    let dao = &rbac_dao.rbac.role;
+    let role = dao
+        .find_by_id(&param.role_id)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_dao
+        .rbac
+        .check(
+            &AccessRoleView {
+                user_id,
+                res_user_id: role.user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let data = dao
+        .role_user_expiring(
+            role.id,
+            param.within,
+            &Some(param.page.unwrap_or_default().into()),
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let total = if param.count_num.unwrap_or(false) {
+        Some(
+            dao.role_user_expiring_count(role.id, param.within)
+                .await
+                .map_err(|e| req_dao.fluent_json_data(e))?,
+        )
+    } else {
+        None
+    };
+    Ok(JsonData::data(json!({ "data": data,"total":total })))
+}
 #[derive(Debug, Deserialize)]
 pub struct RoleAddUserParam {
     pub role_id: u64,
@@ -595,6 +647,86 @@ pub async fn rbac_role_delete(
     Ok(JsonData::default())
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RoleSetParentParam {
+    pub role_id: u64,
+    pub parent_id: Vec<u64>,
+}
+pub async fn rbac_role_set_parent(
+    param: RoleSetParentParam,
+    rbac_dao: &RbacDao,
+    user_id: u64,
+    req_dao: &RequestDao,
+) -> JsonResult<JsonData> {
+    let dao = &rbac_dao.rbac.role;
+    let role = dao
+        .find_by_id(&param.role_id)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_dao
+        .rbac
+        .check(
+            &AccessRoleEdit {
+                user_id,
+                res_user_id: role.user_id,
+                op_range: None,
+                op_param: None,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    dao.role_set_parents(
+        &role,
+        &param.parent_id,
+        user_id,
+        None,
+        Some(&req_dao.req_env),
+    )
+    .await
+    .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::default())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RoleParentParam {
+    pub role_id: u64,
+}
+pub async fn rbac_role_parent(
+    param: RoleParentParam,
+    rbac_dao: &RbacDao,
+    user_id: u64,
+    req_dao: &RequestDao,
+) -> JsonResult<JsonData> {
+    let dao = &rbac_dao.rbac.role;
+    let role = dao
+        .find_by_id(&param.role_id)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_dao
+        .rbac
+        .check(
+            &AccessRoleView {
+                user_id,
+                res_user_id: role.user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let data = dao
+        .role_get_parents(&[role.id])
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?
+        .remove(&role.id)
+        .unwrap_or_default();
+    let child = dao
+        .role_get_children(role.id)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::data(json!({ "data": data,"child_id":child })))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RoleListDataParam {
     pub count_num: Option<bool>,
@@ -0,0 +1,134 @@
+use crate::{
+    dao::RequestDao,
+    handler::access::{AccessResEdit, AccessResView, AccessRoleEdit, AccessRoleView, RoleOpCheck},
+    {JsonData, JsonResult},
+};
+
+use lsys_rbac::dao::{RbacDao, RbacTransferData};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+pub struct RbacExportParam {
+    pub user_id: Option<u64>,
+}
+pub async fn rbac_export(
+    param: RbacExportParam,
+    rbac_dao: &RbacDao,
+    user_id: u64,
+    req_dao: &RequestDao,
+) -> JsonResult<JsonData> {
+    let export_user_id = param.user_id.unwrap_or(user_id);
+    rbac_dao
+        .rbac
+        .check(
+            &AccessResView {
+                user_id,
+                res_user_id: export_user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_dao
+        .rbac
+        .check(
+            &AccessRoleView {
+                user_id,
+                res_user_id: export_user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let data = rbac_dao
+        .rbac
+        .transfer
+        .export(export_user_id)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::data(json!({ "data": data })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RbacImportParam {
+    pub user_id: Option<u64>,
+    pub data: RbacTransferData,
+    pub remove_miss: Option<bool>,
+    pub dry_run: Option<bool>,
+}
+pub async fn rbac_import(
+    param: RbacImportParam,
+    rbac_dao: &RbacDao,
+    user_id: u64,
+    req_dao: &RequestDao,
+) -> JsonResult<JsonData> {
+    let import_user_id = param.user_id.unwrap_or(user_id);
+    rbac_dao
+        .rbac
+        .check(
+            &AccessResEdit {
+                user_id,
+                res_user_id: import_user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    //按导入角色的操作范围分别检查角色编辑权限
+    let mut op_ranges = param
+        .data
+        .role
+        .iter()
+        .map(|e| Some(e.res_op_range))
+        .collect::<Vec<_>>();
+    op_ranges.sort();
+    op_ranges.dedup();
+    if op_ranges.is_empty() {
+        op_ranges.push(None);
+    }
+    for op_range in op_ranges {
+        let op_param = param
+            .data
+            .role
+            .iter()
+            .filter(|e| Some(e.res_op_range) == op_range)
+            .flat_map(|e| e.ops.iter())
+            .map(|e| RoleOpCheck {
+                op_id: 0,
+                op_user_id: if e.res_user_id == param.data.user_id {
+                    import_user_id
+                } else {
+                    e.res_user_id
+                },
+            })
+            .collect::<Vec<_>>();
+        rbac_dao
+            .rbac
+            .check(
+                &AccessRoleEdit {
+                    user_id,
+                    res_user_id: import_user_id,
+                    op_range,
+                    op_param: Some(op_param),
+                },
+                None,
+            )
+            .await
+            .map_err(|e| req_dao.fluent_json_data(e))?;
+    }
+    let changes = rbac_dao
+        .rbac
+        .transfer
+        .import(
+            import_user_id,
+            &param.data,
+            param.remove_miss.unwrap_or(false),
+            param.dry_run.unwrap_or(false),
+            user_id,
+            Some(&req_dao.req_env),
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::data(json!({ "data": changes })))
+}
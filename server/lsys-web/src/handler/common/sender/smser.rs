@@ -26,7 +26,7 @@ pub struct SmserMessageLogParam {
     pub page: Option<PageParam>,
 }
 
-pub async fn smser_message_log<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_message_log<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserMessageLogParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -102,7 +102,7 @@ pub struct SmserMessageBodyParam {
     pub message_id: String,
 }
 
-pub async fn smser_message_body<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_message_body<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserMessageBodyParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -180,7 +180,7 @@ pub struct SmserMessageListParam {
     pub limit: Option<LimitParam>,
 }
 
-pub async fn smser_message_list<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_message_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserMessageListParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -313,7 +313,7 @@ pub struct SmserMessageCancelParam {
     pub message_id: String,
 }
 
-pub async fn smser_message_cancel<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_message_cancel<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserMessageCancelParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -392,7 +392,7 @@ pub struct SmserConfigAddParam {
     pub config_data: Value,
 }
 
-pub async fn smser_config_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_config_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserConfigAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -444,7 +444,7 @@ pub async fn smser_config_add<'t, T: SessionTokenData, D: SessionData, S: UserSe
 pub struct SmserConfigDeleteParam {
     pub config_id: u64,
 }
-pub async fn smser_config_del<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_config_del<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserConfigDeleteParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -506,7 +506,7 @@ pub struct SmserConfigListParam {
     pub app_id: Option<u64>,
 }
 
-pub async fn smser_config_list<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_config_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserConfigListParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -704,7 +704,7 @@ pub async fn smser_tpl_config_list<T: SessionTokenData, D: SessionData, S: UserS
     .await
 }
 
-pub async fn smser_tpl_config_del<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_tpl_config_del<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: TplConfigDelParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -728,7 +728,7 @@ pub struct SmserMessageSendParam {
     pub send_time: Option<String>,
 }
 //后台界面发送短信接口
-pub async fn smser_message_send<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_message_send<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserMessageSendParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -31,7 +31,7 @@ pub struct SmserHwConfigListParam {
     pub full_data: Option<bool>,
 }
 
-pub async fn smser_hw_config_list<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_hw_config_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserHwConfigListParam,
     callback_call: impl Fn(&SettingData<HwYunConfig>) -> String,
     req_dao: &RequestAuthDao<T, D, S>,
@@ -106,7 +106,7 @@ pub struct SmserHwConfigAddParam {
     pub callback_key: String,
 }
 
-pub async fn smser_hw_config_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_hw_config_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserHwConfigAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -158,7 +158,7 @@ pub struct SmserHwConfigEditParam {
     pub callback_key: String,
 }
 
-pub async fn smser_hw_config_edit<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_hw_config_edit<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserHwConfigEditParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -205,7 +205,7 @@ pub struct SmserHwConfigDelParam {
     pub id: u64,
 }
 
-pub async fn smser_hw_config_del<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_hw_config_del<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserHwConfigDelParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
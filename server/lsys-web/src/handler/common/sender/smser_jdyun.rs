@@ -26,7 +26,7 @@ pub struct ShowJDYunConfig {
     pub limit: u16,
 }
 
-pub async fn smser_jd_config_list<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_jd_config_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserJDConfigListParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -96,7 +96,7 @@ pub struct SmserJDConfigAddParam {
     pub limit: Option<u16>,
 }
 
-pub async fn smser_jd_config_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_jd_config_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserJDConfigAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -146,7 +146,7 @@ pub struct SmserJDConfigEditParam {
     pub limit: Option<u16>,
 }
 
-pub async fn smser_jd_config_edit<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_jd_config_edit<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserJDConfigEditParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -192,7 +192,7 @@ pub struct SmserJDConfigDelParam {
     pub id: u64,
 }
 
-pub async fn smser_jd_config_del<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_jd_config_del<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserJDConfigDelParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -107,7 +107,7 @@ pub struct SmserTenConfigAddParam {
     pub callback_key: String,
 }
 
-pub async fn smser_ten_config_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_ten_config_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserTenConfigAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -209,7 +209,7 @@ pub struct SmserTenConfigDelParam {
     pub id: u64,
 }
 
-pub async fn smser_ten_config_del<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn smser_ten_config_del<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: SmserTenConfigDelParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
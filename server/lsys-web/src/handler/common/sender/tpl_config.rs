@@ -15,7 +15,6 @@ pub struct TplConfigDelParam {
 }
 
 pub(crate) async fn tpl_config_del<
-    't,
     T: SessionTokenData,
     D: SessionData,
     S: UserSession<T, D>,
@@ -76,7 +75,6 @@ pub struct TplConfigListParam {
 }
 //模板列表
 pub(crate) async fn tpl_config_list<
-    't,
     T: SessionTokenData,
     D: SessionData,
     S: UserSession<T, D>,
@@ -60,7 +60,6 @@ pub async fn setting_set<
 }
 
 pub async fn setting_get<
-    'a,
     A: SettingKey + SettingDecode + SettingEncode + Serialize + Default,
     T: SessionTokenData,
     D: SessionData,
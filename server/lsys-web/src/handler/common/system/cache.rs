@@ -0,0 +1,79 @@
+use crate::{
+    dao::RequestAuthDao,
+    handler::access::AccessAdminCache,
+    {JsonData, JsonResult},
+};
+
+use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
+use serde::Deserialize;
+use serde_json::json;
+
+pub async fn system_cache_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessAdminCache {
+                user_id: req_auth.user_data().user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let data = req_dao.web_dao.local_cache.stats().await;
+    Ok(JsonData::data(json!({
+        "hostname": req_dao.web_dao.remote_notify.hostname(),
+        "data": data,
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemCacheClearParam {
+    pub cache_name: String,
+    //不传时清理该缓存全部数据
+    pub key: Option<String>,
+}
+
+pub async fn system_cache_clear<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: SystemCacheClearParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessAdminCache {
+                user_id: req_auth.user_data().user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .local_cache
+        .clear(&param.cache_name, param.key.as_deref())
+        .await
+        .map_err(JsonData::message_error)?;
+    Ok(JsonData::default())
+}
@@ -0,0 +1,62 @@
+use crate::{
+    dao::RequestAuthDao,
+    handler::access::AccessAdminConfigReload,
+    {JsonData, JsonResult},
+};
+
+use lsys_core::{config_cluster_reload, IntoFluentMessage};
+use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+pub struct SystemConfigReloadParam {
+    //是否通知所有服务器重新加载
+    pub cluster: Option<bool>,
+}
+
+pub async fn system_config_reload<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: SystemConfigReloadParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessAdminConfigReload {
+                user_id: req_auth.user_data().user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    if !param.cluster.unwrap_or(false) {
+        req_dao
+            .web_dao
+            .app_core
+            .config
+            .reload()
+            .await
+            .map_err(|e| req_dao.fluent_json_data(e))?;
+        return Ok(JsonData::default());
+    }
+    let data = config_cluster_reload(&req_dao.web_dao.remote_notify, 3000)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e.to_fluent_message()))?
+        .into_iter()
+        .map(|e| match e.data {
+            Ok(_) => json!({ "hostname": e.from_host, "status": true }),
+            Err(err) => json!({ "hostname": e.from_host, "status": false, "message": err }),
+        })
+        .collect::<Vec<_>>();
+    Ok(JsonData::data(json!({ "data": data })))
+}
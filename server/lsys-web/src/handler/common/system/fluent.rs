@@ -0,0 +1,224 @@
+use crate::{
+    dao::RequestAuthDao,
+    handler::access::AccessAdminFluent,
+    {JsonData, JsonResult},
+};
+
+use lsys_core::{fluent_cluster_reload, FluentOverrideItem, IntoFluentMessage};
+use lsys_setting::dao::FluentOverrideConfig;
+use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+async fn fluent_check<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<u64> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessAdminFluent {
+                user_id: req_auth.user_data().user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(req_auth.user_data().user_id)
+}
+
+//通知所有服务器重新加载翻译,包含本机
+async fn fluent_reload<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<Vec<Value>> {
+    Ok(fluent_cluster_reload(&req_dao.web_dao.remote_notify, 3000)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e.to_fluent_message()))?
+        .into_iter()
+        .map(|e| match e.data {
+            Ok(_) => json!({ "hostname": e.from_host, "status": true }),
+            Err(err) => json!({ "hostname": e.from_host, "status": false, "message": err }),
+        })
+        .collect::<Vec<_>>())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemFluentKeysParam {
+    pub crate_name: Option<String>,
+    //仅返回该语言未翻译的KEY
+    pub missing_lang: Option<String>,
+}
+
+pub async fn system_fluent_keys<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: SystemFluentKeysParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    fluent_check(req_dao).await?;
+    let data = req_dao
+        .web_dao
+        .fluent
+        .message_keys(param.crate_name.as_deref())
+        .into_iter()
+        .filter(|e| match &param.missing_lang {
+            Some(lang) => e.missing.contains(lang),
+            None => true,
+        })
+        .collect::<Vec<_>>();
+    Ok(JsonData::data(json!({
+        "langs": req_dao.web_dao.fluent.langs(),
+        "data": data,
+        "total": data.len(),
+    })))
+}
+
+pub async fn system_fluent_override_list<
+    T: SessionTokenData,
+    D: SessionData,
+    S: UserSession<T, D>,
+>(
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    fluent_check(req_dao).await?;
+    let data = req_dao
+        .web_dao
+        .setting
+        .fluent
+        .list_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?
+        .into_iter()
+        .map(|e| {
+            json!({
+               "id": e.model().id,
+               "lang": e.lang,
+               "crate_name": e.crate_name,
+               "key": e.key,
+               "value": e.value,
+               "change_user_id": e.model().change_user_id,
+               "change_time": e.model().change_time,
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(JsonData::data(json!({ "data": data })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemFluentOverrideSetParam {
+    pub lang: String,
+    pub crate_name: String,
+    pub key: String,
+    pub value: String,
+}
+
+pub async fn system_fluent_override_set<
+    T: SessionTokenData,
+    D: SessionData,
+    S: UserSession<T, D>,
+>(
+    param: SystemFluentOverrideSetParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let user_id = fluent_check(req_dao).await?;
+    if !req_dao.web_dao.fluent.langs().contains(&param.lang) {
+        return Err(JsonData::message(format!("not find lang:{}", param.lang)));
+    }
+    if param.crate_name.trim().is_empty() {
+        return Err(JsonData::message("crate name can't be empty"));
+    }
+    let item = FluentOverrideItem {
+        lang: param.lang,
+        crate_name: param.crate_name.trim().to_owned(),
+        key: param.key.trim().to_owned(),
+        value: param.value,
+    };
+    item.check()
+        .map_err(|e| req_dao.fluent_json_data(e.to_fluent_message()))?;
+    let id = req_dao
+        .web_dao
+        .setting
+        .fluent
+        .set(
+            &FluentOverrideConfig {
+                lang: item.lang,
+                crate_name: item.crate_name,
+                key: item.key,
+                value: item.value,
+            },
+            &user_id,
+            Some(&req_dao.req_env),
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let reload = fluent_reload(req_dao).await?;
+    Ok(JsonData::data(json!({ "id": id, "reload": reload })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemFluentOverrideDelParam {
+    pub lang: String,
+    pub crate_name: String,
+    pub key: String,
+}
+
+pub async fn system_fluent_override_del<
+    T: SessionTokenData,
+    D: SessionData,
+    S: UserSession<T, D>,
+>(
+    param: SystemFluentOverrideDelParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let user_id = fluent_check(req_dao).await?;
+    let num = req_dao
+        .web_dao
+        .setting
+        .fluent
+        .del(
+            &param.lang,
+            &param.crate_name,
+            &param.key,
+            &user_id,
+            Some(&req_dao.req_env),
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let reload = if num > 0 {
+        fluent_reload(req_dao).await?
+    } else {
+        vec![]
+    };
+    Ok(JsonData::data(json!({ "num": num, "reload": reload })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemFluentReloadParam {
+    //是否通知所有服务器重新加载
+    pub cluster: Option<bool>,
+}
+
+pub async fn system_fluent_reload<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: SystemFluentReloadParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    fluent_check(req_dao).await?;
+    if !param.cluster.unwrap_or(false) {
+        req_dao
+            .web_dao
+            .fluent
+            .reload()
+            .await
+            .map_err(|e| req_dao.fluent_json_data(e.to_fluent_message()))?;
+        return Ok(JsonData::default());
+    }
+    let data = fluent_reload(req_dao).await?;
+    Ok(JsonData::data(json!({ "data": data })))
+}
@@ -0,0 +1,10 @@
+mod cache;
+mod config;
+mod fluent;
+mod node;
+mod task;
+pub use cache::*;
+pub use config::*;
+pub use fluent::*;
+pub use node::*;
+pub use task::*;
@@ -0,0 +1,38 @@
+use crate::{
+    dao::RequestAuthDao,
+    handler::access::AccessAdminNode,
+    {JsonData, JsonResult},
+};
+
+use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
+use serde_json::json;
+
+pub async fn system_node_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessAdminNode {
+                user_id: req_auth.user_data().user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let data = req_dao.web_dao.remote_notify.nodes();
+    Ok(JsonData::data(json!({
+        "hostname": req_dao.web_dao.remote_notify.hostname(),
+        "data": data,
+    })))
+}
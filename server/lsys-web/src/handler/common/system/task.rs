@@ -0,0 +1,80 @@
+use crate::{
+    dao::RequestAuthDao,
+    handler::access::AccessAdminTask,
+    {JsonData, JsonResult},
+};
+
+use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
+use serde::Deserialize;
+use serde_json::json;
+
+pub async fn system_task_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessAdminTask {
+                user_id: req_auth.user_data().user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    let data = req_dao
+        .web_dao
+        .task_monitor
+        .snapshot()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::data(json!({ "data": data })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemTaskReleaseParam {
+    pub task_name: String,
+    pub task_id: String,
+}
+
+pub async fn system_task_release<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: SystemTaskReleaseParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .user
+        .rbac_dao
+        .rbac
+        .check(
+            &AccessAdminTask {
+                user_id: req_auth.user_data().user_id,
+            },
+            None,
+        )
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    req_dao
+        .web_dao
+        .task_monitor
+        .task_release(&param.task_name, &param.task_id)
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    Ok(JsonData::default())
+}
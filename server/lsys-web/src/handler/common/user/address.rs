@@ -21,7 +21,7 @@ pub struct AddressAddParam {
     pub mobile: String,
 }
 
-pub async fn user_address_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_address_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: AddressAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -106,7 +106,7 @@ pub struct AddressEditParam {
     pub name: String,
     pub mobile: String,
 }
-pub async fn user_address_edit<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_address_edit<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: AddressEditParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -193,7 +193,7 @@ pub async fn user_address_edit<'t, T: SessionTokenData, D: SessionData, S: UserS
 pub struct AddressDeleteParam {
     pub address_id: u64,
 }
-pub async fn user_address_delete<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_address_delete<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: AddressDeleteParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
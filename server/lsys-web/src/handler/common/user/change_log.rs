@@ -15,7 +15,7 @@ pub struct ChangeLogsListParam {
     pub add_user_id: Option<u64>,
 }
 
-pub async fn change_logs_list<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn change_logs_list<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: ChangeLogsListParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -12,7 +12,7 @@ use serde_json::json;
 pub struct EmailAddParam {
     pub email: String,
 }
-pub async fn user_email_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_email_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: EmailAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -64,7 +64,7 @@ pub struct EmailSendCodeParam {
     pub email: String,
     pub captcha: CaptchaParam,
 }
-pub async fn user_email_send_code<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_email_send_code<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: EmailSendCodeParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -172,7 +172,7 @@ pub struct EmailConfirmParam {
     pub email_id: u64,
     pub code: String,
 }
-pub async fn user_email_confirm<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_email_confirm<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: EmailConfirmParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -217,7 +217,7 @@ pub async fn user_email_confirm<'t, T: SessionTokenData, D: SessionData, S: User
 pub struct EmailDeleteParam {
     pub email_id: u64,
 }
-pub async fn user_email_delete<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_email_delete<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: EmailDeleteParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -278,7 +278,7 @@ pub async fn user_email_delete<'t, T: SessionTokenData, D: SessionData, S: UserS
 pub struct EmailListDataParam {
     pub status: Option<Vec<i8>>,
 }
-pub async fn user_email_list_data<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_email_list_data<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: EmailListDataParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -14,7 +14,7 @@ use serde_json::json;
 pub struct ExternalDeleteParam {
     pub ext_id: u64,
 }
-pub async fn user_external_delete<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_external_delete<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: ExternalDeleteParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
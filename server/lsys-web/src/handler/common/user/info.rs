@@ -128,7 +128,7 @@ pub struct InfoSetUserInfoParam {
     pub headimg: Option<String>,
     pub birthday: Option<String>,
 }
-pub async fn user_info_set_data<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_info_set_data<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: InfoSetUserInfoParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -213,7 +213,7 @@ pub async fn user_info_set_data<'t, T: SessionTokenData, D: SessionData, S: User
     Ok(JsonData::default())
 }
 
-pub async fn password_last_modify<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn password_last_modify<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
     let req_auth = req_dao
@@ -275,7 +275,7 @@ pub async fn password_last_modify<'t, T: SessionTokenData, D: SessionData, S: Us
     })))
 }
 
-pub async fn user_delete<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_delete<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
     let req_auth = req_dao
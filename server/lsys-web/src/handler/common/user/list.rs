@@ -26,7 +26,7 @@ pub struct UserSearchParam {
     pub email: Option<Vec<i8>>,
     pub mobile: Option<Vec<i8>>,
 }
-pub async fn user_search<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_search<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: UserSearchParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -144,7 +144,7 @@ pub async fn user_search<'t, T: SessionTokenData, D: SessionData, S: UserSession
             Option<Vec<UserMobileModel>>,
         ),
     )>>();
-    user_data.sort_by(|a, b| b.0.cmp(&a.0));
+    user_data.sort_by_key(|a| std::cmp::Reverse(a.0));
 
     let mut out = Vec::with_capacity(user_data.len());
     for (uid, udat) in user_data {
@@ -217,7 +217,7 @@ pub struct UserIdSearchParam {
     pub mobile: Option<Vec<i8>>,
 }
 
-pub async fn user_id_search<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_id_search<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: UserIdSearchParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
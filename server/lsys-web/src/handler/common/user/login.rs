@@ -8,7 +8,7 @@ use serde::Deserialize;
 use serde_json::json;
 
 /// logout
-pub async fn user_logout<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_logout<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
     req_dao
@@ -29,7 +29,7 @@ pub struct LoginHistoryParam {
     pub is_login: Option<i8>,
     pub page: Option<PageParam>,
 }
-pub async fn user_login_history<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_login_history<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: LoginHistoryParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -14,7 +14,7 @@ pub struct MobileAddParam {
     pub mobile: String,
     pub code: Option<String>,
 }
-pub async fn user_mobile_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_mobile_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: MobileAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -189,7 +189,7 @@ pub struct MobileConfirmParam {
     pub mobile_id: u64,
     pub code: String,
 }
-pub async fn user_mobile_confirm<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_mobile_confirm<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: MobileConfirmParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -231,7 +231,7 @@ pub async fn user_mobile_confirm<'t, T: SessionTokenData, D: SessionData, S: Use
 pub struct MobileDeleteParam {
     pub mobile_id: u64,
 }
-pub async fn user_mobile_delete<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_mobile_delete<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: MobileDeleteParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
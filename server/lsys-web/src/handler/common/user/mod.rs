@@ -11,6 +11,7 @@ mod password;
 mod rbac_access;
 mod rbac_res;
 mod rbac_role;
+mod rbac_transfer;
 mod register;
 #[cfg(feature = "area")]
 pub use address::*;
@@ -25,4 +26,5 @@ pub use password::*;
 pub use rbac_access::*;
 pub use rbac_res::*;
 pub use rbac_role::*;
+pub use rbac_transfer::*;
 pub use register::*;
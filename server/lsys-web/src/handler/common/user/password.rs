@@ -13,7 +13,7 @@ pub struct SetPasswordParam {
     pub old_password: Option<String>,
     pub new_password: String,
 }
-pub async fn user_set_password<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_set_password<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: &SetPasswordParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -285,7 +285,7 @@ pub async fn user_reset_password_from_mobile<
     .await
 }
 
-async fn reset_password<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+async fn reset_password<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     user_id: &u64,
     new_password: &str,
     from_type: &String,
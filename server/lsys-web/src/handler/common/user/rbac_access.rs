@@ -1,11 +1,12 @@
 use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
 
 use crate::handler::common::rbac::{
-    rbac_access_check, rbac_menu_check, RbacAccessParam, RbacMenuParam,
+    rbac_access_check, rbac_access_explain, rbac_menu_check, rbac_op_access, RbacAccessParam,
+    RbacExplainParam, RbacMenuParam, RbacOpAccessParam,
 };
 use crate::{dao::RequestAuthDao, JsonData, JsonResult};
 
-pub async fn user_access_check<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_access_check<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RbacAccessParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -25,7 +26,7 @@ pub async fn user_access_check<'t, T: SessionTokenData, D: SessionData, S: UserS
     .await
 }
 
-pub async fn user_menu_check<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_menu_check<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RbacMenuParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -43,3 +44,43 @@ pub async fn user_menu_check<'t, T: SessionTokenData, D: SessionData, S: UserSes
     )
     .await
 }
+
+pub async fn user_access_explain<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: RbacExplainParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_access_explain(
+        param,
+        &req_dao.web_dao.user.rbac_dao,
+        req_auth.user_data().user_id,
+        req_dao,
+    )
+    .await
+}
+
+pub async fn user_op_access<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: RbacOpAccessParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_op_access(
+        param,
+        &req_dao.web_dao.user.rbac_dao,
+        req_auth.user_data().user_id,
+        req_dao,
+    )
+    .await
+}
@@ -7,7 +7,7 @@ use crate::{
     {JsonData, JsonResult},
 };
 use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
-pub async fn user_res_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_res_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: ResAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -27,7 +27,7 @@ pub async fn user_res_add<'t, T: SessionTokenData, D: SessionData, S: UserSessio
     .await
 }
 
-pub async fn user_res_edit<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_res_edit<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: ResEditParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -47,7 +47,7 @@ pub async fn user_res_edit<'t, T: SessionTokenData, D: SessionData, S: UserSessi
     .await
 }
 
-pub async fn user_res_delete<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_res_delete<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: ResDeleteParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -67,7 +67,7 @@ pub async fn user_res_delete<'t, T: SessionTokenData, D: SessionData, S: UserSes
     .await
 }
 
-pub async fn user_res_list_data<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_res_list_data<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: ResListDataParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -87,7 +87,7 @@ pub async fn user_res_list_data<'t, T: SessionTokenData, D: SessionData, S: User
     .await
 }
 
-pub async fn user_res_tags<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_res_tags<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: ResTagsParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
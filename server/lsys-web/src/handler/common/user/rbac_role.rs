@@ -1,16 +1,17 @@
 use crate::handler::common::rbac::{
     rbac_role_add, rbac_role_add_user, rbac_role_delete, rbac_role_delete_user, rbac_role_edit,
-    rbac_role_list_data, rbac_role_list_user, rbac_role_tags, rbac_user_relation_data,
-    rbac_user_role_options, RoleAddParam, RoleAddUserParam, RoleDeleteParam, RoleDeleteUserParam,
-    RoleEditParam, RoleListDataParam, RoleListUserParam, RoleOptionsParam, RoleRelationDataParam,
-    RoleTagsParam,
+    rbac_role_expiring_user, rbac_role_list_data, rbac_role_list_user, rbac_role_parent,
+    rbac_role_set_parent, rbac_role_tags, rbac_user_relation_data, rbac_user_role_options,
+    RoleAddParam, RoleAddUserParam, RoleDeleteParam, RoleDeleteUserParam, RoleEditParam,
+    RoleExpiringUserParam, RoleListDataParam, RoleListUserParam, RoleOptionsParam, RoleParentParam,
+    RoleRelationDataParam, RoleSetParentParam, RoleTagsParam,
 };
 use crate::{
     dao::RequestAuthDao,
     {JsonData, JsonResult},
 };
 use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
-pub async fn user_role_add<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_add<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleAddParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -30,7 +31,7 @@ pub async fn user_role_add<'t, T: SessionTokenData, D: SessionData, S: UserSessi
     .await
 }
 
-pub async fn user_role_edit<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_edit<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleEditParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -50,7 +51,7 @@ pub async fn user_role_edit<'t, T: SessionTokenData, D: SessionData, S: UserSess
     .await
 }
 
-pub async fn user_role_list_user<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_list_user<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleListUserParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -70,7 +71,7 @@ pub async fn user_role_list_user<'t, T: SessionTokenData, D: SessionData, S: Use
     .await
 }
 
-pub async fn user_role_add_user<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_add_user<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleAddUserParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -110,7 +111,7 @@ pub async fn user_role_delete_user<T: SessionTokenData, D: SessionData, S: UserS
     .await
 }
 
-pub async fn user_role_delete<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_delete<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleDeleteParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -130,7 +131,71 @@ pub async fn user_role_delete<'t, T: SessionTokenData, D: SessionData, S: UserSe
     .await
 }
 
-pub async fn user_role_list_data<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_expiring_user<
+    T: SessionTokenData,
+    D: SessionData,
+    S: UserSession<T, D>,
+>(
+    param: RoleExpiringUserParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_role_expiring_user(
+        param,
+        &req_dao.web_dao.user.rbac_dao,
+        req_auth.user_data().user_id,
+        req_dao,
+    )
+    .await
+}
+
+pub async fn user_role_set_parent<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: RoleSetParentParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_role_set_parent(
+        param,
+        &req_dao.web_dao.user.rbac_dao,
+        req_auth.user_data().user_id,
+        req_dao,
+    )
+    .await
+}
+
+pub async fn user_role_parent<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: RoleParentParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_role_parent(
+        param,
+        &req_dao.web_dao.user.rbac_dao,
+        req_auth.user_data().user_id,
+        req_dao,
+    )
+    .await
+}
+
+pub async fn user_role_list_data<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleListDataParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -150,7 +215,7 @@ pub async fn user_role_list_data<'t, T: SessionTokenData, D: SessionData, S: Use
     .await
 }
 
-pub async fn user_role_tags<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_tags<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleTagsParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -170,7 +235,7 @@ pub async fn user_role_tags<'t, T: SessionTokenData, D: SessionData, S: UserSess
     .await
 }
 
-pub async fn user_role_options<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_role_options<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleOptionsParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -190,7 +255,7 @@ pub async fn user_role_options<'t, T: SessionTokenData, D: SessionData, S: UserS
     .await
 }
 
-pub async fn user_relation_data<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_relation_data<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RoleRelationDataParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
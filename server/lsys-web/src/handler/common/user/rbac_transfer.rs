@@ -0,0 +1,45 @@
+use crate::handler::common::rbac::{rbac_export, rbac_import, RbacExportParam, RbacImportParam};
+use crate::{
+    dao::RequestAuthDao,
+    {JsonData, JsonResult},
+};
+use lsys_user::dao::auth::{SessionData, SessionTokenData, UserSession};
+pub async fn user_rbac_export<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: RbacExportParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_export(
+        param,
+        &req_dao.web_dao.user.rbac_dao,
+        req_auth.user_data().user_id,
+        req_dao,
+    )
+    .await
+}
+
+pub async fn user_rbac_import<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+    param: RbacImportParam,
+    req_dao: &RequestAuthDao<T, D, S>,
+) -> JsonResult<JsonData> {
+    let req_auth = req_dao
+        .user_session
+        .read()
+        .await
+        .get_session_data()
+        .await
+        .map_err(|e| req_dao.fluent_json_data(e))?;
+    rbac_import(
+        param,
+        &req_dao.web_dao.user.rbac_dao,
+        req_auth.user_data().user_id,
+        req_dao,
+    )
+    .await
+}
@@ -15,7 +15,7 @@ pub struct RegFromNameParam {
     pub name: String,
     pub password: String,
 }
-pub async fn user_reg_from_name<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_reg_from_name<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RegFromNameParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -200,7 +200,7 @@ pub struct RegFromEmailParam {
     pub nikename: String,
 }
 
-pub async fn user_reg_from_email<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_reg_from_email<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RegFromEmailParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
@@ -256,7 +256,7 @@ pub struct RegFromMobileParam {
     pub nikename: String,
 }
 
-pub async fn user_reg_from_mobile<'t, T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
+pub async fn user_reg_from_mobile<T: SessionTokenData, D: SessionData, S: UserSession<T, D>>(
     param: RegFromMobileParam,
     req_dao: &RequestAuthDao<T, D, S>,
 ) -> JsonResult<JsonData> {
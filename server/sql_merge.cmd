@@ -1 +1 @@
-powershell -Command "if (Test-Path 'tables.sql') { Remove-Item 'tables.sql' }; Get-ChildItem -Directory | ForEach-Object { Get-ChildItem -Path $_.FullName -Filter *.sql | ForEach-Object { Get-Content $_.FullName | Add-Content 'tables.sql'; Add-Content 'tables.sql' '' } }"
\ No newline at end of file
+powershell -Command "if (Test-Path 'tables.sql') { Remove-Item 'tables.sql' }; Get-ChildItem -Directory | ForEach-Object { Get-ChildItem -Path $_.FullName -Filter *.sql | ForEach-Object { Get-Content $_.FullName | Add-Content 'tables.sql'; Add-Content 'tables.sql' '' }; $m = Join-Path $_.FullName 'migrations'; if (Test-Path $m) { Get-ChildItem $m -Filter *.sql | Sort-Object Name | ForEach-Object { Get-Content $_.FullName | Add-Content 'tables.sql'; Add-Content 'tables.sql' '' } } }"
\ No newline at end of file
@@ -11,6 +11,13 @@ for d in */; do
            echo "" >> "$outputFile"
         fi
     done
+    #各模块 migrations 目录下为 tables.sql 之后的数据库迁移,按文件名顺序合并
+    for f in "$d"migrations/*.sql; do
+        if [ -e  "$f" ]; then
+           cat "$f" >> "$outputFile"
+           echo "" >> "$outputFile"
+        fi
+    done
 	
 done
 